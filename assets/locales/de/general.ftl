@@ -5,7 +5,6 @@ update-background = Hintergrundbild aktualisieren
 update-background-description = Lädt das offizielle Hintergrundbild herunter für den Launcher. SIe können es deaktiverien für ein benutzerdefiniertes Bild.
 
 launcher-language = Launcher Sprache
-launcher-language-description = Gilt nach Neustart
 
 game-edition = Spiel-Edition
 global = Global
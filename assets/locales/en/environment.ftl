@@ -1,7 +1,47 @@
 environment = Environment
 game-command = Game command
 game-command-description = Command used to launch the game. Placeholder %command% is generated automatically by the launcher. For example: gamemoderun '%command%'
+insert-placeholder = Insert placeholder
+placeholder-command = Wine with the game executable and its arguments
+placeholder-bash-command = Same as %command%
+placeholder-windows-command = Game executable and its arguments without wine
+placeholder-game = Game folder
+placeholder-prefix = Wine prefix folder
+placeholder-build = Selected wine build folder
+placeholder-temp = Temporary files folder
+placeholder-launcher = Launcher folder
+game-command-issues = Command issues
+game-command-no-command = Command doesn't contain %command% so the game won't be started
+game-command-unknown-placeholder = Unknown placeholder {$placeholder} won't be replaced
+game-command-unclosed-quote = Quote {$quote} is not closed
+final-command = Final command
+final-command-description = Command which will be executed with environment variables, sandbox and gamescope applied
+final-command-failed = Failed to build the command: {$error}
+hooks = Hooks
+hooks-description = Shell commands executed before the game launch and after its exit. HRL_HOOK, HRL_GAME_PATH, HRL_GAME_VERSION, HRL_GAME_EDITION, HRL_PREFIX_PATH, HRL_WINE_NAME, HRL_WINE_PATH and HRL_LAUNCHER_FOLDER variables describe the launch. Post-exit hook also gets HRL_GAME_STATUS
+pre-launch-hook = Pre-launch command
+post-exit-hook = Post-exit command
+hooks-timeout = Timeout
+hooks-no-timeout = No timeout
+hooks-timeout-seconds = {$seconds} seconds
+hooks-timeout-minutes = {$minutes ->
+    [1] 1 minute
+    *[other] {$minutes} minutes
+}
+abort-on-hook-failure = Abort on failure
+abort-on-hook-failure-description = Don't launch the game if the pre-launch command failed or didn't finish in time
+env-presets = Presets
+env-presets-description = Groups of variables which can be toggled together. Changed variables are kept when the preset is disabled
+preset-dxvk-debug = DXVK debug
+preset-nvidia-tweaks = Nvidia tweaks
+preset-mesa-aco = Mesa ACO
 new-variable = New variable
 name = Name
 value = Value
 add = Add
+variables = Variables
+variable-enabled = Use this variable
+import-env-file = Import from .env file
+export-env-file = Export to .env file
+env-file-imported = Imported variables: {$count}
+env-file-exported = Variables saved to {$path}
@@ -9,8 +9,10 @@ wish-url-opening-error = Could not open wishes url
 wine-run-error = Failed to run {$executable} executable using wine
 
 game-launching-failed = Failed to launch game
+game-kill-failed = Failed to kill game
 failed-get-selected-wine = Failed to get selected wine version
 downloading-failed = Downloading failed
+downloading-cancelled = Downloading cancelled
 unpacking-failed = Unpacking failed
 
 game-file-repairing-error = Failed to repair game file
@@ -20,6 +22,15 @@ background-downloading-failed = Failed to download background picture
 components-index-sync-failed = Failed to sync components index
 components-index-verify-failed = Failed to verify components index
 config-update-error = Failed to save config
+config-section-reset-failed = Failed to reset config section
+config-backup-restore-failed = Failed to restore config backup
+config-still-invalid = Config file is still invalid
+launcher-restart-failed = Failed to restart the launcher
+config-backups-list-failed = Failed to list config backups
+config-reload-invalid = Edited config file is invalid and wasn't loaded
+config-reload-failed = Failed to reload config file
+settings-reload-invalid = Edited launcher settings file is invalid
+config-backup-create-failed = Failed to create config backup
 wine-prefix-update-failed = Failed to update wine prefix
 dxvk-install-failed = Failed to install DXVK
 voice-package-deletion-error = Failed to delete voice package
@@ -53,3 +64,18 @@ game-session-update-failed = Failed to update game session
 game-session-remove-failed = Failed to remove game session
 game-session-set-current-failed = Failed to set current game session
 game-session-apply-failed = Failed to apply game session
+language-change-failed = Failed to change launcher language
+game-language-apply-failed = Failed to apply game language
+settings-update-failed = Failed to save launcher settings
+warps-load-failed = Failed to load warps history
+warps-fetch-failed = Failed to fetch warps history
+warps-export-failed = Failed to export warps history
+warps-import-failed = Failed to import warps history
+shortcut-creation-failed = Failed to create shortcut
+pre-launch-hook-failed = Pre-launch hook failed
+post-exit-hook-failed = Post-exit hook failed
+dry-run-failed = Failed to build launch command
+env-file-import-failed = Failed to import environment variables
+env-file-export-failed = Failed to export environment variables
+env-variable-add-failed = Failed to add environment variable
+env-variable-wrong-name = Wrong variable name: {$name}. Only latin letters, digits and underscores are allowed, and it can't start with a digit
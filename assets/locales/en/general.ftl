@@ -1,16 +1,46 @@
 appearance = Appearance
 modern = Modern
 classic = Classic
+big-picture = Big picture mode
+big-picture-description = Fullscreen layout with large buttons and gamepad navigation
+big-picture-auto = Automatic in game mode
+big-picture-enabled = Enabled
+big-picture-disabled = Disabled
 update-background = Update background picture
 update-background-description = Download official background picture for the launcher. You can disable it to use your custom image instead
 
 launcher-language = Launcher language
-launcher-language-description = Applies after restart
 
 game-edition = Game edition
 global = Global
 china = China
 
+game-language = Game language
+game-language-description = Languages set in the game settings. Voice pack of the selected language is downloaded by the game
+game-text-language = Text language
+game-voice-language = Voice language
+game-language-keep = Don't change
+
+game-language--cn = Chinese (Simplified)
+game-language--cht = Chinese (Traditional)
+game-language--en = English
+game-language--jp = Japanese
+game-language--kr = Korean
+game-language--de = German
+game-language--es = Spanish
+game-language--fr = French
+game-language--id = Indonesian
+game-language--pt = Portuguese
+game-language--ru = Russian
+game-language--th = Thai
+game-language--vi = Vietnamese
+
+game-voice-language--cn = Chinese
+game-voice-language--en = English
+game-voice-language--jp = Japanese
+game-voice-language--kr = Korean
+game-voice-pack-missing = {$language} voice pack is not downloaded yet. The game will download it on the next launch
+
 game-environment = Game environment
 game-environment-description = Get specific features like additional payment methods
 
@@ -62,6 +92,36 @@ apply-xlua-patch = Apply xlua patch
 ask-superuser-permissions = Ask superuser permissions
 ask-superuser-permissions-description = Launcher will use them to automatically update your hosts file. This is not needed in flatpak edition
 
+background-mode = Background mode
+tray-icon = Tray icon
+tray-icon-description = Show launcher icon in the system tray while its window is hidden
+keep-in-background = Keep in background
+keep-in-background-description = Hide launcher to the tray when its window is closed. Available predownloads will be started automatically
+notifications = Notifications
+notifications-description = Notify about finished downloads and available updates when the launcher window is not focused
+
+update-checks = Update checks
+update-checks-description = Check game and main patch updates while the launcher is running
+update-check-interval = Check interval
+update-check-disabled = Disabled
+update-check-minutes = Every {$minutes} minutes
+update-check-hours = {$hours ->
+    [1] Every hour
+    *[other] Every {$hours} hours
+}
+auto-predownload = Predownload automatically
+auto-predownload-description = Start predownloads found by the update checks
+predownload-from = Predownload from
+predownload-to = Predownload until
+predownload-to-description = Same hours allow predownloading at any time
+
+config-backups = Restore previous settings
+config-backups-description = Config is backed up automatically when important settings like paths, game edition or wine version are changed
+create-backup = Create backup
+config-backup-identical = Same as current settings
+config-backup-changes = {$count} changes
+config-backup-restored = Settings restored. Some of them will be applied after restart
+
 wine-tools = Wine tools
 command-line = Command line
 registry-editor = Registry editor
@@ -69,3 +129,18 @@ explorer = Explorer
 task-manager = Task manager
 configuration = Configuration
 debugger = Debugger
+
+shortcuts = Shortcuts
+shortcuts-description = Add the launcher and the game to the applications menu or Steam
+launcher-shortcut = Launcher shortcut
+launcher-shortcut-description = Add the launcher to the applications menu
+game-shortcut = Game shortcut
+game-shortcut-description = Add the applications menu entries which launch the game directly, one for each game session
+steam-shortcut = Add to Steam
+steam-shortcut-description = Add the game as a non-Steam game, once for each game session. Steam should be closed
+shortcut-created = Shortcut created
+steam-shortcut-added = {$users ->
+    [1] Added for 1 Steam user
+    *[other] Added for {$users} Steam users
+}
+steam-is-running = Close Steam first. It overwrites shortcuts on exit
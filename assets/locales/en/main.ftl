@@ -13,12 +13,43 @@ launcher-folder = Launcher folder
 game-folder = Game folder
 config-file = Config file
 debug-file = Debug file
-wish-url = Open wishes
+dry-run = Dry run
+wish-url = Warps URL
+warps = Warps history
 about = About
 
+wish-url-age = Cached {$age} ago. Expires in {$expires}
+wish-url-expired = Cached {$age} ago and probably expired. Open warps history in the game to refresh it
+wish-url-copied = Warps URL copied to clipboard
+dry-run-description = Command and environment the game would be started with. Nothing was launched
+dry-run-copied = Launch command copied to clipboard
+duration-hours-minutes = {$hours} h {$minutes} min
+
+tray-game-running = Game is running
+tray-background = Running in background
+tray-show-launcher = Show launcher
+tray-kill-game = Kill game
+tray-quit-after-game = Quit after game
+tray-quit = Quit
+
+notification-game-update = Game update
+notification-update-available = Game version {$version} is available
+notification-predownload-available = Game version {$version} is available for predownloading
+notification-download-finished = Download finished
+notification-predownload-finished = Predownload finished
+notification-wine-installed = Wine installed
+notification-patch-status = Main patch status changed
+notification-patch-not-available = Main patch is not available
+notification-patch-outdated = Main patch {$version} is outdated
+notification-patch-testing = Main patch {$version} is in testing
+notification-patch-available = Main patch {$version} is available
+notification-patch-applied = Main patch applied
+
 
 close = Close
 save = Save
+open = Open
+copy = Copy
 continue = Continue
 resume = Resume
 exit = Exit
@@ -60,11 +91,58 @@ create-prefix = Create prefix
 update = Update
 download = Download
 predownload-update = Pre-download {$version} update ({$size})
+predownload = Pre-download
 
 main-window--patch-unavailable-tooltip = Patch servers are unavailable and launcher can't verify the game's patching status. You're allowed to run the game on your own risk
 main-window--patch-outdated-tooltip = Patch is outdated or in preparation state, so unavailable for usage. Return back later to see its status
 main-window--version-outdated-tooltip = Version is too outdated and can't be updated
 
+config-recovery = Config recovery
+config-recovery-title = Config file is broken
+config-recovery-message =
+    Launcher can't use your config file. Reset the sections listed below, restore the last working config or fix the file manually
+config-recovery-issues = Found issues
+config-file-unreadable = Failed to read config file
+config-syntax-error = Syntax error at line {$line}, column {$column}
+reset-config-section = Reset "{$section}"
+restore-backup = Restore backup
+restore-backup-description = Restore the last config file the launcher was successfully started with
+
+config-reloaded = Config file was reloaded
+config-reload-conflicts = Config file was reloaded. Some of your unsaved changes were overwritten
+settings-reloaded = Launcher settings file was reloaded
+
 preferences = Preferences
 general = General
 enhancements = Enhancements
+
+warps-uid = UID {$uid}
+warps-fetch = Fetch new warps
+warps-searching-url = Searching warps URL
+warps-fetching = Fetching {$banner}: {$count}
+warps-fetched = New warps: {$count}
+warps-empty = No warps history
+warps-empty-description = Open warps history in the game and then press the refresh button to fetch it
+warps-total = Total warps: {$count}
+warps-pity-5 = Warps since the last 5 star
+warps-pity-4 = Warps since the last 4 star
+warps-four-stars = 4 star items
+warps-average-5 = Average warps per 5 star
+warps-five-stars = 5 star items
+warps-pulls = {$count} warps
+warps-export-import = Export and import
+warps-export-csv = Export as CSV
+warps-export-srgf = Export as SRGF
+warps-export-uigf = Export as UIGF
+warps-import = Import from file
+warps-import-files = Warps history files
+warps-format--csv = CSV files
+warps-format--srgf = SRGF files
+warps-format--uigf = UIGF files
+warps-exported = Warps history saved to {$path}
+warps-imported = Imported warps: {$count}
+
+warp-banner--regular = Stellar Warp
+warp-banner--departure = Departure Warp
+warp-banner--character = Character Event Warp
+warp-banner--light-cone = Light Cone Event Warp
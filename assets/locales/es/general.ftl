@@ -5,7 +5,6 @@ update-background = Actualizar imagen de fondo
 update-background-description = Descarga la imagen de fondo oficial para el launcher. Puedes desactivarla para usar tu propia imagen en su lugar.
 
 launcher-language = Idioma del launcher
-launcher-language-description = Se aplica tras un reinicio.
 
 game-edition = Edición del Juego
 global = Global
@@ -5,7 +5,6 @@ update-background = Mise à jour automatique de l'image de fond
 update-background-description = Télécharger l'image de fond du launcher officiel. Vous pouvez la désactiver pour utiliser une image personnalisée à la place
 
 launcher-language = Langue du launcher
-launcher-language-description = S'applique après un redémarrage
 
 game-edition = Game edition
 global = Global
@@ -5,7 +5,6 @@ update-background = Perbarui gambar latar belakang
 update-background-description = Unduh gambar resmi untuk launcher. Anda bisa menonaktifkannya dan menggunakan gambar Anda sendiri
 
 launcher-language = Tampilan bahasa Launcher
-launcher-language-description = Efektif setelah muat ulang
 
 game-edition = Edisi game
 global = Global
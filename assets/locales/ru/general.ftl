@@ -5,7 +5,6 @@ update-background = Обновлять фоновое изображение
 update-background-description = Скачивать фоновое изображение для лаунчера. Вы можете отключить это чтобы использовать ваше собственное изображение
 
 launcher-language = Язык лаунчера
-launcher-language-description = Применяется после перезапуска
 
 game-edition = Редакция игры
 global = Глобальная
@@ -5,7 +5,6 @@ update-background = Arkaplan imajını güncelle
 update-background-description = İstemci için resmi arka plan resmini indirin. Bunun yerine özel resminizi kullanmak için devre dışı bırakabilirsiniz.
 
 launcher-language = İstemci dili
-launcher-language-description = Yeniden başlattıktan sonra uygulanır
 
 game-edition = Oyun Sürümü
 global = Evrensel
@@ -5,7 +5,6 @@ update-background = 更新背景图片
 update-background-description = 下载官方启动器背景图片。你可以关闭这个选项并使用你自己的图片
 
 launcher-language = 启动器语言
-launcher-language-description = 重启后生效
 
 game-edition = 游戏版本
 global = 国际服
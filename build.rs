@@ -1,7 +1,131 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
 fn main() {
 	glib_build_tools::compile_resources(
 		&["assets"],
 		"assets/resources.xml",
 		"resources.gresource",
 	);
+
+	generate_locales_index();
+}
+
+/// Generate `locales.rs` with lists of all the messages
+/// from `assets/locales` and their usages in `src`
+fn generate_locales_index() {
+	println!("cargo:rerun-if-changed=assets/locales");
+	println!("cargo:rerun-if-changed=src");
+
+	let mut locales = BTreeMap::new();
+
+	for entry in std::fs::read_dir("assets/locales").unwrap().flatten() {
+		if entry.path().is_dir() {
+			let mut keys = Vec::new();
+
+			for file in std::fs::read_dir(entry.path()).unwrap().flatten() {
+				keys.extend(read_keys(&file.path()));
+			}
+
+			keys.sort();
+
+			locales.insert(entry.file_name().to_string_lossy().to_string(), keys);
+		}
+	}
+
+	let common_keys = read_keys(Path::new("assets/locales/common.ftl"));
+
+	let mut sources = Vec::new();
+
+	read_sources(Path::new("src"), &mut sources);
+
+	// Messages which are mentioned anywhere in the code
+	let mut referenced = locales.get("en")
+		.map(|keys| keys.iter()
+			.filter(|key| sources.iter().any(|(_, text)| text.contains(&format!("\"{key}\""))))
+			.cloned()
+			.collect::<Vec<_>>())
+		.unwrap_or_default();
+
+	referenced.sort();
+
+	// Messages requested by `tr` and `tr_args` calls
+	let mut requested = Vec::new();
+
+	for (path, text) in &sources {
+		for (i, line) in text.lines().enumerate() {
+			for call in ["tr(\"", "tr_args(\""] {
+				for (start, _) in line.match_indices(call) {
+					// Skip calls like `push_str("`
+					if line[..start].ends_with(|char: char| char.is_ascii_alphanumeric() || char == '_') {
+						continue;
+					}
+
+					let key = &line[start + call.len()..];
+
+					if let Some(end) = key.find('"') {
+						requested.push((key[..end].to_string(), path.clone(), i + 1));
+					}
+				}
+			}
+		}
+	}
+
+	let mut output = String::new();
+
+	output += "/// Messages defined in every locale folder\n";
+	output += "pub const LOCALE_KEYS: &[(&str, &[&str])] = &[\n";
+
+	for (locale, keys) in &locales {
+		output += &format!("\t({locale:?}, &{keys:?}),\n");
+	}
+
+	output += "];\n\n";
+
+	output += "/// Messages defined in the core locale shared by all the languages\n";
+	output += &format!("pub const COMMON_KEYS: &[&str] = &{common_keys:?};\n\n");
+
+	output += "/// English messages mentioned in the source code\n";
+	output += &format!("pub const REFERENCED_KEYS: &[&str] = &{referenced:?};\n\n");
+
+	output += "/// Messages requested in the source code with their file and line\n";
+	output += "pub const REQUESTED_KEYS: &[(&str, &str, usize)] = &[\n";
+
+	for (key, path, line) in requested {
+		output += &format!("\t({key:?}, {path:?}, {line}),\n");
+	}
+
+	output += "];\n";
+
+	let out_dir = std::env::var("OUT_DIR").unwrap();
+
+	std::fs::write(Path::new(&out_dir).join("locales.rs"), output).unwrap();
+}
+
+/// Read messages ids from the fluent file
+fn read_keys(path: &Path) -> Vec<String> {
+	std::fs::read_to_string(path)
+		.unwrap_or_default()
+		.lines()
+		.filter(|line| line.starts_with(|char: char| char.is_ascii_alphabetic()))
+		.filter_map(|line| line.split_once('='))
+		.map(|(key, _)| key.trim().to_string())
+		.collect()
+}
+
+fn read_sources(path: &Path, sources: &mut Vec<(String, String)>) {
+	for entry in std::fs::read_dir(path).unwrap().flatten() {
+		let path = entry.path();
+
+		if path.is_dir() {
+			read_sources(&path, sources);
+		}
+
+		else if path.extension().map(|ext| ext == "rs").unwrap_or_default() {
+			sources.push((
+				path.to_string_lossy().to_string(),
+				std::fs::read_to_string(&path).unwrap_or_default()
+			));
+		}
+	}
 }
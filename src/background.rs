@@ -9,10 +9,18 @@ pub struct Background {
     pub hash: String
 }
 
+/// Get launcher API uri with the background picture
+///
+/// Game text language is used if it's selected in the settings, launcher language otherwise
 pub fn get_uri() -> String {
     let uri = concat!("https://hk", "rpg-launcher-static.ho", "yov", "ers", "e.com/hk", "rpg_global/mdk/launcher/api/content?filter_adv=true&key=vplOVX8Vn7cwG8yb&launcher_id=35&language=");
 
-    uri.to_owned() + &crate::i18n::format_lang(&crate::i18n::get_lang())
+    let lang = crate::settings::Settings::get().ok()
+        .and_then(|settings| settings.game.text_language)
+        .map(|lang| lang.api_lang().to_string())
+        .unwrap_or_else(|| crate::i18n::format_lang(&crate::i18n::get_lang()));
+
+    uri.to_owned() + &lang
 }
 
 #[cached::proc_macro::cached(result)]
@@ -0,0 +1,298 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::Config;
+use anime_launcher_sdk::star_rail::consts::config_file;
+
+use serde_json::Value as Json;
+
+use crate::settings::{Settings, SETTINGS_FILE};
+
+/// Amount of the latest backups which will be kept
+pub const BACKUPS_LIMIT: usize = 20;
+
+/// Config fields changing of which will create a backup
+///
+/// Written as JSON pointers. Nested fields are compared as well
+pub const MATERIAL_FIELDS: &[&str] = &[
+    "/launcher/edition",
+    "/launcher/temp",
+    "/game/path",
+    "/game/wine/selected",
+    "/game/wine/builds",
+    "/game/wine/prefix",
+    "/game/dxvk/builds",
+    "/game/command",
+    "/sandbox/enabled",
+    "/components/path",
+    "/patch/path",
+    "/patch/apply"
+];
+
+lazy_static::lazy_static! {
+    /// Path to config backups folder. Standard is `$HOME/.local/share/anime-game-launcher/config-backups`
+    pub static ref BACKUPS_FOLDER: PathBuf = crate::LAUNCHER_FOLDER.join("config-backups");
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+
+    /// UTC timestamp of the backup creation
+    pub timestamp: u64
+}
+
+impl Backup {
+    #[inline]
+    pub fn read(&self) -> anyhow::Result<Json> {
+        Ok(serde_json::from_slice(&std::fs::read(&self.path)?)?)
+    }
+
+    /// Path to the launcher settings saved together with the config
+    #[inline]
+    pub fn settings_path(&self) -> PathBuf {
+        BACKUPS_FOLDER.join(format!("settings-{}.json", self.timestamp))
+    }
+
+    /// Read launcher settings saved together with the config
+    ///
+    /// Returns `None` if there were no settings file when the backup was made
+    pub fn read_settings(&self) -> anyhow::Result<Option<Settings>> {
+        let path = self.settings_path();
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    /// Backup creation time formatted in local timezone
+    pub fn date(&self) -> String {
+        gtk::glib::DateTime::from_unix_local(self.timestamp as i64)
+            .and_then(|date| date.format("%d.%m.%Y %H:%M:%S"))
+            .map(|date| date.to_string())
+            .unwrap_or_else(|_| self.timestamp.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Dot-separated path to the changed field, e.g. `game.wine.selected`
+    pub path: String,
+
+    pub old: Option<Json>,
+    pub new: Option<Json>
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = |value: &Option<Json>| match value {
+            Some(value) => value.to_string(),
+            None => String::from("-")
+        };
+
+        write!(f, "{} -> {}", format(&self.old), format(&self.new))
+    }
+}
+
+/// List existing backups, newest first
+pub fn list() -> anyhow::Result<Vec<Backup>> {
+    if !BACKUPS_FOLDER.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut backups = Vec::new();
+
+    for entry in BACKUPS_FOLDER.read_dir()?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        let timestamp = name.strip_prefix("config-")
+            .and_then(|name| name.strip_suffix(".json"))
+            .and_then(|timestamp| timestamp.parse::<u64>().ok());
+
+        if let Some(timestamp) = timestamp {
+            backups.push(Backup {
+                path: entry.path(),
+                timestamp
+            });
+        }
+    }
+
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    Ok(backups)
+}
+
+/// Copy current config and launcher settings files to the backups folder
+///
+/// Returns `None` if there's no config file yet
+pub fn create() -> anyhow::Result<Option<Backup>> {
+    let config = config_file()?;
+
+    if !config.exists() {
+        return Ok(None);
+    }
+
+    if !BACKUPS_FOLDER.exists() {
+        std::fs::create_dir_all(BACKUPS_FOLDER.as_path())?;
+    }
+
+    let mut timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    // Don't overwrite backups made in the same second
+    while BACKUPS_FOLDER.join(format!("config-{timestamp}.json")).exists() {
+        timestamp += 1;
+    }
+
+    let backup = Backup {
+        path: BACKUPS_FOLDER.join(format!("config-{timestamp}.json")),
+        timestamp
+    };
+
+    std::fs::copy(config, &backup.path)?;
+
+    // Broken settings are not worth restoring
+    if Settings::validate().is_ok() && SETTINGS_FILE.exists() {
+        std::fs::copy(SETTINGS_FILE.as_path(), backup.settings_path())?;
+    }
+
+    tracing::info!("Created config backup: {:?}", backup.path);
+
+    for outdated in list()?.into_iter().skip(BACKUPS_LIMIT) {
+        let settings = outdated.settings_path();
+
+        if settings.exists() {
+            std::fs::remove_file(settings)?;
+        }
+
+        std::fs::remove_file(outdated.path)?;
+    }
+
+    Ok(Some(backup))
+}
+
+/// Create backup if the config materially differs from the latest one or launcher settings were changed
+pub fn create_if_changed() -> anyhow::Result<Option<Backup>> {
+    let config = config_file()?;
+
+    if !config.exists() {
+        return Ok(None);
+    }
+
+    let current = serde_json::from_slice::<Json>(&std::fs::read(config)?)?;
+
+    let settings = Settings::get().ok()
+        .filter(|_| SETTINGS_FILE.exists());
+
+    match list()?.first() {
+        Some(latest) => {
+            let config_changed = is_material_change(&latest.read()?, &current);
+            let settings_changed = latest.read_settings().ok().flatten() != settings;
+
+            if config_changed || settings_changed {
+                create()
+            } else {
+                Ok(None)
+            }
+        }
+
+        None => create()
+    }
+}
+
+/// Save config from the memory to the disk
+///
+/// Backs up the file stored on the disk before if changes are material
+pub fn flush() -> anyhow::Result<()> {
+    let path = config_file()?;
+    let current = serde_json::to_value(Config::get()?)?;
+
+    if path.exists() {
+        let stored = serde_json::from_slice::<Json>(&std::fs::read(path)?)?;
+
+        if is_material_change(&stored, &current) {
+            create()?;
+        }
+    }
+
+    Config::flush()?;
+
+    crate::config_watcher::mark_synced(current);
+
+    Ok(())
+}
+
+/// Replace current config and launcher settings by the backup
+///
+/// Current files are backed up before so this action can be reverted.
+/// Restored files are not marked as synced so the launcher reloads them
+/// the same way as if they were edited outside of it
+pub fn restore(backup: &Backup) -> anyhow::Result<()> {
+    // Make sure the backup is readable before touching the current config
+    backup.read()?;
+
+    let settings = backup.read_settings()?;
+
+    create()?;
+
+    std::fs::copy(&backup.path, config_file()?)?;
+
+    if settings.is_some() {
+        let temp = SETTINGS_FILE.with_extension("json.tmp");
+
+        std::fs::copy(backup.settings_path(), &temp)?;
+        std::fs::rename(temp, SETTINGS_FILE.as_path())?;
+    }
+
+    // Unsaved in-app changes shouldn't be merged into the restored config
+    Config::update(Config::get_raw()?);
+
+    tracing::info!("Restored config backup: {:?}", backup.path);
+
+    Ok(())
+}
+
+/// Check if any of `MATERIAL_FIELDS` differ between two configs
+pub fn is_material_change(old: &Json, new: &Json) -> bool {
+    MATERIAL_FIELDS.iter().any(|field| old.pointer(field) != new.pointer(field))
+}
+
+/// Get list of the changed fields between two configs
+pub fn diff(old: &Json, new: &Json) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    diff_values(String::new(), Some(old), Some(new), &mut changes);
+
+    changes
+}
+
+fn diff_values(path: String, old: Option<&Json>, new: Option<&Json>, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Some(Json::Object(old)), Some(Json::Object(new))) => {
+            let mut keys = old.keys().chain(new.keys()).collect::<Vec<_>>();
+
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+
+                diff_values(path, old.get(key), new.get(key), changes);
+            }
+        }
+
+        (old, new) if old != new => changes.push(Change {
+            path,
+            old: old.cloned(),
+            new: new.cloned()
+        }),
+
+        _ => ()
+    }
+}
@@ -0,0 +1,247 @@
+use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+use anime_launcher_sdk::star_rail::config::Schema;
+use anime_launcher_sdk::star_rail::config::schema::launcher::LauncherStyle;
+use anime_launcher_sdk::star_rail::consts::config_file;
+
+use serde_json::Value as Json;
+use unic_langid::LanguageIdentifier;
+
+use crate::i18n::{supported_languages, negotiate_lang};
+use crate::config_backups::{self, Backup};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssue {
+    /// Config file exists but couldn't be read
+    Unreadable(String),
+
+    /// Config file is not a valid JSON
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String
+    },
+
+    /// Some config field has a value the launcher can't use
+    Field {
+        /// Dot-separated path to the field, e.g. `launcher.language`
+        path: String,
+        message: String
+    }
+}
+
+impl ConfigIssue {
+    /// Top level config section this issue belongs to
+    ///
+    /// Returns `None` if the whole file is broken
+    pub fn section(&self) -> Option<&str> {
+        match self {
+            Self::Field { path, .. } => path.split('.').next().filter(|section| !section.is_empty()),
+
+            _ => None
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unreadable(err) => write!(f, "{err}"),
+            Self::Syntax { line, column, message } => write!(f, "line {line}, column {column}: {message}"),
+            Self::Field { path, message } => write!(f, "{path}: {message}")
+        }
+    }
+}
+
+/// Read config file and check that the launcher will be able to use it
+///
+/// Missing fields are not reported because they're filled with defaults by the SDK
+pub fn validate() -> Vec<ConfigIssue> {
+    let path = match config_file() {
+        Ok(path) => path,
+        Err(err) => return vec![ConfigIssue::Unreadable(err.to_string())]
+    };
+
+    // Config file will be created by the SDK
+    if !path.exists() {
+        return vec![];
+    }
+
+    match std::fs::read(path) {
+        Ok(config) => validate_raw(&config),
+        Err(err) => vec![ConfigIssue::Unreadable(err.to_string())]
+    }
+}
+
+/// Check config file content
+pub fn validate_raw(config: &[u8]) -> Vec<ConfigIssue> {
+    match serde_json::from_slice::<Json>(config) {
+        Ok(config) => validate_json(&config),
+
+        Err(err) => vec![ConfigIssue::Syntax {
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string()
+        }]
+    }
+}
+
+/// Check parsed config fields
+pub fn validate_json(config: &Json) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    if !config.is_object() {
+        issues.push(ConfigIssue::Field {
+            path: String::new(),
+            message: String::from("config must be an object")
+        });
+
+        return issues;
+    }
+
+    let mut check = |path: &str, validator: fn(&Json) -> Result<(), String>| {
+        let mut value = config;
+
+        for key in path.split('.') {
+            match value.get(key) {
+                Some(inner) => value = inner,
+                None => return
+            }
+        }
+
+        if let Err(message) = validator(value) {
+            issues.push(ConfigIssue::Field {
+                path: path.to_string(),
+                message
+            });
+        }
+    };
+
+    for section in ["launcher", "game", "game.path", "game.wine", "game.dxvk", "sandbox", "components", "patch"] {
+        check(section, is_object);
+    }
+
+    check("launcher.language", is_language);
+    check("launcher.edition", is_edition);
+    check("launcher.style", is_style);
+    check("launcher.temp", is_optional_string);
+
+    check("game.path.global", is_string);
+    check("game.path.china", is_string);
+
+    check("game.wine.builds", is_string);
+    check("game.wine.prefix", is_string);
+    check("game.wine.selected", is_optional_string);
+
+    check("game.dxvk.builds", is_string);
+
+    check("game.environment", is_strings_map);
+    check("game.command", is_optional_string);
+
+    check("components.path", is_string);
+    check("components.servers", is_strings_list);
+
+    check("patch.path", is_string);
+    check("patch.servers", is_strings_list);
+
+    issues
+}
+
+/// Replace given section of the config with its default value
+pub fn reset_section(config: &mut Json, section: &str) -> anyhow::Result<()> {
+    let default = serde_json::to_value(Schema::default())?;
+
+    match default.get(section) {
+        Some(value) => config[section] = value.clone(),
+        None => anyhow::bail!("Config doesn't have section '{section}'")
+    }
+
+    Ok(())
+}
+
+/// Reset given section of the config file to its default value
+pub fn reset_file_section(section: &str) -> anyhow::Result<()> {
+    let path = config_file()?;
+
+    let mut config = serde_json::from_slice::<Json>(&std::fs::read(&path)?)?;
+
+    reset_section(&mut config, section)?;
+
+    std::fs::write(path, serde_json::to_string_pretty(&config)?)?;
+
+    Ok(())
+}
+
+/// Find the latest config backup which passes validation
+pub fn latest_valid_backup() -> Option<Backup> {
+    config_backups::list()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|backup| {
+            std::fs::read(&backup.path)
+                .map(|config| validate_raw(&config).is_empty())
+                .unwrap_or(false)
+        })
+}
+
+fn is_object(value: &Json) -> Result<(), String> {
+    if value.is_object() {
+        Ok(())
+    } else {
+        Err(String::from("expected an object"))
+    }
+}
+
+fn is_string(value: &Json) -> Result<(), String> {
+    if value.is_string() {
+        Ok(())
+    } else {
+        Err(String::from("expected a string"))
+    }
+}
+
+fn is_optional_string(value: &Json) -> Result<(), String> {
+    if value.is_string() || value.is_null() {
+        Ok(())
+    } else {
+        Err(String::from("expected a string or null"))
+    }
+}
+
+fn is_strings_list(value: &Json) -> Result<(), String> {
+    match value.as_array() {
+        Some(list) if list.iter().all(Json::is_string) => Ok(()),
+        _ => Err(String::from("expected a list of strings"))
+    }
+}
+
+fn is_strings_map(value: &Json) -> Result<(), String> {
+    match value.as_object() {
+        Some(map) if map.values().all(Json::is_string) => Ok(()),
+        _ => Err(String::from("expected an object with string values"))
+    }
+}
+
+fn is_language(value: &Json) -> Result<(), String> {
+    let Some(lang) = value.as_str() else {
+        return Err(String::from("expected a string"));
+    };
+
+    match lang.parse::<LanguageIdentifier>() {
+        Ok(lang) if negotiate_lang(&[lang], &supported_languages()).is_some() => Ok(()),
+        Ok(_) => Err(format!("language '{lang}' is not supported")),
+        Err(_) => Err(format!("'{lang}' is not a valid language code, e.g. 'en-us'"))
+    }
+}
+
+fn is_edition(value: &Json) -> Result<(), String> {
+    serde_json::from_value::<GameEdition>(value.clone())
+        .map(|_| ())
+        .map_err(|_| format!("unknown game edition: {value}"))
+}
+
+fn is_style(value: &Json) -> Result<(), String> {
+    serde_json::from_value::<LauncherStyle>(value.clone())
+        .map(|_| ())
+        .map_err(|_| format!("unknown launcher style: {value}"))
+}
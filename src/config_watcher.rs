@@ -0,0 +1,193 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use gtk::gio;
+use gtk::gio::prelude::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::{Config, Schema};
+use anime_launcher_sdk::star_rail::consts::config_file;
+
+use serde_json::Value as Json;
+
+use crate::config_validation::{self, ConfigIssue};
+use crate::config_backups::{self, Change};
+use crate::settings::{Settings, SETTINGS_FILE};
+
+lazy_static::lazy_static! {
+    /// Config which was stored on the disk last time the launcher read or wrote it
+    static ref SYNCED_CONFIG: Mutex<Option<Json>> = Mutex::new(None);
+
+    /// Launcher settings which were stored on the disk last time the launcher read or wrote them
+    static ref SYNCED_SETTINGS: Mutex<Option<Settings>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigReload {
+    /// Config file content is the same as the launcher knows
+    Unchanged,
+
+    /// Config file was changed but it's invalid so it wasn't loaded
+    Invalid(Vec<ConfigIssue>),
+
+    /// Config was reloaded from the file
+    Reloaded {
+        /// In-app changes which were overwritten by the file
+        conflicts: Vec<Change>
+    }
+}
+
+/// Remember config stored on the disk
+///
+/// Should be called every time the launcher writes config file
+pub fn mark_synced(config: Json) {
+    *SYNCED_CONFIG.lock().unwrap() = Some(config);
+}
+
+/// Remember current config file content
+pub fn mark_file_synced() -> anyhow::Result<()> {
+    let config = serde_json::from_slice::<Json>(&std::fs::read(config_file()?)?)?;
+
+    mark_synced(config);
+
+    Ok(())
+}
+
+/// Remember launcher settings stored on the disk
+///
+/// Called by `Settings::update`
+pub fn mark_settings_synced(settings: Settings) {
+    *SYNCED_SETTINGS.lock().unwrap() = Some(settings);
+}
+
+/// Call `callback` every time config file is changed
+///
+/// Returned monitor must be kept alive while the file should be watched
+#[inline]
+pub fn watch(callback: impl Fn() + 'static) -> anyhow::Result<gio::FileMonitor> {
+    watch_file(config_file()?, callback)
+}
+
+/// Call `callback` every time launcher settings file is changed
+///
+/// Returned monitor must be kept alive while the file should be watched
+#[inline]
+pub fn watch_settings(callback: impl Fn() + 'static) -> anyhow::Result<gio::FileMonitor> {
+    watch_file(SETTINGS_FILE.as_path(), callback)
+}
+
+fn watch_file(path: impl AsRef<Path>, callback: impl Fn() + 'static) -> anyhow::Result<gio::FileMonitor> {
+    let monitor = gio::File::for_path(path)
+        .monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE)?;
+
+    monitor.connect_changed(move |_, _, _, event| {
+        if matches!(event, gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created) {
+            callback();
+        }
+    });
+
+    Ok(monitor)
+}
+
+/// Load config file changes made outside of the launcher
+///
+/// In-app changes which were not saved yet are kept
+/// unless the same fields were changed in the file
+pub fn reload() -> anyhow::Result<ConfigReload> {
+    let stored = std::fs::read(config_file()?)?;
+
+    let issues = config_validation::validate_raw(&stored);
+
+    if !issues.is_empty() {
+        return Ok(ConfigReload::Invalid(issues));
+    }
+
+    let stored = serde_json::from_slice::<Json>(&stored)?;
+    let current = serde_json::to_value(Config::get()?)?;
+
+    let synced = SYNCED_CONFIG.lock().unwrap()
+        .clone()
+        .unwrap_or_else(|| current.clone());
+
+    // Launcher wrote this file itself
+    if stored == synced {
+        return Ok(ConfigReload::Unchanged);
+    }
+
+    let external_changes = config_backups::diff(&synced, &stored);
+    let in_app_changes = config_backups::diff(&synced, &current);
+
+    let mut merged = stored.clone();
+    let mut conflicts = Vec::new();
+
+    for change in in_app_changes {
+        match external_changes.iter().find(|external| external.path == change.path) {
+            Some(external) if external.new != change.new => conflicts.push(change),
+            Some(_) => (),
+
+            None => set_field(&mut merged, &change.path, change.new)
+        }
+    }
+
+    Config::update(Schema::from(&merged));
+
+    mark_synced(stored);
+
+    tracing::info!("Config file was reloaded. Conflicts: {:?}", conflicts);
+
+    Ok(ConfigReload::Reloaded { conflicts })
+}
+
+/// Check if launcher settings file was changed outside of the launcher
+///
+/// Settings are read from the disk every time they're used,
+/// so the launcher only needs to update the widgets showing them.
+/// Fails if the edited file can't be read
+pub fn reload_settings() -> anyhow::Result<bool> {
+    let settings = Settings::get()?;
+
+    let mut synced = SYNCED_SETTINGS.lock().unwrap();
+
+    if synced.as_ref() == Some(&settings) {
+        return Ok(false);
+    }
+
+    // First read of the file is not a change
+    let changed = synced.is_some();
+
+    *synced = Some(settings);
+
+    if changed {
+        tracing::info!("Launcher settings file was reloaded");
+    }
+
+    Ok(changed)
+}
+
+fn set_field(config: &mut Json, path: &str, value: Option<Json>) {
+    let mut keys = path.split('.').collect::<Vec<_>>();
+
+    let Some(last) = keys.pop() else {
+        return;
+    };
+
+    let mut parent = config;
+
+    for key in keys {
+        if !parent[key].is_object() {
+            parent[key] = Json::Object(Default::default());
+        }
+
+        parent = &mut parent[key];
+    }
+
+    match value {
+        Some(value) => parent[last] = value,
+
+        None => {
+            if let Some(parent) = parent.as_object_mut() {
+                parent.remove(last);
+            }
+        }
+    }
+}
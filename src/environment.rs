@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Named group of environment variables which can be toggled together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    pub locale_key: &'static str,
+    pub variables: &'static [(&'static str, &'static str)]
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        locale_key: "preset-dxvk-debug",
+        variables: &[
+            ("DXVK_LOG_LEVEL", "debug"),
+            ("DXVK_HUD", "devinfo,fps,frametimes,version,api,memory")
+        ]
+    },
+
+    Preset {
+        locale_key: "preset-nvidia-tweaks",
+        variables: &[
+            ("__GL_SHADER_DISK_CACHE", "1"),
+            ("__GL_SHADER_DISK_CACHE_SKIP_CLEANUP", "1"),
+            ("DXVK_ENABLE_NVAPI", "1")
+        ]
+    },
+
+    Preset {
+        locale_key: "preset-mesa-aco",
+        variables: &[
+            ("RADV_PERFTEST", "aco")
+        ]
+    }
+];
+
+/// Prefix of the disabled variables in `.env` files.
+/// Other tools read such lines as comments
+pub const DISABLED_PREFIX: &str = "#disabled ";
+
+impl Preset {
+    /// Preset is enabled when all its variables are set with the same values
+    pub fn is_enabled(&self, environment: &HashMap<String, String>) -> bool {
+        self.variables.iter().all(|(key, value)| {
+            environment.get(*key).map(String::as_str) == Some(*value)
+        })
+    }
+
+    /// Set preset variables
+    ///
+    /// Values of the existing variables are moved to the `overridden` map
+    pub fn enable(&self, environment: &mut HashMap<String, String>, overridden: &mut HashMap<String, String>) {
+        for (key, value) in self.variables {
+            if let Some(old) = environment.insert(key.to_string(), value.to_string()) {
+                if old != *value {
+                    overridden.entry(key.to_string()).or_insert(old);
+                }
+            }
+        }
+    }
+
+    /// Remove preset variables which weren't changed by the user
+    ///
+    /// Values overridden by the preset are restored
+    pub fn disable(&self, environment: &mut HashMap<String, String>, overridden: &mut HashMap<String, String>) {
+        for (key, value) in self.variables {
+            let old = overridden.remove(*key);
+
+            if environment.get(*key).map(String::as_str) == Some(*value) {
+                environment.remove(*key);
+
+                if let Some(old) = old {
+                    environment.insert(key.to_string(), old);
+                }
+            }
+        }
+    }
+}
+
+/// Check if the string can be used as a variable name
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() &&
+    !name.starts_with(|char: char| char.is_ascii_digit()) &&
+    name.chars().all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Parse `.env` file content
+///
+/// Supports comments, `export` prefix and single or double quoted values.
+/// Returns variables names, values and whether they're enabled
+pub fn parse(content: &str) -> anyhow::Result<Vec<(String, String, bool)>> {
+    let mut variables = Vec::new();
+
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        let (line, enabled) = match line.strip_prefix(DISABLED_PREFIX) {
+            Some(line) => (line.trim_start(), false),
+            None => (line, true)
+        };
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        let Some((name, value)) = line.split_once('=') else {
+            // Just a comment starting with the same word
+            if !enabled {
+                continue;
+            }
+
+            anyhow::bail!("Line {} is not a variable: {line}", number + 1);
+        };
+
+        let name = name.trim();
+
+        if !is_valid_name(name) {
+            if !enabled {
+                continue;
+            }
+
+            anyhow::bail!("Line {} has wrong variable name: {name}", number + 1);
+        }
+
+        variables.push((name.to_string(), parse_value(value.trim()), enabled));
+    }
+
+    Ok(variables)
+}
+
+fn parse_value(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].to_string();
+    }
+
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut result = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' => match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some(char) => result.push(char),
+                    None => result.push('\\')
+                }
+
+                char => result.push(char)
+            }
+        }
+
+        return result;
+    }
+
+    // Unquoted values can have trailing comments
+    match value.split_once(" #") {
+        Some((value, _)) => value.trim_end().to_string(),
+        None => value.to_string()
+    }
+}
+
+fn serialize_value(value: &str) -> String {
+    let plain = value.chars().all(|char| char.is_ascii_alphanumeric() || "_-.,:/+@%=".contains(char));
+
+    if plain && !value.is_empty() {
+        return value.to_string();
+    }
+
+    let mut result = String::from("\"");
+
+    for char in value.chars() {
+        match char {
+            '"' | '\\' | '$' | '`' => {
+                result.push('\\');
+                result.push(char);
+            }
+
+            '\n' => result.push_str("\\n"),
+
+            char => result.push(char)
+        }
+    }
+
+    result.push('"');
+
+    result
+}
+
+/// Serialize variables to `.env` file content
+///
+/// Disabled variables are written as comments with `DISABLED_PREFIX`
+pub fn serialize(enabled: &HashMap<String, String>, disabled: &HashMap<String, String>) -> String {
+    let mut variables = enabled.iter()
+        .map(|(key, value)| (key, value, true))
+        .chain(disabled.iter().map(|(key, value)| (key, value, false)))
+        .collect::<Vec<_>>();
+
+    variables.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut content = String::new();
+
+    for (key, value, enabled) in variables {
+        if !enabled {
+            content += DISABLED_PREFIX;
+        }
+
+        content += &format!("{key}={}\n", serialize_value(value));
+    }
+
+    content
+}
+
+/// Read variables from `.env` file
+#[inline]
+pub fn import_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<(String, String, bool)>> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+/// Write variables to `.env` file
+#[inline]
+pub fn export_file(path: impl AsRef<Path>, enabled: &HashMap<String, String>, disabled: &HashMap<String, String>) -> anyhow::Result<()> {
+    Ok(std::fs::write(path, serialize(enabled, disabled))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_file() {
+        let content = "
+            # Comment
+            export DXVK_HUD=fps
+            MANGOHUD=1 # trailing comment
+            QUOTED='single $quoted'
+            ESCAPED=\"line\\nbreak \\\"quote\\\"\"
+            #disabled PROTON_LOG=1
+            #disabled because of something
+        ";
+
+        assert_eq!(parse(content).unwrap(), vec![
+            (String::from("DXVK_HUD"), String::from("fps"), true),
+            (String::from("MANGOHUD"), String::from("1"), true),
+            (String::from("QUOTED"), String::from("single $quoted"), true),
+            (String::from("ESCAPED"), String::from("line\nbreak \"quote\""), true),
+            (String::from("PROTON_LOG"), String::from("1"), false)
+        ]);
+
+        assert!(parse("NOT A VARIABLE").is_err());
+        assert!(parse("1WRONG=name").is_err());
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let enabled = HashMap::from([
+            (String::from("DXVK_HUD"), String::from("devinfo,fps")),
+            (String::from("WINEDLLOVERRIDES"), String::from("\"quoted\" $value\nnext line"))
+        ]);
+
+        let disabled = HashMap::from([
+            (String::from("PROTON_LOG"), String::from("1")),
+            (String::from("EMPTY"), String::new())
+        ]);
+
+        let mut variables = parse(&serialize(&enabled, &disabled)).unwrap();
+
+        variables.sort();
+
+        let mut expected = enabled.iter()
+            .map(|(key, value)| (key.clone(), value.clone(), true))
+            .chain(disabled.iter().map(|(key, value)| (key.clone(), value.clone(), false)))
+            .collect::<Vec<_>>();
+
+        expected.sort();
+
+        assert_eq!(variables, expected);
+    }
+
+    #[test]
+    fn presets_restore_overridden_values() {
+        let preset = &PRESETS[0];
+
+        let mut environment = HashMap::from([
+            (String::from("DXVK_HUD"), String::from("fps")),
+            (String::from("OTHER"), String::from("1"))
+        ]);
+
+        let mut overridden = HashMap::new();
+
+        preset.enable(&mut environment, &mut overridden);
+
+        assert!(preset.is_enabled(&environment));
+        assert_eq!(overridden, HashMap::from([(String::from("DXVK_HUD"), String::from("fps"))]));
+
+        preset.disable(&mut environment, &mut overridden);
+
+        assert!(!preset.is_enabled(&environment));
+        assert!(overridden.is_empty());
+
+        assert_eq!(environment, HashMap::from([
+            (String::from("DXVK_HUD"), String::from("fps")),
+            (String::from("OTHER"), String::from("1"))
+        ]));
+
+        // Variables changed by the user after enabling are kept
+        preset.enable(&mut environment, &mut overridden);
+
+        environment.insert(String::from("DXVK_HUD"), String::from("memory"));
+
+        preset.disable(&mut environment, &mut overridden);
+
+        assert_eq!(environment.get("DXVK_HUD").map(String::as_str), Some("memory"));
+        assert!(!environment.contains_key("DXVK_LOG_LEVEL"));
+        assert!(overridden.is_empty());
+    }
+
+    #[test]
+    fn variable_names() {
+        assert!(is_valid_name("RADV_PERFTEST"));
+        assert!(is_valid_name("_private1"));
+
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("1WRONG"));
+        assert!(!is_valid_name("WITH SPACE"));
+        assert!(!is_valid_name("WITH=VALUE"));
+        assert!(!is_valid_name("DASHED-NAME"));
+    }
+
+    #[test]
+    fn presets_have_valid_variables() {
+        assert!(PRESETS.iter().any(|preset| preset.variables == [("RADV_PERFTEST", "aco")]));
+
+        for preset in PRESETS {
+            assert!(preset.variables.iter().all(|(key, _)| is_valid_name(key)), "{} has wrong variable name", preset.locale_key);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::Config;
+
+use crate::settings::GameSettings;
+
+/// Registry value with the in-game text language
+const TEXT_LANGUAGE_VALUE: &str = "LanguageSettings_LocalTextLanguage_h2764291023";
+
+/// Registry value with the in-game voice language
+const VOICE_LANGUAGE_VALUE: &str = "LanguageSettings_LocalAudioLanguage_h882585060";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameTextLanguage {
+    #[serde(rename = "cn")]
+    ChineseSimplified,
+
+    #[serde(rename = "cht")]
+    ChineseTraditional,
+
+    #[serde(rename = "en")]
+    English,
+
+    #[serde(rename = "jp")]
+    Japanese,
+
+    #[serde(rename = "kr")]
+    Korean,
+
+    #[serde(rename = "de")]
+    German,
+
+    #[serde(rename = "es")]
+    Spanish,
+
+    #[serde(rename = "fr")]
+    French,
+
+    #[serde(rename = "id")]
+    Indonesian,
+
+    #[serde(rename = "pt")]
+    Portuguese,
+
+    #[serde(rename = "ru")]
+    Russian,
+
+    #[serde(rename = "th")]
+    Thai,
+
+    #[serde(rename = "vi")]
+    Vietnamese
+}
+
+impl GameTextLanguage {
+    pub const LIST: &[Self] = &[
+        Self::ChineseSimplified,
+        Self::ChineseTraditional,
+        Self::English,
+        Self::Japanese,
+        Self::Korean,
+        Self::German,
+        Self::Spanish,
+        Self::French,
+        Self::Indonesian,
+        Self::Portuguese,
+        Self::Russian,
+        Self::Thai,
+        Self::Vietnamese
+    ];
+
+    /// Language code used by the game
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ChineseSimplified  => "cn",
+            Self::ChineseTraditional => "cht",
+            Self::English            => "en",
+            Self::Japanese           => "jp",
+            Self::Korean             => "kr",
+            Self::German             => "de",
+            Self::Spanish            => "es",
+            Self::French             => "fr",
+            Self::Indonesian         => "id",
+            Self::Portuguese         => "pt",
+            Self::Russian            => "ru",
+            Self::Thai               => "th",
+            Self::Vietnamese         => "vi"
+        }
+    }
+
+    /// Translation key of the language name
+    pub fn locale_key(&self) -> String {
+        format!("game-language--{}", self.code())
+    }
+
+    /// Language tag used by the launcher API, e.g. for the background picture
+    pub fn api_lang(&self) -> &'static str {
+        match self {
+            Self::ChineseSimplified  => "zh-cn",
+            Self::ChineseTraditional => "zh-tw",
+            Self::English            => "en-us",
+            Self::Japanese           => "ja-jp",
+            Self::Korean             => "ko-kr",
+            Self::German             => "de-de",
+            Self::Spanish            => "es-es",
+            Self::French             => "fr-fr",
+            Self::Indonesian         => "id-id",
+            Self::Portuguese         => "pt-pt",
+            Self::Russian            => "ru-ru",
+            Self::Thai               => "th-th",
+            Self::Vietnamese         => "vi-vn"
+        }
+    }
+}
+
+/// Game downloads voice pack of the selected language itself
+///
+/// The star rail part of the SDK can't install or update voice packages,
+/// so the launcher writes selected language to the game registry by `apply`
+/// and only checks if its voice pack is already downloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameVoiceLanguage {
+    #[serde(rename = "cn")]
+    Chinese,
+
+    #[serde(rename = "en")]
+    English,
+
+    #[serde(rename = "jp")]
+    Japanese,
+
+    #[serde(rename = "kr")]
+    Korean
+}
+
+impl GameVoiceLanguage {
+    pub const LIST: &[Self] = &[
+        Self::Chinese,
+        Self::English,
+        Self::Japanese,
+        Self::Korean
+    ];
+
+    /// Language code used by the game
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Chinese  => "cn",
+            Self::English  => "en",
+            Self::Japanese => "jp",
+            Self::Korean   => "kr"
+        }
+    }
+
+    /// Translation key of the language name
+    pub fn locale_key(&self) -> String {
+        format!("game-voice-language--{}", self.code())
+    }
+
+    /// Name of the voice pack folder in the game files
+    pub fn folder(&self) -> &'static str {
+        match self {
+            Self::Chinese  => "Chinese(PRC)",
+            Self::English  => "English",
+            Self::Japanese => "Japanese",
+            Self::Korean   => "Korean"
+        }
+    }
+
+    /// Check if voice pack of this language is downloaded
+    ///
+    /// Packs shipped with the game are stored in `StreamingAssets`,
+    /// ones downloaded by the game in `Persistent` folder
+    pub fn is_installed(&self, game_path: &Path, edition: GameEdition) -> bool {
+        ["StreamingAssets", "Persistent"].into_iter().any(|folder| {
+            game_path
+                .join(edition.data_folder())
+                .join(folder)
+                .join("Audio/AudioPackage/Windows")
+                .join(self.folder())
+                .exists()
+        })
+    }
+}
+
+/// Get registry key with the game settings
+fn registry_key(edition: GameEdition) -> &'static str {
+    match edition {
+        GameEdition::Global => concat!("HKEY_CURRENT_USER\\Software\\Cog", "nosph", "ere\\Star Rail"),
+        GameEdition::China  => concat!("HKEY_CURRENT_USER\\Software\\mi", "Ho", "Yo\\崩坏：星穹铁道")
+    }
+}
+
+/// Write selected languages to the game registry in the wine prefix
+///
+/// Does nothing if the prefix is not created yet. Languages are applied
+/// before each game launch as well so it will be done later
+pub fn apply(settings: &GameSettings) -> anyhow::Result<()> {
+    let values = [
+        (TEXT_LANGUAGE_VALUE, settings.text_language.map(|lang| lang.code())),
+        (VOICE_LANGUAGE_VALUE, settings.voice_language.map(|lang| lang.code()))
+    ];
+
+    if values.iter().all(|(_, lang)| lang.is_none()) {
+        return Ok(());
+    }
+
+    let config = Config::get()?;
+
+    if !config.game.wine.prefix.exists() {
+        return Ok(());
+    }
+
+    let Some(wine) = config.get_selected_wine()? else {
+        anyhow::bail!("Wine is not selected");
+    };
+
+    let wine = wine
+        .to_wine(config.components.path, Some(config.game.wine.builds.join(&wine.name)))
+        .with_prefix(config.game.wine.prefix)
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64);
+
+    let key = registry_key(config.launcher.edition);
+
+    for (value, lang) in values {
+        let Some(lang) = lang else {
+            continue;
+        };
+
+        // Game stores languages as null-terminated strings
+        let data = lang.bytes()
+            .chain([0])
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        let output = wine
+            .run_args(["reg", "add", key, "/v", value, "/t", "REG_BINARY", "/d", &data, "/f"])?
+            .wait_with_output()?;
+
+        if !output.status.success() {
+            anyhow::bail!("Failed to set {value} registry value: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        tracing::info!("Set {value} registry value to {lang}");
+    }
+
+    Ok(())
+}
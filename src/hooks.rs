@@ -0,0 +1,279 @@
+use std::os::unix::process::CommandExt;
+use std::sync::Mutex;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::{Config, Schema};
+
+use anime_launcher_sdk::discord_rpc::{DiscordRpc, RpcUpdates};
+use anime_launcher_sdk::sessions::SessionsExt;
+use anime_launcher_sdk::star_rail::sessions::Sessions;
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::anime_game_core::star_rail::prelude::*;
+use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+use crate::settings::{Settings, HooksSettings};
+use crate::launch_command::LaunchCommand;
+
+lazy_static::lazy_static! {
+    /// Process group of the running game. Same as the id of its outer process
+    static ref GAME_PROCESS_GROUP: Mutex<Option<u32>> = Mutex::new(None);
+}
+
+/// Hooks timeouts in seconds available in the settings
+pub const TIMEOUTS: &[u64] = &[0, 10, 30, 60, 300, 900];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Runs before the game launch. The game isn't started if it fails and it's configured so
+    PreLaunch,
+
+    /// Runs after the game was closed
+    PostExit
+}
+
+impl Hook {
+    /// Value of the `HRL_HOOK` variable
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PreLaunch => "pre-launch",
+            Self::PostExit  => "post-exit"
+        }
+    }
+
+    pub fn failed_locale_key(&self) -> &'static str {
+        match self {
+            Self::PreLaunch => "pre-launch-hook-failed",
+            Self::PostExit  => "post-exit-hook-failed"
+        }
+    }
+
+    fn command<'a>(&self, settings: &'a HooksSettings) -> Option<&'a str> {
+        let command = match self {
+            Self::PreLaunch => settings.pre_launch.as_deref(),
+            Self::PostExit  => settings.post_exit.as_deref()
+        };
+
+        command.map(str::trim).filter(|command| !command.is_empty())
+    }
+}
+
+/// Environment variables describing the launch
+///
+/// `game_succeeded` is set only for the post-exit hook
+pub fn variables(hook: Hook, config: &Schema, game_succeeded: Option<bool>) -> Vec<(&'static str, String)> {
+    let game_path = config.game.path.for_edition(config.launcher.edition);
+
+    let mut variables = vec![
+        ("HRL_HOOK", hook.name().to_string()),
+        ("HRL_LAUNCHER_FOLDER", crate::LAUNCHER_FOLDER.to_string_lossy().to_string()),
+        ("HRL_GAME_PATH", game_path.to_string_lossy().to_string()),
+        ("HRL_GAME_EDITION", match config.launcher.edition {
+            GameEdition::Global => String::from("global"),
+            GameEdition::China  => String::from("china")
+        }),
+        ("HRL_PREFIX_PATH", config.game.wine.prefix.to_string_lossy().to_string())
+    ];
+
+    if let Ok(version) = Game::new(game_path, config.launcher.edition).get_version() {
+        variables.push(("HRL_GAME_VERSION", version.to_string()));
+    }
+
+    if let Some(wine) = &config.game.wine.selected {
+        variables.push(("HRL_WINE_NAME", wine.clone()));
+        variables.push(("HRL_WINE_PATH", config.game.wine.builds.join(wine).to_string_lossy().to_string()));
+    }
+
+    if let Some(succeeded) = game_succeeded {
+        variables.push(("HRL_GAME_STATUS", if succeeded { "success" } else { "failed" }.to_string()));
+    }
+
+    variables
+}
+
+/// Run hook command if it's set
+///
+/// Fails if the command exits with non-zero code or doesn't finish in time
+pub fn run(hook: Hook, settings: &HooksSettings, game_succeeded: Option<bool>) -> anyhow::Result<()> {
+    let Some(command) = hook.command(settings) else {
+        return Ok(());
+    };
+
+    let config = Config::get()?;
+
+    tracing::info!("Running {} hook: {command}", hook.name());
+
+    let mut process = Command::new("sh");
+
+    process.arg("-c")
+        .arg(command)
+        .envs(variables(hook, &config, game_succeeded))
+        .stdin(Stdio::null())
+        // Own process group to kill processes started by the hook as well
+        .process_group(0);
+
+    let game_path = config.game.path.for_edition(config.launcher.edition);
+
+    if game_path.exists() {
+        process.current_dir(game_path);
+    }
+
+    let mut child = process.spawn()?;
+
+    let started = Instant::now();
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if !status.success() {
+                anyhow::bail!("Hook command finished with {status}");
+            }
+
+            return Ok(());
+        }
+
+        if settings.timeout > 0 && started.elapsed() >= Duration::from_secs(settings.timeout) {
+            // Process group id is the same as the shell's process id.
+            // Process could finish right before killing
+            let killed = Command::new("kill")
+                .args(["-KILL", "--", &format!("-{}", child.id())])
+                .stderr(Stdio::null())
+                .status();
+
+            if !killed.map(|status| status.success()).unwrap_or_default() {
+                let _ = child.kill();
+            }
+
+            let _ = child.wait();
+
+            anyhow::bail!("Hook command didn't finish in {} seconds", settings.timeout);
+        }
+
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Apply selected game languages and run the game with pre-launch and post-exit hooks
+///
+/// Game is not started if the pre-launch hook fails and `abort_on_failure` is set.
+/// Languages applying error is given to the `on_language_error` callback,
+/// other hooks errors are given to the `on_hook_error` callback
+pub fn run_game(on_language_error: impl Fn(anyhow::Error), on_hook_error: impl Fn(Hook, anyhow::Error)) -> anyhow::Result<()> {
+    let settings = Settings::get().unwrap_or_default();
+
+    // Game could change its languages so set the selected ones again
+    if let Err(err) = crate::game_language::apply(&settings.game) {
+        tracing::error!("Failed to apply game language: {err}");
+
+        on_language_error(err);
+    }
+
+    let settings = settings.hooks;
+
+    if let Err(err) = run(Hook::PreLaunch, &settings, None) {
+        tracing::error!("Pre-launch hook failed: {err}");
+
+        if settings.abort_on_failure {
+            anyhow::bail!("Pre-launch hook failed: {err}");
+        }
+
+        on_hook_error(Hook::PreLaunch, err);
+    }
+
+    let result = launch_game();
+
+    if let Err(err) = run(Hook::PostExit, &settings, Some(result.is_ok())) {
+        tracing::error!("Post-exit hook failed: {err}");
+
+        on_hook_error(Hook::PostExit, err);
+    }
+
+    result
+}
+
+/// Start the game and wait until it's closed
+///
+/// Game is started by the same `LaunchCommand` which is shown in the preview and dry run report
+fn launch_game() -> anyhow::Result<()> {
+    let config = Config::get()?;
+    let command = LaunchCommand::build(&config)?;
+
+    if !command.current_dir.exists() {
+        anyhow::bail!("Game is not installed");
+    }
+
+    // Apply selected session before launching the game
+    if let Some(current) = Sessions::get_sessions()?.current {
+        Sessions::apply(current, config.get_wine_prefix_path())?;
+    }
+
+    let rpc = if config.launcher.discord_rpc.enabled {
+        Some(DiscordRpc::new(config.launcher.discord_rpc.clone().into()))
+    } else {
+        None
+    };
+
+    if let Some(rpc) = &rpc {
+        rpc.update(RpcUpdates::Connect)?;
+    }
+
+    tracing::info!("Running the game:\n{command}");
+
+    let mut child = command.spawn()?;
+
+    *GAME_PROCESS_GROUP.lock().unwrap() = Some(child.id());
+
+    let status = child.wait();
+
+    if let Ok(status) = &status {
+        tracing::debug!("Game command finished with {status}");
+
+        // Wine can keep the game running after the outer process is finished
+        while process_group_exists(child.id()) {
+            std::thread::sleep(Duration::from_secs(3));
+        }
+    }
+
+    *GAME_PROCESS_GROUP.lock().unwrap() = None;
+
+    status?;
+
+    tracing::info!("Game was closed");
+
+    if let Some(rpc) = &rpc {
+        rpc.update(RpcUpdates::Disconnect)?;
+    }
+
+    Ok(())
+}
+
+/// Stop the game started by the launcher with all the processes it started
+///
+/// Does nothing if the game isn't running
+pub fn kill_game() -> anyhow::Result<()> {
+    let Some(group) = *GAME_PROCESS_GROUP.lock().unwrap() else {
+        return Ok(());
+    };
+
+    let output = Command::new("kill")
+        .args(["-TERM", "--", &format!("-{group}")])
+        .output()?;
+
+    // Group can be finished between the check and the signal
+    if !output.status.success() && process_group_exists(group) {
+        anyhow::bail!("kill failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(())
+}
+
+/// Check if any process of the group is still running
+fn process_group_exists(group: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", "--", &format!("-{group}")])
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or_default()
+}
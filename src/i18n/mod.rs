@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+use std::sync::{Mutex, RwLock};
+
+use fluent_templates::Loader;
+use unic_langid::{langid, LanguageIdentifier};
+
+pub mod report;
+pub mod overrides;
+
+mod keys {
+    include!(concat!(env!("OUT_DIR"), "/locales.rs"));
+}
+
+fluent_templates::static_loader! {
+    static LOCALES = {
+        locales: "./assets/locales",
+        core_locales: "./assets/locales/common.ftl",
+        fallback_language: "en"
+    };
+}
+
+/// Map of supported languages
+pub const SUPPORTED_LANGUAGES: &[LanguageIdentifier] = &[
+    langid!("en-us"),
+    langid!("ru-ru"),
+    langid!("de-de"),
+    langid!("fr-fr"),
+    langid!("es-es"),
+    langid!("tr-tr"),
+    langid!("it-it"),
+    langid!("id-id"),
+    langid!("zh-cn"),
+];
+
+/// Language used when a message is missing in the selected one
+pub const FALLBACK_LANGUAGE: LanguageIdentifier = langid!("en-us");
+
+static LANG: RwLock<LanguageIdentifier> = RwLock::new(langid!("en-us"));
+
+lazy_static::lazy_static! {
+    /// Messages which were already reported as missing
+    static ref MISSING_MESSAGES: Mutex<HashSet<(LanguageIdentifier, String)>> = Mutex::new(HashSet::new());
+}
+
+/// Get built-in and user languages
+pub fn supported_languages() -> Vec<LanguageIdentifier> {
+    let mut languages = SUPPORTED_LANGUAGES.to_vec();
+
+    for lang in overrides::languages() {
+        if !languages.iter().any(|item| locale_matches(&lang, item)) {
+            languages.push(lang);
+        }
+    }
+
+    languages
+}
+
+/// Set launcher language
+/// 
+/// Closest supported language is used, e.g. `de-at` sets `de-de`
+pub fn set_lang(lang: LanguageIdentifier) -> anyhow::Result<()> {
+    match negotiate_lang(&[lang.clone()], &supported_languages()) {
+        Some(lang) => {
+            *LANG.write().unwrap() = lang;
+
+            Ok(())
+        }
+
+        None => anyhow::bail!("Language '{lang}' is not supported")
+    }
+}
+
+/// Get launcher language
+pub fn get_lang() -> LanguageIdentifier {
+    LANG.read().unwrap().clone()
+}
+
+/// Get system languages in order of preference
+/// 
+/// Locale is taken from the first set env variable:
+/// - `LC_ALL`
+/// - `LC_MESSAGES`
+/// - `LANG`
+/// 
+/// Languages from `LANGUAGE` list are preferred over the locale
+/// unless the locale is `C` or `POSIX`, like gettext does.
+/// 
+/// Variables are read by `lookup`, e.g. `|name| std::env::var(name).ok()`
+pub fn get_system_langs(lookup: impl Fn(&str) -> Option<String>) -> Vec<LanguageIdentifier> {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+        .filter_map(&lookup)
+        .find(|value| !value.is_empty())
+        .and_then(|locale| parse_locale(&locale));
+
+    let mut langs = Vec::new();
+
+    if locale.is_some() {
+        if let Some(list) = lookup("LANGUAGE") {
+            langs.extend(list.split(':').filter_map(parse_locale));
+        }
+    }
+
+    langs.extend(locale);
+
+    langs
+}
+
+/// Get system language or default language if system one is not supported
+pub fn get_default_lang() -> LanguageIdentifier {
+    negotiate_lang(&get_system_langs(|name| std::env::var(name).ok()), &supported_languages()).unwrap_or(FALLBACK_LANGUAGE)
+}
+
+/// Parse POSIX locale name
+/// 
+/// Format is `language[_territory][.codeset][@modifier]`, e.g. `sr_RS.UTF-8@latin`.
+/// Returns `None` for `C` and `POSIX` locales
+pub fn parse_locale(locale: &str) -> Option<LanguageIdentifier> {
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale, Some(modifier)),
+        None => (locale, None)
+    };
+
+    let locale = locale.split('.').next().unwrap_or_default();
+
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        return None;
+    }
+
+    let mut lang = locale.replace('_', "-").parse::<LanguageIdentifier>().ok()?;
+
+    match modifier {
+        Some("latin") => lang.script = "Latn".parse().ok(),
+        Some("cyrillic") => lang.script = "Cyrl".parse().ok(),
+
+        _ => ()
+    }
+
+    Some(lang)
+}
+
+/// Get writing script of the language
+/// 
+/// Script is guessed by the region for languages which have several of them
+fn lang_script(lang: &LanguageIdentifier) -> Option<String> {
+    if let Some(script) = lang.script {
+        return Some(script.to_string());
+    }
+
+    match (lang.language.as_str(), lang.region.map(|region| region.to_string())) {
+        ("zh", Some(region)) if ["TW", "HK", "MO"].contains(&region.as_str()) => Some(String::from("Hant")),
+        ("zh", _) => Some(String::from("Hans")),
+
+        _ => None
+    }
+}
+
+/// Find the best supported language for the requested ones
+/// 
+/// Requested languages are checked in order. Same language with other region
+/// is used if there's no exact match and they use the same script,
+/// so `de-at` matches `de-de` but `zh-tw` doesn't match `zh-cn`
+pub fn negotiate_lang(requested: &[LanguageIdentifier], supported: &[LanguageIdentifier]) -> Option<LanguageIdentifier> {
+    for lang in requested {
+        let script = lang_script(lang);
+
+        let same_language = supported.iter()
+            .filter(|item| item.language == lang.language && lang_script(item) == script)
+            .collect::<Vec<_>>();
+
+        let found = same_language.iter()
+            .find(|item| item.region == lang.region)
+            .or_else(|| same_language.first());
+
+        if let Some(found) = found {
+            return Some((*found).clone());
+        }
+    }
+
+    None
+}
+
+pub fn format_lang(lang: &LanguageIdentifier) -> String {
+    format!("{}-{}", lang.language, match lang.region {
+        Some(region) => region.to_string().to_ascii_lowercase(),
+        None => lang.language.to_string()
+    })
+}
+
+/// Check if the message is defined in the given language
+pub fn has_message(lang: &LanguageIdentifier, id: &str) -> bool {
+    if keys::COMMON_KEYS.contains(&id) {
+        return true;
+    }
+
+    keys::LOCALE_KEYS.iter()
+        .filter(|(locale, _)| locale.parse::<LanguageIdentifier>()
+            .map(|locale| locale_matches(&locale, lang))
+            .unwrap_or_default())
+        .any(|(_, keys)| keys.contains(&id))
+}
+
+/// Check if locale folder (e.g. `de` or `zh-cn`) contains messages for the language
+fn locale_matches(locale: &LanguageIdentifier, lang: &LanguageIdentifier) -> bool {
+    locale.language == lang.language && (locale.region.is_none() || locale.region == lang.region)
+}
+
+/// Get language which should be used to translate the message
+/// 
+/// Fallbacks to English if the message is missing in the launcher language
+fn message_lang(id: &str) -> LanguageIdentifier {
+    let lang = get_lang();
+
+    if has_message(&lang, id) {
+        return lang;
+    }
+
+    if MISSING_MESSAGES.lock().unwrap().insert((lang.clone(), id.to_string())) {
+        tracing::warn!("Message '{id}' is missing in '{lang}' locale. Using '{FALLBACK_LANGUAGE}' instead");
+    }
+
+    FALLBACK_LANGUAGE
+}
+
+fn missing_message(id: &str) -> String {
+    tracing::error!("Failed to find message with given id: {id}");
+
+    id.to_string()
+}
+
+/// Get translated message by key
+/// 
+/// User translations from `overrides::USER_LOCALES_FOLDER` are preferred.
+/// Returns message id if it's missing in all the locales
+/// 
+/// ```no_run
+/// println!("Translated message: {}", tr("launch"));
+/// ``` 
+pub fn tr(id: &str) -> String {
+    if let Some(message) = overrides::lookup(&get_lang(), id, None) {
+        return message;
+    }
+
+    LOCALES
+        .lookup(&message_lang(id), id)
+        .unwrap_or_else(|| missing_message(id))
+}
+
+/// Get translated message by key with filled arguments
+/// 
+/// Returns message id if it's missing in all the locales
+/// 
+/// ```no_run
+/// println!("Translated message: {}", tr_args("game-outdated", [
+///     ("latest", "3.3.0".into())
+/// ]));
+/// ``` 
+pub fn tr_args<I, T>(id: &str, args: I) -> String
+where
+    I: IntoIterator<Item = (T, fluent_templates::fluent_bundle::FluentValue<'static>)>,
+    T: AsRef<str> + std::hash::Hash + Eq
+{
+    let args = args.into_iter()
+        .map(|(key, value)| (key.as_ref().to_string(), value))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    if let Some(message) = overrides::lookup(&get_lang(), id, Some(&args)) {
+        return message;
+    }
+
+    LOCALES
+        .lookup_with_args(&message_lang(id), id, &args)
+        .unwrap_or_else(|| missing_message(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_posix_locales() {
+        let cases = [
+            ("en_US.UTF-8", Some(langid!("en-US"))),
+            ("de_AT", Some(langid!("de-AT"))),
+            ("ru", Some(langid!("ru"))),
+            ("sr_RS@latin", Some(langid!("sr-Latn-RS"))),
+            ("sr_RS.UTF-8@cyrillic", Some(langid!("sr-Cyrl-RS"))),
+            ("zh_TW.Big5", Some(langid!("zh-TW"))),
+            ("C", None),
+            ("C.UTF-8", None),
+            ("POSIX", None),
+            ("", None)
+        ];
+
+        for (locale, expected) in cases {
+            assert_eq!(parse_locale(locale), expected, "locale: {locale}");
+        }
+    }
+
+    #[test]
+    fn negotiate_supported_languages() {
+        let cases = [
+            (vec![langid!("en-US")], Some(langid!("en-us"))),
+            (vec![langid!("en-GB")], Some(langid!("en-us"))),
+            (vec![langid!("de-AT")], Some(langid!("de-de"))),
+            (vec![langid!("de")], Some(langid!("de-de"))),
+            (vec![langid!("zh-CN")], Some(langid!("zh-cn"))),
+            (vec![langid!("zh-SG")], Some(langid!("zh-cn"))),
+            (vec![langid!("zh-Hans")], Some(langid!("zh-cn"))),
+            (vec![langid!("zh-TW")], None),
+            (vec![langid!("zh-HK")], None),
+            (vec![langid!("zh-MO")], None),
+            (vec![langid!("zh-Hant-CN")], None),
+            (vec![langid!("ja-JP")], None),
+            (vec![langid!("ja-JP"), langid!("fr-CA")], Some(langid!("fr-fr"))),
+            (vec![langid!("zh-TW"), langid!("en-US")], Some(langid!("en-us"))),
+            (vec![], None)
+        ];
+
+        for (requested, expected) in cases {
+            assert_eq!(negotiate_lang(&requested, SUPPORTED_LANGUAGES), expected, "requested: {requested:?}");
+        }
+    }
+
+    #[test]
+    fn negotiate_traditional_chinese() {
+        let supported = [langid!("zh-cn"), langid!("zh-tw")];
+
+        assert_eq!(negotiate_lang(&[langid!("zh-HK")], &supported), Some(langid!("zh-tw")));
+        assert_eq!(negotiate_lang(&[langid!("zh-MO")], &supported), Some(langid!("zh-tw")));
+        assert_eq!(negotiate_lang(&[langid!("zh-SG")], &supported), Some(langid!("zh-cn")));
+    }
+
+    #[test]
+    fn system_langs_from_env() {
+        let cases: [(&[(&str, &str)], Vec<LanguageIdentifier>); 7] = [
+            (&[("LANG", "en_US.UTF-8")], vec![langid!("en-US")]),
+            (&[("LANG", "en_US.UTF-8"), ("LC_ALL", "de_AT.UTF-8")], vec![langid!("de-AT")]),
+            (&[("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "sr_RS@latin")], vec![langid!("sr-Latn-RS")]),
+            (&[("LANG", "de_DE.UTF-8"), ("LANGUAGE", "fr_FR:ru:C")], vec![langid!("fr-FR"), langid!("ru"), langid!("de-DE")]),
+            (&[("LANG", "C"), ("LANGUAGE", "fr_FR:ru")], vec![]),
+            (&[("LC_ALL", ""), ("LANG", "POSIX")], vec![]),
+            (&[], vec![])
+        ];
+
+        for (env, expected) in cases {
+            let lookup = |name: &str| env.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string());
+
+            assert_eq!(get_system_langs(lookup), expected, "env: {env:?}");
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use fluent_templates::fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use fluent_templates::fluent_bundle::concurrent::FluentBundle;
+
+use unic_langid::LanguageIdentifier;
+
+lazy_static::lazy_static! {
+    /// Path to user locales folder. Standard is `$HOME/.local/share/anime-game-launcher/locales`
+    ///
+    /// Each sub-folder is a language (e.g. `locales/de-de`) with `.ftl` files
+    /// which messages override the built-in ones
+    pub static ref USER_LOCALES_FOLDER: PathBuf = crate::LAUNCHER_FOLDER.join("locales");
+
+    static ref USER_LOCALES: RwLock<Vec<(LanguageIdentifier, FluentBundle<FluentResource>)>> = RwLock::new(Vec::new());
+}
+
+/// Load user locales from the `USER_LOCALES_FOLDER`
+///
+/// Previously loaded locales are replaced
+pub fn load() -> anyhow::Result<()> {
+    let mut locales = Vec::new();
+
+    if USER_LOCALES_FOLDER.exists() {
+        for entry in USER_LOCALES_FOLDER.read_dir()?.flatten() {
+            let path = entry.path();
+
+            if !path.is_dir() {
+                continue;
+            }
+
+            let lang = match entry.file_name().to_string_lossy().parse::<LanguageIdentifier>() {
+                Ok(lang) => lang,
+
+                Err(err) => {
+                    tracing::warn!("Skipping user locale {:?}: {err}", entry.file_name());
+
+                    continue;
+                }
+            };
+
+            let mut bundle = FluentBundle::new_concurrent(vec![lang.clone()]);
+
+            bundle.set_use_isolating(false);
+
+            // Allow user messages to use terms like `{-game-name-1}`
+            let common = FluentResource::try_new(include_str!("../../assets/locales/common.ftl").to_string())
+                .unwrap_or_else(|(resource, _)| resource);
+
+            bundle.add_resource_overriding(common);
+
+            let files = match path.read_dir() {
+                Ok(files) => files,
+
+                Err(err) => {
+                    tracing::warn!("Skipping user locale {lang}: {err}");
+
+                    continue;
+                }
+            };
+
+            for file in files.flatten() {
+                let file = file.path();
+
+                if file.extension().map(|ext| ext != "ftl").unwrap_or(true) {
+                    continue;
+                }
+
+                let content = match std::fs::read_to_string(&file) {
+                    Ok(content) => content,
+
+                    Err(err) => {
+                        tracing::warn!("Failed to read user locale file {:?}: {err}", file);
+
+                        continue;
+                    }
+                };
+
+                let resource = match FluentResource::try_new(content) {
+                    Ok(resource) => resource,
+
+                    // Use correctly parsed messages anyway
+                    Err((resource, errors)) => {
+                        tracing::warn!("Failed to parse user locale file {:?}: {:?}", file, errors);
+
+                        resource
+                    }
+                };
+
+                bundle.add_resource_overriding(resource);
+            }
+
+            tracing::info!("Loaded user locale {lang}");
+
+            locales.push((lang, bundle));
+        }
+    }
+
+    *USER_LOCALES.write().unwrap() = locales;
+
+    Ok(())
+}
+
+/// Get list of user locales languages
+pub fn languages() -> Vec<LanguageIdentifier> {
+    USER_LOCALES.read().unwrap()
+        .iter()
+        .map(|(lang, _)| lang.clone())
+        .collect()
+}
+
+/// Get user translation of the message
+pub fn lookup(lang: &LanguageIdentifier, id: &str, args: Option<&HashMap<String, FluentValue<'static>>>) -> Option<String> {
+    let locales = USER_LOCALES.read().unwrap();
+
+    let (_, bundle) = locales.iter().find(|(locale, _)| super::locale_matches(locale, lang))?;
+
+    let pattern = bundle.get_message(id)?.value()?;
+
+    let args = args.map(|args| {
+        let mut fluent_args = FluentArgs::new();
+
+        for (key, value) in args {
+            fluent_args.set(key.as_str(), value.clone());
+        }
+
+        fluent_args
+    });
+
+    let mut errors = Vec::new();
+
+    let message = bundle.format_pattern(pattern, args.as_ref(), &mut errors);
+
+    if !errors.is_empty() {
+        tracing::warn!("Failed to format user message '{id}': {:?}", errors);
+    }
+
+    Some(message.to_string())
+}
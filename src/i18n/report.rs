@@ -0,0 +1,144 @@
+use super::keys;
+
+/// Report about the locales state
+///
+/// Lists are filled at build time from `assets/locales` and `src` folders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalesReport {
+    /// Locales which differ from the English one
+    pub locales: Vec<LocaleReport>,
+
+    /// English messages which are not used in the code
+    pub unused: Vec<&'static str>,
+
+    /// Messages which are used in the code but not defined in English locale.
+    /// Contains message id, file and line
+    pub undefined: Vec<(&'static str, &'static str, usize)>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleReport {
+    pub locale: &'static str,
+
+    /// English messages which are not translated
+    pub missing: Vec<&'static str>,
+
+    /// Messages which are not defined in English locale
+    pub unknown: Vec<&'static str>
+}
+
+impl LocalesReport {
+    pub fn new() -> Self {
+        let english = keys::LOCALE_KEYS.iter()
+            .find(|(locale, _)| *locale == "en")
+            .map(|(_, keys)| *keys)
+            .unwrap_or_default();
+
+        let locales = keys::LOCALE_KEYS.iter()
+            .filter(|(locale, _)| *locale != "en")
+            .map(|(locale, keys)| LocaleReport {
+                locale,
+
+                missing: english.iter()
+                    .filter(|key| !keys.contains(key))
+                    .copied()
+                    .collect(),
+
+                unknown: keys.iter()
+                    .filter(|key| !english.contains(key))
+                    .copied()
+                    .collect()
+            })
+            .filter(|report| !report.missing.is_empty() || !report.unknown.is_empty())
+            .collect();
+
+        let unused = english.iter()
+            .filter(|key| !keys::REFERENCED_KEYS.contains(key))
+            .copied()
+            .collect();
+
+        let undefined = keys::REQUESTED_KEYS.iter()
+            .filter(|(key, _, _)| !english.contains(key) && !keys::COMMON_KEYS.contains(key))
+            .copied()
+            .collect();
+
+        Self {
+            locales,
+            unused,
+            undefined
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locales.is_empty() && self.unused.is_empty() && self.undefined.is_empty()
+    }
+}
+
+impl Default for LocalesReport {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for LocalesReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "All the locales are complete");
+        }
+
+        for locale in &self.locales {
+            writeln!(f, "[{}]", locale.locale)?;
+
+            if !locale.missing.is_empty() {
+                writeln!(f, "  [Missing]")?;
+
+                for key in &locale.missing {
+                    writeln!(f, "    {key}")?;
+                }
+            }
+
+            if !locale.unknown.is_empty() {
+                writeln!(f, "  [Unknown]")?;
+
+                for key in &locale.unknown {
+                    writeln!(f, "    {key}")?;
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        if !self.unused.is_empty() {
+            writeln!(f, "[en]\n  [Unused]")?;
+
+            for key in &self.unused {
+                writeln!(f, "    {key}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        if !self.undefined.is_empty() {
+            writeln!(f, "[src]\n  [Undefined]")?;
+
+            for (key, file, line) in &self.undefined {
+                writeln!(f, "    {key} ({file}, line {line})")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_has_requested_keys() {
+        let report = LocalesReport::new();
+
+        assert!(report.undefined.is_empty(), "Messages are used in the code but not defined in English locale: {:?}", report.undefined);
+    }
+}
@@ -0,0 +1,686 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Child};
+use std::os::unix::process::CommandExt;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::{Config, Schema};
+
+use anime_launcher_sdk::config::schema_blanks::prelude::*;
+
+use crate::hooks::Hook;
+
+/// Game executable started by the windows part of the command
+pub const GAME_EXECUTABLE: &str = "StarRail.exe";
+
+/// Placeholders supported in the game command template with their description locale keys
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("%command%",         "placeholder-command"),
+    ("%bash_command%",    "placeholder-bash-command"),
+    ("%windows_command%", "placeholder-windows-command"),
+    ("%game%",            "placeholder-game"),
+    ("%prefix%",          "placeholder-prefix"),
+    ("%build%",           "placeholder-build"),
+    ("%temp%",            "placeholder-temp"),
+    ("%launcher%",        "placeholder-launcher")
+];
+
+/// Problem found in the game command template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateIssue {
+    /// Template doesn't run the game
+    NoCommand,
+
+    /// Placeholder which won't be replaced
+    UnknownPlaceholder(String),
+
+    /// Quote which is never closed
+    UnclosedQuote(char)
+}
+
+/// Check the game command template
+pub fn validate(template: &str) -> Vec<TemplateIssue> {
+    let mut issues = Vec::new();
+
+    let template = template.trim();
+
+    if template.is_empty() {
+        return issues;
+    }
+
+    if !template.contains("%command%") && !template.contains("%bash_command%") {
+        issues.push(TemplateIssue::NoCommand);
+    }
+
+    // Find %word% sequences
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+
+        let word = &rest[start..start + len + 2];
+
+        if word.len() > 2 && word[1..word.len() - 1].chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            if !PLACEHOLDERS.iter().any(|(placeholder, _)| *placeholder == word) {
+                let issue = TemplateIssue::UnknownPlaceholder(word.to_string());
+
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            }
+
+            rest = &rest[start + len + 2..];
+        }
+
+        // Not a placeholder, e.g. "100% %command%"
+        else {
+            rest = &rest[start + 1..];
+        }
+    }
+
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in template.chars() {
+        match (quote, c) {
+            (_, _) if escaped => escaped = false,
+
+            (None | Some('"'), '\\') => escaped = true,
+
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), c) if open == c => quote = None,
+
+            _ => ()
+        }
+    }
+
+    if let Some(quote) = quote {
+        issues.push(TemplateIssue::UnclosedQuote(quote));
+    }
+
+    issues
+}
+
+/// Quote string for bash
+pub fn shell_quote(string: impl AsRef<str>) -> String {
+    format!("'{}'", string.as_ref().replace('\'', "'\\''"))
+}
+
+/// Replace path placeholders of the game command template
+pub fn replace_keywords(template: &str, config: &Schema) -> String {
+    let build = config.game.wine.selected.as_ref()
+        .map(|wine| config.game.wine.builds.join(wine))
+        .unwrap_or_else(|| config.game.wine.builds.clone());
+
+    let temp = config.launcher.temp.clone().unwrap_or_else(std::env::temp_dir);
+
+    template
+        .replace("%build%", &build.to_string_lossy())
+        .replace("%prefix%", &config.game.wine.prefix.to_string_lossy())
+        .replace("%temp%", &temp.to_string_lossy())
+        .replace("%launcher%", &crate::LAUNCHER_FOLDER.to_string_lossy())
+        .replace("%game%", &config.game.path.for_edition(config.launcher.edition).to_string_lossy())
+}
+
+/// Get gamescope command with arguments from the config
+pub fn gamescope_command(config: &Schema) -> String {
+    let gamescope = &config.game.enhancements.gamescope;
+
+    let mut command = String::from("gamescope");
+
+    let sizes = [
+        ("-w", gamescope.game.width),
+        ("-h", gamescope.game.height),
+        ("-W", gamescope.gamescope.width),
+        ("-H", gamescope.gamescope.height),
+        ("-r", gamescope.framerate.focused),
+        ("-o", gamescope.framerate.unfocused)
+    ];
+
+    for (flag, value) in sizes {
+        if value > 0 {
+            command += &format!(" {flag} {value}");
+        }
+    }
+
+    if gamescope.integer_scaling {
+        command += " -n";
+    }
+
+    if gamescope.fsr {
+        command += " -U";
+    }
+
+    if gamescope.nis {
+        command += " -Y";
+    }
+
+    command += match gamescope.window_type {
+        WindowType::Borderless => " -b",
+        WindowType::Fullscreen => " -f"
+    };
+
+    command
+}
+
+/// Get bubblewrap command with arguments from the config
+pub fn sandbox_command(config: &Schema, game_path: &Path) -> String {
+    let sandbox = &config.sandbox;
+
+    let mut command = String::from("bwrap --ro-bind / / --dev-bind /dev /dev --proc /proc --tmpfs /tmp");
+
+    if sandbox.isolate_home {
+        if let Some(home) = std::env::var_os("HOME") {
+            command += &format!(" --tmpfs {}", shell_quote(home.to_string_lossy()));
+        }
+    }
+
+    if let Some(hostname) = &sandbox.hostname {
+        command += &format!(" --unshare-uts --hostname {}", shell_quote(hostname));
+    }
+
+    for path in &sandbox.private {
+        command += &format!(" --tmpfs {}", shell_quote(path));
+    }
+
+    for (from, to) in &sandbox.mounts.read_only {
+        command += &format!(" --ro-bind {} {}", shell_quote(from), shell_quote(to));
+    }
+
+    for (from, to) in &sandbox.mounts.bind {
+        command += &format!(" --bind {} {}", shell_quote(from), shell_quote(to));
+    }
+
+    for (from, to) in &sandbox.mounts.symlinks {
+        command += &format!(" --symlink {} {}", shell_quote(from), shell_quote(to));
+    }
+
+    // Game needs write access to its own files
+    let mut writable = vec![game_path.to_path_buf(), config.game.wine.prefix.clone()];
+
+    if let Some(temp) = &config.launcher.temp {
+        writable.push(temp.clone());
+    }
+
+    for path in writable {
+        let path = shell_quote(path.to_string_lossy());
+
+        command += &format!(" --bind {path} {path}");
+    }
+
+    if let Some(args) = &sandbox.args {
+        command += " ";
+        command += args.trim();
+    }
+
+    command + " --"
+}
+
+/// Command which is executed to launch the game
+///
+/// The launcher starts the game with it, so the preview
+/// and the dry run report show exactly what will be executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchCommand {
+    /// Folder the command is started from
+    pub current_dir: PathBuf,
+
+    /// Environment variables set for the command
+    pub environment: Vec<(String, String)>,
+
+    /// Command given to `bash -c`
+    pub command: String,
+
+    /// Processes started by the command, from the outer one to the game itself
+    pub process_tree: Vec<String>
+}
+
+impl LaunchCommand {
+    /// Build the game launching command from the config
+    ///
+    /// Fails if there's no selected wine version
+    pub fn build(config: &Schema) -> anyhow::Result<Self> {
+        let Some(wine) = config.get_selected_wine()? else {
+            anyhow::bail!("Wine is not selected");
+        };
+
+        let build = config.game.wine.builds.join(&wine.name);
+        let wine = wine.to_wine(&config.components.path, Some(&build));
+
+        Ok(Self::with_wine(config, &wine.binary, &build))
+    }
+
+    /// Build the game launching command with given wine binary and its build folder
+    ///
+    /// Game command is wrapped by the custom command template, then by sandbox and gamescope
+    pub fn with_wine(config: &Schema, wine_binary: &Path, build: &Path) -> Self {
+        let game_path = config.game.path.for_edition(config.launcher.edition).to_path_buf();
+
+        // Filled from the game to the outer process and reversed at the end
+        let mut process_tree = Vec::new();
+
+        let mut bash_command = String::new();
+
+        if config.game.enhancements.gamemode {
+            bash_command += "gamemoderun ";
+        }
+
+        bash_command += &shell_quote(wine_binary.to_string_lossy());
+        bash_command += " ";
+
+        let mut windows_command = String::new();
+
+        if config.game.wine.virtual_desktop.enabled {
+            windows_command += &format!("explorer /desktop=the_honkers_railway,{}x{} ",
+                config.game.wine.virtual_desktop.width,
+                config.game.wine.virtual_desktop.height
+            );
+        }
+
+        windows_command += GAME_EXECUTABLE;
+
+        if config.game.wine.borderless {
+            windows_command += " -screen-fullscreen 0 -popupwindow";
+        }
+
+        process_tree.push(windows_command.clone());
+        process_tree.push(wine_binary.to_string_lossy().to_string());
+
+        if config.game.enhancements.gamemode {
+            process_tree.push(String::from("gamemoderun"));
+        }
+
+        let bash_command = bash_command + &windows_command;
+
+        let mut command = match &config.game.command {
+            Some(template) if !template.trim().is_empty() => {
+                let template = replace_keywords(template.trim(), config);
+
+                process_tree.push(template.clone());
+
+                template
+                    .replace("%command%", &bash_command)
+                    .replace("%bash_command%", &bash_command)
+                    .replace("%windows_command%", &windows_command)
+            }
+
+            _ => bash_command
+        };
+
+        if config.sandbox.enabled {
+            let sandbox = sandbox_command(config, &game_path);
+
+            process_tree.push(format!("{sandbox} bash -c"));
+
+            command = format!("{sandbox} bash -c {}", shell_quote(command));
+        }
+
+        if config.game.enhancements.gamescope.enabled {
+            let gamescope = gamescope_command(config);
+
+            process_tree.push(format!("{gamescope} --"));
+
+            command = format!("{gamescope} -- {command}");
+        }
+
+        process_tree.push(String::from("bash -c"));
+        process_tree.reverse();
+
+        let mut environment = vec![
+            (String::from("WINEARCH"), String::from("win64")),
+            (String::from("WINEPREFIX"), config.game.wine.prefix.to_string_lossy().to_string())
+        ];
+
+        let variables = config.game.wine.sync.get_env_vars().into_iter()
+            .chain(config.game.enhancements.hud.get_env_vars(config.game.enhancements.gamescope.enabled))
+            .chain(config.game.wine.language.get_env_vars())
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .chain(config.game.enhancements.fsr.get_env_vars().into_iter().map(|(key, value)| (key.to_string(), value.to_string())))
+            .chain(config.game.environment.iter().map(|(key, value)| (key.clone(), value.clone())));
+
+        let mut shared_libraries = Vec::new();
+
+        if config.game.wine.shared_libraries.wine {
+            shared_libraries.push((
+                "LD_LIBRARY_PATH",
+                ["lib64", "lib", "lib64/wine/x86_64-unix", "lib/wine/x86_64-unix", "lib/wine/i386-unix"].as_slice()
+            ));
+        }
+
+        if config.game.wine.shared_libraries.gstreamer {
+            shared_libraries.push((
+                "GST_PLUGIN_PATH",
+                ["lib64/gstreamer-1.0", "lib/gstreamer-1.0"].as_slice()
+            ));
+        }
+
+        let shared_libraries = shared_libraries.into_iter().map(|(key, folders)| {
+            let mut paths = folders.iter()
+                .map(|folder| build.join(folder).to_string_lossy().to_string())
+                .collect::<Vec<_>>();
+
+            // Keep system libraries available
+            if let Ok(current) = std::env::var(key) {
+                paths.push(current);
+            }
+
+            (key.to_string(), paths.join(":"))
+        });
+
+        // Later variables override earlier ones
+        for (key, value) in shared_libraries.chain(variables) {
+            environment.retain(|(name, _)| name != &key);
+            environment.push((key, value));
+        }
+
+        Self {
+            current_dir: game_path,
+            environment,
+            command,
+            process_tree
+        }
+    }
+
+    /// Build the game launching command from the current config file
+    #[inline]
+    pub fn get() -> anyhow::Result<Self> {
+        Self::build(&Config::get()?)
+    }
+
+    /// Start the command in its own process group
+    ///
+    /// Group id is the same as the started process id, so the game
+    /// can be stopped together with all the processes it started
+    pub fn spawn(&self) -> std::io::Result<Child> {
+        Command::new("bash")
+            .arg("-c")
+            .arg(&self.command)
+            .current_dir(&self.current_dir)
+            .envs(self.environment.iter().cloned())
+            .process_group(0)
+            .spawn()
+    }
+}
+
+impl std::fmt::Display for LaunchCommand {
+    /// Format the command as a shell script which can be copied to the terminal
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "cd {} && \\", shell_quote(self.current_dir.to_string_lossy()))?;
+
+        for (key, value) in &self.environment {
+            writeln!(f, "{key}={} \\", shell_quote(value))?;
+        }
+
+        write!(f, "bash -c {}", shell_quote(&self.command))
+    }
+}
+
+/// Describe what will be executed on the game launch without starting anything
+///
+/// Used by the dry run launch
+pub fn dry_run_report() -> anyhow::Result<String> {
+    let command = LaunchCommand::get()?;
+    let hooks = crate::settings::Settings::get().unwrap_or_default().hooks;
+
+    let mut report = String::from("Process tree:\n");
+
+    for (depth, process) in command.process_tree.iter().enumerate() {
+        if depth == 0 {
+            report += &format!("{process}\n");
+        } else {
+            report += &format!("{}└─ {process}\n", "   ".repeat(depth - 1));
+        }
+    }
+
+    report += &format!("\nWorking directory:\n{}\n", command.current_dir.to_string_lossy());
+
+    report += "\nEnvironment:\n";
+
+    for (key, value) in &command.environment {
+        report += &format!("{key}={value}\n");
+    }
+
+    report += "\nHooks:\n";
+
+    for (hook, script) in [(Hook::PreLaunch, &hooks.pre_launch), (Hook::PostExit, &hooks.post_exit)] {
+        match script.as_deref().map(str::trim).filter(|script| !script.is_empty()) {
+            Some(script) => report += &format!("{}: {script}\n", hook.name()),
+            None => report += &format!("{}: none\n", hook.name())
+        }
+    }
+
+    report += &format!("\nShell command:\n{command}\n");
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+    use super::*;
+
+    const WINE: &str = "/builds/wine-ge/bin/wine64";
+    const BUILD: &str = "/builds/wine-ge";
+
+    /// Config with everything which adds environment variables disabled
+    fn config() -> Schema {
+        let mut config = Schema::default();
+
+        config.launcher.edition = GameEdition::Global;
+        config.game.path.global = PathBuf::from("/games/StarRail");
+        config.game.wine.prefix = PathBuf::from("/prefixes/star rail");
+
+        config.game.wine.sync = WineSync::None;
+        config.game.wine.language = WineLang::System;
+        config.game.wine.borderless = false;
+        config.game.wine.virtual_desktop.enabled = false;
+        config.game.wine.shared_libraries.wine = false;
+        config.game.wine.shared_libraries.gstreamer = false;
+
+        config.game.enhancements.hud = HUD::None;
+        config.game.enhancements.fsr.enabled = false;
+        config.game.enhancements.gamemode = false;
+        config.game.enhancements.gamescope.enabled = false;
+
+        config.sandbox.enabled = false;
+
+        config.game.command = None;
+        config.game.environment.clear();
+
+        config
+    }
+
+    #[test]
+    fn validate_templates() {
+        assert!(validate("").is_empty());
+        assert!(validate("   ").is_empty());
+        assert!(validate("mangohud %command%").is_empty());
+        assert!(validate("%bash_command% --flag").is_empty());
+
+        // Percent sign which isn't a part of placeholder
+        assert!(validate("MANGOHUD_CONFIG=fps_limit=100% %command%").is_empty());
+
+        assert_eq!(validate("mangohud"), [TemplateIssue::NoCommand]);
+        assert_eq!(validate("%windows_command%"), [TemplateIssue::NoCommand]);
+    }
+
+    #[test]
+    fn validate_unknown_placeholders() {
+        assert_eq!(validate("%wine% %command% %wine% %game_path%"), [
+            TemplateIssue::UnknownPlaceholder(String::from("%wine%")),
+            TemplateIssue::UnknownPlaceholder(String::from("%game_path%"))
+        ]);
+
+        assert_eq!(validate("%unknown%"), [
+            TemplateIssue::NoCommand,
+            TemplateIssue::UnknownPlaceholder(String::from("%unknown%"))
+        ]);
+
+        // Every known placeholder is accepted
+        let template = PLACEHOLDERS.iter()
+            .map(|(placeholder, _)| *placeholder)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert!(validate(&template).is_empty());
+    }
+
+    #[test]
+    fn validate_quotes() {
+        assert!(validate("bash -c '%command%'").is_empty());
+        assert!(validate("echo \"it's\" && %command%").is_empty());
+        assert!(validate("echo \\\" %command%").is_empty());
+        assert!(validate("echo 'back\\' %command%").is_empty());
+
+        assert_eq!(validate("mangohud \"%command%"), [TemplateIssue::UnclosedQuote('"')]);
+        assert_eq!(validate("echo it's %command%"), [TemplateIssue::UnclosedQuote('\'')]);
+        assert_eq!(validate("echo \"\\\" %command%"), [TemplateIssue::UnclosedQuote('"')]);
+    }
+
+    #[test]
+    fn shell_quote_strings() {
+        assert_eq!(shell_quote("simple"), "'simple'");
+        assert_eq!(shell_quote("with spaces"), "'with spaces'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+
+        for string in ["with spaces", "it's \"quoted\"", "$HOME `id` \\n", "'", "\"'\"'"] {
+            let output = Command::new("bash")
+                .args(["-c", &format!("printf %s {}", shell_quote(string))])
+                .output()
+                .unwrap();
+
+            assert_eq!(String::from_utf8_lossy(&output.stdout), string);
+        }
+    }
+
+    #[test]
+    fn replace_path_placeholders() {
+        let mut config = config();
+
+        config.game.wine.builds = PathBuf::from("/builds");
+        config.game.wine.selected = Some(String::from("wine-ge"));
+        config.launcher.temp = Some(PathBuf::from("/tmp/launcher"));
+
+        let template = "%game% %prefix% %build% %temp% %launcher% %command% %unknown%";
+
+        assert_eq!(replace_keywords(template, &config), format!(
+            "/games/StarRail /prefixes/star rail /builds/wine-ge /tmp/launcher {} %command% %unknown%",
+            crate::LAUNCHER_FOLDER.to_string_lossy()
+        ));
+    }
+
+    #[test]
+    fn plain_command() {
+        let command = LaunchCommand::with_wine(&config(), Path::new(WINE), Path::new(BUILD));
+
+        assert_eq!(command.command, format!("'{WINE}' StarRail.exe"));
+        assert_eq!(command.current_dir, PathBuf::from("/games/StarRail"));
+
+        assert_eq!(command.process_tree, [
+            String::from("bash -c"),
+            String::from(WINE),
+            String::from("StarRail.exe")
+        ]);
+
+        assert_eq!(command.environment, [
+            (String::from("WINEARCH"), String::from("win64")),
+            (String::from("WINEPREFIX"), String::from("/prefixes/star rail"))
+        ]);
+    }
+
+    #[test]
+    fn template_and_environment() {
+        let mut config = config();
+
+        config.game.enhancements.gamemode = true;
+        config.game.command = Some(String::from(" mangohud %command% --flag "));
+
+        config.game.environment.insert(String::from("WINEARCH"), String::from("win32"));
+        config.game.environment.insert(String::from("DXVK_HUD"), String::from("fps"));
+
+        let command = LaunchCommand::with_wine(&config, Path::new(WINE), Path::new(BUILD));
+
+        assert_eq!(command.command, format!("mangohud gamemoderun '{WINE}' StarRail.exe --flag"));
+
+        assert_eq!(command.process_tree, [
+            String::from("bash -c"),
+            String::from("mangohud %command% --flag"),
+            String::from("gamemoderun"),
+            String::from(WINE),
+            String::from("StarRail.exe")
+        ]);
+
+        // Custom variables override the launcher ones
+        assert_eq!(command.environment[0], (String::from("WINEPREFIX"), String::from("/prefixes/star rail")));
+        assert!(command.environment.contains(&(String::from("WINEARCH"), String::from("win32"))));
+        assert!(command.environment.contains(&(String::from("DXVK_HUD"), String::from("fps"))));
+        assert_eq!(command.environment.len(), 3);
+    }
+
+    #[test]
+    fn windows_command_placeholder() {
+        let mut config = config();
+
+        config.game.wine.borderless = true;
+        config.game.command = Some(String::from("%build%/bin/wine64 %windows_command%"));
+
+        let command = LaunchCommand::with_wine(&config, Path::new(WINE), Path::new(BUILD));
+
+        assert_eq!(command.command, "/builds/wine-ge/bin/wine64 StarRail.exe -screen-fullscreen 0 -popupwindow");
+    }
+
+    #[test]
+    fn sandbox_inside_gamescope() {
+        let mut config = config();
+
+        config.sandbox.enabled = true;
+        config.game.enhancements.gamescope.enabled = true;
+
+        let command = LaunchCommand::with_wine(&config, Path::new(WINE), Path::new(BUILD));
+
+        let sandbox = sandbox_command(&config, Path::new("/games/StarRail"));
+        let gamescope = gamescope_command(&config);
+
+        assert_eq!(command.command, format!("{gamescope} -- {sandbox} bash -c {}", shell_quote(format!("'{WINE}' StarRail.exe"))));
+
+        assert_eq!(command.process_tree, [
+            String::from("bash -c"),
+            format!("{gamescope} --"),
+            format!("{sandbox} bash -c"),
+            String::from(WINE),
+            String::from("StarRail.exe")
+        ]);
+
+        // Game folder and prefix are writable
+        assert!(sandbox.contains("--bind '/games/StarRail' '/games/StarRail'"));
+        assert!(sandbox.contains("--bind '/prefixes/star rail' '/prefixes/star rail'"));
+    }
+
+    #[test]
+    fn spawn_in_own_process_group() {
+        let folder = std::env::temp_dir().join(format!("launch-command-test-{}", std::process::id()));
+
+        std::fs::create_dir_all(&folder).unwrap();
+
+        // 5th field of the stat file is the process group id
+        let command = LaunchCommand {
+            current_dir: folder.clone(),
+            environment: vec![(String::from("LAUNCH_TEST"), String::from("it's a value"))],
+            command: String::from("printf '%s\\n' \"$LAUNCH_TEST\" \"$PWD\" > output; cut -d ' ' -f 5 /proc/$$/stat >> output"),
+            process_tree: vec![]
+        };
+
+        let mut child = command.spawn().unwrap();
+
+        assert!(child.wait().unwrap().success());
+
+        let output = std::fs::read_to_string(folder.join("output")).unwrap();
+
+        std::fs::remove_dir_all(&folder).unwrap();
+
+        assert_eq!(output, format!("it's a value\n{}\n{}\n", folder.to_string_lossy(), child.id()));
+    }
+}
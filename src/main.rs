@@ -4,7 +4,9 @@ use anime_launcher_sdk::config::ConfigExt;
 use anime_launcher_sdk::star_rail::config::{Config, Schema};
 
 use anime_launcher_sdk::star_rail::states::LauncherState;
-use anime_launcher_sdk::star_rail::consts::launcher_dir;
+use anime_launcher_sdk::star_rail::sessions::Sessions;
+use anime_launcher_sdk::sessions::SessionsExt;
+use anime_launcher_sdk::star_rail::consts::{launcher_dir, config_file};
 
 use anime_launcher_sdk::anime_game_core::prelude::*;
 use anime_launcher_sdk::anime_game_core::star_rail::prelude::*;
@@ -17,25 +19,29 @@ use std::path::PathBuf;
 pub mod move_folder;
 pub mod i18n;
 pub mod background;
+pub mod config_validation;
+pub mod config_backups;
+pub mod config_watcher;
+pub mod settings;
+pub mod game_language;
+pub mod warps;
+pub mod single_instance;
+pub mod update_check;
+pub mod systemd;
+pub mod shortcuts;
+pub mod hooks;
+pub mod launch_command;
+pub mod environment;
 pub mod ui;
 
 use ui::main::*;
 use ui::first_run::main::*;
+use ui::config_recovery::{self, ConfigRecoveryApp};
 
 pub const APP_ID: &str = "moe.launcher.the-honkers-railway-launcher";
 pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const APP_DEBUG: bool = cfg!(debug_assertions);
 
-/// Sets to `true` when the `App` component is ready (fully initialized)
-pub static mut READY: bool = false;
-
-// TODO: get rid of using this function in all the components' events
-//       e.g. by converting preferences pages into Relm4 Components
-/// Check if the app is ready
-pub fn is_ready() -> bool {
-    unsafe { READY }
-}
-
 lazy_static::lazy_static! {
     /// Config loaded on the app's start. Use `Config::get()` to get up to date config instead.
     /// This one is used to prepare some launcher UI components on start
@@ -64,6 +70,84 @@ lazy_static::lazy_static! {
 }
 
 fn main() {
+    // Force debug output
+    let force_debug = std::env::args().any(|arg| &arg == "--debug");
+
+    // Commands without the launcher window print their logs to stderr
+    let headless = matches!(std::env::args().nth(1).as_deref(), Some("warp-url" | "check-updates" | "systemd-units")) ||
+        std::env::args().any(|arg| &arg == "--dry-run");
+
+    if headless {
+        let stderr = tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter({
+                if APP_DEBUG || force_debug {
+                    LevelFilter::TRACE
+                } else {
+                    LevelFilter::WARN
+                }
+            })
+            .with_filter(filter_fn(|metadata| {
+                !metadata.target().contains("rustls")
+            }));
+
+        tracing_subscriber::registry()
+            .with(stderr)
+            .init();
+    }
+
+    // Print locales report and exit
+    if std::env::args().any(|arg| &arg == "--locales-report") {
+        print!("{}", i18n::report::LocalesReport::new());
+
+        return;
+    }
+
+    // Commands without the launcher window can't show config recovery window
+    if headless {
+        let issues = config_validation::validate();
+
+        if !issues.is_empty() {
+            eprintln!("Config file is invalid:");
+
+            for issue in issues {
+                eprintln!("- {issue}");
+            }
+
+            std::process::exit(1);
+        }
+    }
+
+    // Print warps URL and exit
+    if std::env::args().nth(1).as_deref() == Some("warp-url") {
+        match warps::url::get() {
+            Ok(Some(url)) => {
+                println!("{}", url.url);
+
+                let age = url.age().as_secs() / 60;
+
+                match url.expires_in() {
+                    Some(left) => eprintln!("Cached {age} minutes ago, expires in {} minutes", left.as_secs() / 60),
+                    None => eprintln!("Cached {age} minutes ago and probably expired")
+                }
+            }
+
+            Ok(None) => {
+                eprintln!("No warps URL found");
+
+                std::process::exit(1);
+            }
+
+            Err(err) => {
+                eprintln!("Failed to find warps URL: {err}");
+
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
     // Create launcher folder if it isn't
     if !LAUNCHER_FOLDER.exists() {
         std::fs::create_dir_all(LAUNCHER_FOLDER.as_path()).expect("Failed to create launcher folder");
@@ -79,8 +163,69 @@ fn main() {
         Config::update_raw(config).expect("Failed to update config");
     }
 
-    // Force debug output
-    let force_debug = std::env::args().any(|arg| &arg == "--debug");
+    // Check updates and exit. Used by the systemd timer
+    if std::env::args().nth(1).as_deref() == Some("check-updates") {
+        let result = update_check::CommandOptions::from_args(std::env::args().skip(2))
+            .and_then(update_check::run_command);
+
+        if let Err(err) = result {
+            eprintln!("Failed to check updates: {err}");
+
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    // Install or remove systemd units and exit
+    if std::env::args().nth(1).as_deref() == Some("systemd-units") {
+        let result = match std::env::args().nth(2).as_deref() {
+            Some("remove") => systemd::remove().map(|_| println!("Systemd units removed")),
+
+            Some("install") => systemd::UnitsOptions::from_args(std::env::args().skip(3))
+                .and_then(|options| systemd::install(&options))
+                .map(|(service, timer)| {
+                    println!("Written {}", service.display());
+                    println!("Written {}", timer.display());
+                    println!("Timer {}.timer enabled", systemd::UNIT_NAME);
+                }),
+
+            // Print units without installing them
+            _ => std::env::current_exe()
+                .map_err(anyhow::Error::from)
+                .and_then(|exe| {
+                    let options = systemd::UnitsOptions::from_args(std::env::args().skip(2))?;
+
+                    println!("# {}.service\n{}", systemd::UNIT_NAME, systemd::service_unit(&options, &exe));
+                    println!("# {}.timer\n{}", systemd::UNIT_NAME, systemd::timer_unit(&options));
+
+                    Ok(())
+                })
+        };
+
+        if let Err(err) = result {
+            eprintln!("Failed to manage systemd units: {err}");
+
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    // Print the game launching command and exit
+    if std::env::args().any(|arg| &arg == "--dry-run") {
+        match launch_command::dry_run_report() {
+            Ok(report) => print!("{report}"),
+
+            Err(err) => {
+                eprintln!("Failed to build the game command: {err}");
+
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
 
     // Run the game
     let run_game = std::env::args().any(|arg| &arg == "--run-game");
@@ -88,6 +233,31 @@ fn main() {
     // Forcely run the game
     let just_run_game = std::env::args().any(|arg| &arg == "--just-run-game");
 
+    // Select game session before the game is started by this or already running launcher
+    let args = std::env::args().collect::<Vec<_>>();
+
+    if let Some(index) = args.iter().position(|arg| arg == "--session") {
+        let Some(session) = args.get(index + 1) else {
+            eprintln!("Session name is not specified");
+
+            std::process::exit(1);
+        };
+
+        if let Err(err) = select_session(session) {
+            eprintln!("Failed to select game session {session}: {err}");
+
+            std::process::exit(1);
+        }
+    }
+
+    // Don't start second launcher touching the same files
+    match single_instance::forward() {
+        Ok(true) => return,
+        Ok(false) => (),
+
+        Err(err) => eprintln!("Failed to check running launcher: {err}")
+    }
+
     // Prepare stdout logger
     let stdout = tracing_subscriber::fmt::layer()
         .pretty()
@@ -123,6 +293,14 @@ fn main() {
 
     tracing::info!("Starting application ({APP_VERSION})");
 
+    if APP_DEBUG {
+        let report = i18n::report::LocalesReport::new();
+
+        if !report.is_empty() {
+            tracing::warn!("Some translations are incomplete:\n{report}");
+        }
+    }
+
     adw::init().expect("Libadwaita initialization failed");
 
     // Register and include resources
@@ -161,12 +339,91 @@ fn main() {
         .round-bin {{
             border-radius: 24px;
         }}
+
+        .big-picture-tile {{
+            min-width: 220px;
+            min-height: 180px;
+            border-radius: 24px;
+        }}
+
+        .big-picture-tile:focus-visible {{
+            outline: 4px solid @accent_color;
+            outline-offset: 4px;
+        }}
     ", BACKGROUND_FILE.to_string_lossy()));
 
+    // Load user translations
+    if let Err(err) = i18n::overrides::load() {
+        tracing::error!("Failed to load user locales: {err}");
+    }
+
+    // Validate config file before it will be loaded
+    let issues = config_validation::validate();
+
+    if !issues.is_empty() {
+        tracing::error!("Config file is invalid: {:?}", issues);
+
+        // Config language can't be trusted here
+        if let Err(err) = i18n::set_lang(i18n::get_default_lang()) {
+            tracing::warn!("Failed to set system language: {err}");
+        }
+
+        // Create the app
+        let app = RelmApp::new(APP_ID);
+
+        single_instance::serve(&relm4::main_application());
+
+        // Show config recovery window
+        app.run::<ConfigRecoveryApp>(issues);
+
+        // Start the launcher again only after this one has released its bus name,
+        // otherwise the new process would forward its arguments to this one
+        if let Some(exe) = config_recovery::take_restart() {
+            tracing::info!("Restarting the launcher");
+
+            if let Err(err) = std::process::Command::new(exe).args(std::env::args().skip(1)).spawn() {
+                tracing::error!("Failed to restart the launcher: {err}");
+
+                eprintln!("Failed to restart the launcher: {err}");
+
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    // Settings file can't be fixed manually as easy as the config, so it's replaced by the latest backup
+    if let Err(err) = settings::Settings::validate() {
+        tracing::error!("Settings file is invalid: {err}");
+
+        if let Err(err) = settings::Settings::recover() {
+            tracing::error!("Failed to recover settings file: {err}");
+        }
+    }
+
+    // Backup valid config file if it was changed since the last backup
+    if let Err(err) = config_backups::create_if_changed() {
+        tracing::warn!("Failed to backup config file: {err}");
+    }
+
+    // Remember loaded config to find external changes later
+    if config_file().map(|path| path.exists()).unwrap_or_default() {
+        if let Err(err) = config_watcher::mark_file_synced() {
+            tracing::warn!("Failed to read config file: {err}");
+        }
+    }
+
     // Set UI language
-    let lang = CONFIG.launcher.language.parse().expect("Wrong language format used in config");
+    match CONFIG.launcher.language.parse() {
+        Ok(lang) => {
+            if let Err(err) = i18n::set_lang(lang) {
+                tracing::error!("Failed to set launcher language: {err}");
+            }
+        }
 
-    i18n::set_lang(lang).expect("Failed to set launcher language");
+        Err(err) => tracing::error!("Wrong language format used in config: {err}")
+    }
 
     tracing::info!("Set UI language to {}", i18n::get_lang());
 
@@ -175,6 +432,8 @@ fn main() {
         // Create the app
         let app = RelmApp::new(APP_ID);
 
+        single_instance::serve(&relm4::main_application());
+
         // Show first run window
         app.run::<FirstRunApp>(());
     }
@@ -187,7 +446,7 @@ fn main() {
 
             match state {
                 LauncherState::Launch => {
-                    anime_launcher_sdk::star_rail::game::run().expect("Failed to run the game");
+                    run_game_headless();
 
                     return;
                 }
@@ -195,7 +454,7 @@ fn main() {
                 LauncherState::PredownloadAvailable { .. } |
                 LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. }) => {
                     if just_run_game {
-                        anime_launcher_sdk::star_rail::game::run().expect("Failed to run the game");
+                        run_game_headless();
 
                         return;
                     }
@@ -208,7 +467,26 @@ fn main() {
         // Create the app
         let app = RelmApp::new(APP_ID);
 
+        single_instance::serve(&relm4::main_application());
+
         // Show main window
         app.run::<App>(());
     }
 }
+
+/// Make the game session current. It's applied when the game is launched
+fn select_session(name: &str) -> anyhow::Result<()> {
+    if !Sessions::get_sessions()?.sessions.contains_key(name) {
+        anyhow::bail!("Session doesn't exist");
+    }
+
+    Ok(Sessions::set_current(name.to_string())?)
+}
+
+/// Run the game without the launcher window. Errors are printed to stderr
+fn run_game_headless() {
+    hooks::run_game(
+        |err| eprintln!("Failed to apply game language: {err}"),
+        |hook, err| eprintln!("Failed to run {} hook: {err}", hook.name())
+    ).expect("Failed to run the game");
+}
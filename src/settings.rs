@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Serialize, Deserialize};
+
+use crate::game_language::{GameTextLanguage, GameVoiceLanguage};
+
+lazy_static::lazy_static! {
+    /// Path to `settings.json` file with launcher settings which are not stored in the config file.
+    /// Standard is `$HOME/.local/share/anime-game-launcher/settings.json`
+    pub static ref SETTINGS_FILE: PathBuf = crate::LAUNCHER_FOLDER.join("settings.json");
+}
+
+/// Launcher-specific settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub launcher: LauncherSettings,
+    pub updates: UpdatesSettings,
+    pub game: GameSettings,
+    pub hooks: HooksSettings,
+    pub environment: EnvironmentSettings
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LauncherSettings {
+    /// Show tray icon while the launcher window is hidden
+    pub tray_icon: bool,
+
+    /// Hide launcher window to the tray instead of closing it.
+    /// Predownloads are started automatically in this mode
+    pub keep_in_background: bool,
+
+    /// Send desktop notifications about finished operations and available updates
+    pub notifications: bool,
+
+    /// Fullscreen layout with large tiles and gamepad navigation
+    pub big_picture: BigPictureMode
+}
+
+impl Default for LauncherSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            tray_icon: true,
+            keep_in_background: false,
+            notifications: true,
+            big_picture: BigPictureMode::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BigPictureMode {
+    /// Enable big picture mode in the gamescope session (e.g. Steam Deck's game mode)
+    #[default]
+    Auto,
+
+    Enabled,
+    Disabled
+}
+
+impl BigPictureMode {
+    pub const LIST: &[Self] = &[Self::Auto, Self::Enabled, Self::Disabled];
+
+    pub fn locale_key(&self) -> &'static str {
+        match self {
+            Self::Auto     => "big-picture-auto",
+            Self::Enabled  => "big-picture-enabled",
+            Self::Disabled => "big-picture-disabled"
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Auto     => is_gamescope_session(),
+            Self::Enabled  => true,
+            Self::Disabled => false
+        }
+    }
+}
+
+/// Check if the launcher is running in the gamescope session
+pub fn is_gamescope_session() -> bool {
+    std::env::var("XDG_CURRENT_DESKTOP").map(|desktop| desktop.eq_ignore_ascii_case("gamescope")).unwrap_or_default() ||
+    std::env::var_os("SteamGamepadUI").is_some() ||
+    std::env::var_os("GAMESCOPE_WAYLAND_DISPLAY").is_some()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdatesSettings {
+    /// Minutes between updates checks in the running launcher. 0 disables them
+    pub check_interval: u64,
+
+    /// Start predownloads automatically when they're found
+    pub auto_predownload: bool,
+
+    /// Hour from which predownloads can be started automatically
+    pub predownload_from: u8,
+
+    /// Hour until which predownloads can be started automatically.
+    /// Same as `predownload_from` means any time
+    pub predownload_to: u8
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// In-game text language. `None` keeps the one chosen in the game
+    pub text_language: Option<GameTextLanguage>,
+
+    /// In-game voice language. `None` keeps the one chosen in the game
+    pub voice_language: Option<GameVoiceLanguage>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksSettings {
+    /// Shell command executed before the game launch
+    pub pre_launch: Option<String>,
+
+    /// Shell command executed after the game was closed
+    pub post_exit: Option<String>,
+
+    /// Seconds after which hook commands are killed. 0 disables the timeout
+    pub timeout: u64,
+
+    /// Don't launch the game if the pre-launch hook failed
+    pub abort_on_failure: bool
+}
+
+impl Default for HooksSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pre_launch: None,
+            post_exit: None,
+            timeout: 60,
+            abort_on_failure: true
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentSettings {
+    /// Game environment variables which are kept but not used.
+    /// Enabled ones are stored in the config file
+    pub disabled: HashMap<String, String>,
+
+    /// Values of the variables replaced by the enabled presets.
+    /// They're restored when the preset is disabled
+    pub overridden: HashMap<String, String>
+}
+
+impl Settings {
+    /// Read settings file. Default settings are returned if it doesn't exist
+    pub fn get() -> anyhow::Result<Self> {
+        if !SETTINGS_FILE.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_slice(&std::fs::read(SETTINGS_FILE.as_path())?)?)
+    }
+
+    /// Write settings file
+    ///
+    /// Content is written to a temporary file first so the settings can't be lost if the launcher crashes
+    pub fn update(settings: &Self) -> anyhow::Result<()> {
+        let temp = SETTINGS_FILE.with_extension("json.tmp");
+
+        std::fs::write(&temp, serde_json::to_string_pretty(settings)?)?;
+        std::fs::rename(temp, SETTINGS_FILE.as_path())?;
+
+        crate::config_watcher::mark_settings_synced(settings.clone());
+
+        Ok(())
+    }
+
+    /// Check that the settings file can be read by the launcher
+    pub fn validate() -> anyhow::Result<()> {
+        Self::get().map(|_| ())
+    }
+
+    /// Replace broken settings file by the latest valid backup
+    ///
+    /// Broken file is kept as `settings.json.broken`. Default settings are used if there's no valid backup
+    pub fn recover() -> anyhow::Result<()> {
+        if SETTINGS_FILE.exists() {
+            std::fs::rename(SETTINGS_FILE.as_path(), SETTINGS_FILE.with_extension("json.broken"))?;
+        }
+
+        let backup = crate::config_backups::list()?
+            .into_iter()
+            .filter_map(|backup| backup.read_settings().ok().flatten())
+            .next();
+
+        if let Some(settings) = backup {
+            Self::update(&settings)?;
+        }
+
+        Ok(())
+    }
+}
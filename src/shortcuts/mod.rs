@@ -0,0 +1,275 @@
+use std::path::PathBuf;
+
+use gtk::gio;
+
+use anime_launcher_sdk::sessions::SessionsExt;
+use anime_launcher_sdk::star_rail::sessions::Sessions;
+
+use crate::APP_ID;
+
+pub mod steam;
+
+/// Kind of the generated shortcut
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutKind {
+    /// Open the launcher
+    Launcher,
+
+    /// Launch the game directly using `--run-game`,
+    /// with the given game session if it's set
+    Game(Option<String>)
+}
+
+impl ShortcutKind {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Launcher            => String::from("The Honkers Railway Launcher"),
+            Self::Game(None)          => String::from("The Honkers Railway"),
+            Self::Game(Some(session)) => format!("The Honkers Railway ({session})")
+        }
+    }
+
+    /// Name of the desktop entry file
+    ///
+    /// Differs from the packaged `honkers-railway-launcher.desktop` so the shortcut doesn't replace it
+    pub fn file_name(&self) -> String {
+        match self {
+            Self::Launcher   => String::from("honkers-railway-launcher-shortcut.desktop"),
+            Self::Game(None) => String::from("honkers-railway-launcher-game.desktop"),
+
+            Self::Game(Some(session)) => {
+                let session = session.chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+                    .collect::<String>();
+
+                format!("honkers-railway-launcher-game-{session}.desktop")
+            }
+        }
+    }
+
+    pub fn args(&self) -> Vec<String> {
+        match self {
+            Self::Launcher   => vec![],
+            Self::Game(None) => vec![String::from("--run-game")],
+
+            Self::Game(Some(session)) => vec![
+                String::from("--run-game"),
+                String::from("--session"),
+                session.clone()
+            ]
+        }
+    }
+}
+
+/// Game shortcuts for the current session and each of the saved game sessions
+pub fn game_shortcuts() -> Vec<ShortcutKind> {
+    let mut sessions = match Sessions::get_sessions() {
+        Ok(sessions) => sessions.sessions.into_keys().collect::<Vec<_>>(),
+
+        Err(err) => {
+            tracing::warn!("Failed to get game sessions: {err}");
+
+            vec![]
+        }
+    };
+
+    sessions.sort();
+
+    std::iter::once(ShortcutKind::Game(None))
+        .chain(sessions.into_iter().map(|session| ShortcutKind::Game(Some(session))))
+        .collect()
+}
+
+/// Path to the user's data folder. Standard is `$HOME/.local/share`
+fn data_folder() -> anyhow::Result<PathBuf> {
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(data) if !data.is_empty() => Ok(PathBuf::from(data)),
+
+        _ => match std::env::var_os("HOME") {
+            Some(home) => Ok(PathBuf::from(home).join(".local/share")),
+            None => anyhow::bail!("Failed to find data folder")
+        }
+    }
+}
+
+/// Command used to start the launcher
+///
+/// Shortcuts should call the AppImage or flatpak instead of the binary inside of them
+pub fn launcher_command() -> anyhow::Result<Vec<String>> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(vec![appimage.to_string_lossy().to_string()]);
+    }
+
+    if let Ok(flatpak) = std::env::var("FLATPAK_ID") {
+        return Ok(vec![String::from("flatpak"), String::from("run"), flatpak]);
+    }
+
+    Ok(vec![std::env::current_exe()?.to_string_lossy().to_string()])
+}
+
+/// Quote argument of the desktop entry's `Exec` key
+fn quote_exec_arg(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%");
+
+    if !escaped.contains(|c: char| c.is_whitespace() || "\"'\\><~|&;$*?#()`".contains(c)) {
+        return escaped;
+    }
+
+    let mut quoted = String::from("\"");
+
+    for c in escaped.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+
+    // Desktop entry strings have their own escaping layer
+    quoted.replace('\\', "\\\\")
+}
+
+/// Quote argument of the Steam shortcut's launch options if it's needed
+fn quote_launch_option(arg: &str) -> String {
+    if arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:".contains(c)) {
+        arg.to_string()
+    } else {
+        crate::launch_command::shell_quote(arg)
+    }
+}
+
+/// Generate desktop entry of the shortcut
+pub fn desktop_entry(kind: &ShortcutKind, command: &[String], icon: &str) -> String {
+    let exec = command.iter()
+        .chain(kind.args().iter())
+        .map(|arg| quote_exec_arg(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut entry = String::from("[Desktop Entry]\n");
+
+    entry += &format!("Name={}\n", kind.name());
+    entry += &format!("Icon={icon}\n");
+    entry += &format!("Exec={exec}\n");
+    entry += "Type=Application\n";
+    entry += "Categories=Game\n";
+    entry += "Terminal=false\n";
+
+    // Game shortcut starts the launcher which then runs the game
+    if kind == &ShortcutKind::Launcher {
+        entry += &format!("StartupWMClass={APP_ID}\n");
+    }
+
+    entry
+}
+
+/// Extract launcher icon from the bundled resources to the user's icons folder
+///
+/// Returns path to the icon
+pub fn install_icon() -> anyhow::Result<PathBuf> {
+    let icon = data_folder()?.join(format!("icons/hicolor/512x512/apps/{APP_ID}.png"));
+
+    let data = gio::resources_lookup_data("/org/app/images/icon.png", gio::ResourceLookupFlags::NONE)?;
+
+    if let Some(parent) = icon.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(&icon, &data)?;
+
+    Ok(icon)
+}
+
+/// Write desktop entry of the shortcut to the user's applications folder
+///
+/// Returns path to the entry
+pub fn create_desktop_entry(kind: &ShortcutKind) -> anyhow::Result<PathBuf> {
+    let icon = install_icon()?;
+    let command = launcher_command()?;
+
+    let folder = data_folder()?.join("applications");
+    let path = folder.join(kind.file_name());
+
+    std::fs::create_dir_all(&folder)?;
+    std::fs::write(&path, desktop_entry(kind, &command, &icon.to_string_lossy()))?;
+
+    Ok(path)
+}
+
+/// Add the game shortcuts to all the found Steam users
+///
+/// One shortcut runs the game with the current session,
+/// and one more is added for each of the game sessions
+///
+/// Returns number of updated users
+pub fn add_to_steam() -> anyhow::Result<usize> {
+    let files = steam::shortcuts_files();
+
+    if files.is_empty() {
+        anyhow::bail!("Steam users not found");
+    }
+
+    let icon = install_icon()?;
+    let mut command = launcher_command()?;
+
+    let exe = command.remove(0);
+
+    // Flatpak is started by name, so there's no folder to start from
+    let start_dir = match PathBuf::from(&exe).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => format!("\"{}\"", dir.to_string_lossy()),
+        _ => String::new()
+    };
+
+    let shortcuts = game_shortcuts().into_iter().map(|kind| {
+        let launch_options = command.iter()
+            .chain(kind.args().iter())
+            .map(|arg| quote_launch_option(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        steam::Shortcut {
+            name: kind.name(),
+            exe: format!("\"{exe}\""),
+            start_dir: start_dir.clone(),
+            icon: icon.to_string_lossy().to_string(),
+            launch_options
+        }
+    }).collect::<Vec<_>>();
+
+    for file in &files {
+        for shortcut in &shortcuts {
+            steam::add_shortcut(file, shortcut)?;
+        }
+    }
+
+    Ok(files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_game_shortcut() {
+        let kind = ShortcutKind::Game(Some(String::from("Alt account #2")));
+
+        assert_eq!(kind.name(), "The Honkers Railway (Alt account #2)");
+        assert_eq!(kind.file_name(), "honkers-railway-launcher-game-alt-account--2.desktop");
+
+        let entry = desktop_entry(&kind, &[String::from("/usr/bin/honkers-railway-launcher")], "icon");
+
+        assert!(entry.contains("Exec=/usr/bin/honkers-railway-launcher --run-game --session \"Alt account #2\"\n"));
+        assert!(!entry.contains("StartupWMClass"));
+
+        assert_eq!(quote_launch_option("--session"), "--session");
+        assert_eq!(quote_launch_option("Alt account #2"), "'Alt account #2'");
+    }
+
+    #[test]
+    fn shortcut_file_names() {
+        assert_ne!(ShortcutKind::Launcher.file_name(), "honkers-railway-launcher.desktop");
+        assert_ne!(ShortcutKind::Launcher.file_name(), ShortcutKind::Game(None).file_name());
+    }
+}
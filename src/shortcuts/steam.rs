@@ -0,0 +1,446 @@
+use std::path::{Path, PathBuf};
+
+/// Value of the binary VDF file used by Steam for `shortcuts.vdf`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Map(Vec<(String, Value)>),
+    String(String),
+    Int(u32),
+    Float(f32),
+    Long(u64)
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Map(values) => values.iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+
+            _ => None
+        }
+    }
+}
+
+const TYPE_MAP: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT: u8 = 0x02;
+const TYPE_FLOAT: u8 = 0x03;
+const TYPE_LONG: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> anyhow::Result<u8> {
+        let Some(byte) = self.data.get(self.offset) else {
+            anyhow::bail!("Unexpected end of file");
+        };
+
+        self.offset += 1;
+
+        Ok(*byte)
+    }
+
+    fn bytes<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        let Some(bytes) = self.data.get(self.offset..self.offset + N) else {
+            anyhow::bail!("Unexpected end of file");
+        };
+
+        self.offset += N;
+
+        Ok(bytes.try_into()?)
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        let Some(len) = self.data[self.offset..].iter().position(|byte| *byte == 0) else {
+            anyhow::bail!("Unterminated string at {}", self.offset);
+        };
+
+        // Don't try to fix wrong strings to not to corrupt shortcuts on saving
+        let string = String::from_utf8(self.data[self.offset..self.offset + len].to_vec())?;
+
+        self.offset += len + 1;
+
+        Ok(string)
+    }
+
+    fn map(&mut self) -> anyhow::Result<Vec<(String, Value)>> {
+        let mut values = Vec::new();
+
+        loop {
+            let value_type = self.byte()?;
+
+            if value_type == TYPE_END {
+                return Ok(values);
+            }
+
+            let key = self.string()?;
+
+            let value = match value_type {
+                TYPE_MAP    => Value::Map(self.map()?),
+                TYPE_STRING => Value::String(self.string()?),
+                TYPE_INT    => Value::Int(u32::from_le_bytes(self.bytes()?)),
+                TYPE_FLOAT  => Value::Float(f32::from_le_bytes(self.bytes()?)),
+                TYPE_LONG   => Value::Long(u64::from_le_bytes(self.bytes()?)),
+
+                _ => anyhow::bail!("Unknown value type {value_type:#04x} at {}", self.offset - 1)
+            };
+
+            values.push((key, value));
+        }
+    }
+}
+
+/// Parse binary VDF file
+pub fn parse(data: &[u8]) -> anyhow::Result<Vec<(String, Value)>> {
+    Reader { data, offset: 0 }.map()
+}
+
+fn write_map(values: &[(String, Value)], data: &mut Vec<u8>) {
+    for (key, value) in values {
+        let value_type = match value {
+            Value::Map(_)    => TYPE_MAP,
+            Value::String(_) => TYPE_STRING,
+            Value::Int(_)    => TYPE_INT,
+            Value::Float(_)  => TYPE_FLOAT,
+            Value::Long(_)   => TYPE_LONG
+        };
+
+        data.push(value_type);
+        data.extend_from_slice(key.as_bytes());
+        data.push(0);
+
+        match value {
+            Value::Map(values) => write_map(values, data),
+
+            Value::String(string) => {
+                data.extend_from_slice(string.as_bytes());
+                data.push(0);
+            }
+
+            Value::Int(value)   => data.extend_from_slice(&value.to_le_bytes()),
+            Value::Float(value) => data.extend_from_slice(&value.to_le_bytes()),
+            Value::Long(value)  => data.extend_from_slice(&value.to_le_bytes())
+        }
+    }
+
+    data.push(TYPE_END);
+}
+
+/// Serialize binary VDF file
+pub fn serialize(values: &[(String, Value)]) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    write_map(values, &mut data);
+
+    data
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFF_u32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+/// Non-Steam game shortcut
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    pub name: String,
+    pub exe: String,
+    pub start_dir: String,
+    pub icon: String,
+    pub launch_options: String
+}
+
+impl Shortcut {
+    /// App id used by Steam for non-Steam games. It's calculated from the executable and the name
+    pub fn app_id(&self) -> u32 {
+        crc32(format!("{}{}", self.exe, self.name).as_bytes()) | 0x80000000
+    }
+
+    fn to_value(&self) -> Value {
+        let string = |key: &str, value: &str| (key.to_string(), Value::String(value.to_string()));
+        let int = |key: &str, value: u32| (key.to_string(), Value::Int(value));
+
+        Value::Map(vec![
+            int("appid", self.app_id()),
+            string("AppName", &self.name),
+            string("Exe", &self.exe),
+            string("StartDir", &self.start_dir),
+            string("icon", &self.icon),
+            string("ShortcutPath", ""),
+            string("LaunchOptions", &self.launch_options),
+            int("IsHidden", 0),
+            int("AllowDesktopConfig", 1),
+            int("AllowOverlay", 1),
+            int("OpenVR", 0),
+            int("Devkit", 0),
+            string("DevkitGameID", ""),
+            int("DevkitOverrideAppID", 0),
+            int("LastPlayTime", 0),
+            string("FlatpakAppID", ""),
+            (String::from("tags"), Value::Map(vec![]))
+        ])
+    }
+}
+
+/// Add shortcut to the `shortcuts.vdf` file, or replace the existing one with the same app id
+///
+/// Previous file is kept as `shortcuts.vdf.bak`
+pub fn add_shortcut(file: impl AsRef<Path>, shortcut: &Shortcut) -> anyhow::Result<()> {
+    let file = file.as_ref();
+
+    let mut root = if file.exists() {
+        parse(&std::fs::read(file)?)?
+    } else {
+        vec![(String::from("shortcuts"), Value::Map(vec![]))]
+    };
+
+    let Some((_, Value::Map(shortcuts))) = root.iter_mut().find(|(key, _)| key.eq_ignore_ascii_case("shortcuts")) else {
+        anyhow::bail!("Wrong shortcuts file format");
+    };
+
+    let app_id = shortcut.app_id();
+
+    let existing = shortcuts.iter().position(|(_, value)| {
+        // Older Steam versions didn't store app id
+        matches!(value.get("appid"), Some(Value::Int(id)) if *id == app_id) ||
+        matches!(value.get("AppName"), Some(Value::String(name)) if name == &shortcut.name)
+    });
+
+    match existing {
+        Some(index) => shortcuts[index].1 = shortcut.to_value(),
+
+        None => {
+            let index = shortcuts.iter()
+                .filter_map(|(key, _)| key.parse::<usize>().ok())
+                .max()
+                .map(|index| index + 1)
+                .unwrap_or(0);
+
+            shortcuts.push((index.to_string(), shortcut.to_value()));
+        }
+    }
+
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Keep previous shortcuts in case Steam won't accept the new file
+    if file.exists() {
+        std::fs::copy(file, file.with_extension("vdf.bak"))?;
+    }
+
+    // Write to the temp file first so Steam never reads partially written one
+    let temp = file.with_extension("vdf.tmp");
+
+    std::fs::write(&temp, serialize(&root))?;
+    std::fs::rename(temp, file)?;
+
+    Ok(())
+}
+
+/// Find `shortcuts.vdf` files of all the Steam users
+pub fn shortcuts_files() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return vec![];
+    };
+
+    let roots = [
+        home.join(".steam/steam"),
+        home.join(".local/share/Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/data/Steam")
+    ];
+
+    let mut users = Vec::new();
+
+    for root in roots {
+        // ~/.steam/steam is usually a link to another root
+        let Ok(root) = root.canonicalize() else {
+            continue;
+        };
+
+        let Ok(entries) = root.join("userdata").read_dir() else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            let is_user = entry.file_name()
+                .to_str()
+                .map(|name| name != "0" && name.chars().all(|c| c.is_ascii_digit()))
+                .unwrap_or_default();
+
+            if is_user && path.join("config").is_dir() && !users.contains(&path) {
+                users.push(path);
+            }
+        }
+    }
+
+    users.into_iter()
+        .map(|user| user.join("config/shortcuts.vdf"))
+        .collect()
+}
+
+/// Check if Steam is running. It overwrites shortcuts file on exit
+pub fn is_running() -> bool {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return false;
+    };
+
+    std::fs::read_to_string(home.join(".steam/steam.pid")).ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .map(|pid| Path::new(&format!("/proc/{pid}")).exists())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shortcuts file with one entry, written by hand to not to depend on the serializer
+    fn fixture() -> Vec<u8> {
+        let mut data = Vec::new();
+
+        let key = |data: &mut Vec<u8>, value_type: u8, key: &str| {
+            data.push(value_type);
+            data.extend_from_slice(key.as_bytes());
+            data.push(0);
+        };
+
+        key(&mut data, TYPE_MAP, "shortcuts");
+        key(&mut data, TYPE_MAP, "0");
+
+        key(&mut data, TYPE_INT, "appid");
+        data.extend_from_slice(&0x8123_4567_u32.to_le_bytes());
+
+        key(&mut data, TYPE_STRING, "AppName");
+        data.extend_from_slice("Other game ✓\0".as_bytes());
+
+        key(&mut data, TYPE_STRING, "Exe");
+        data.extend_from_slice(b"\"/usr/bin/other game\"\0");
+
+        key(&mut data, TYPE_FLOAT, "Volume");
+        data.extend_from_slice(&0.75_f32.to_le_bytes());
+
+        key(&mut data, TYPE_LONG, "LastPlayTime");
+        data.extend_from_slice(&1_700_000_000_u64.to_le_bytes());
+
+        key(&mut data, TYPE_MAP, "tags");
+        key(&mut data, TYPE_STRING, "0");
+        data.extend_from_slice(b"favorite\0");
+        data.push(TYPE_END);
+
+        // End of the entry, shortcuts and the root
+        data.extend_from_slice(&[TYPE_END, TYPE_END, TYPE_END]);
+
+        data
+    }
+
+    fn shortcut(name: &str) -> Shortcut {
+        Shortcut {
+            name: name.to_string(),
+            exe: String::from("\"/usr/bin/honkers-railway-launcher\""),
+            start_dir: String::from("\"/usr/bin\""),
+            icon: String::from("/icon.png"),
+            launch_options: String::from("--run-game")
+        }
+    }
+
+    #[test]
+    fn parse_serialize_round_trip() {
+        let values = parse(&fixture()).unwrap();
+
+        assert_eq!(values[0].0, "shortcuts");
+
+        let entry = values[0].1.get("0").unwrap();
+
+        assert_eq!(entry.get("appname"), Some(&Value::String(String::from("Other game ✓"))));
+        assert_eq!(entry.get("appid"), Some(&Value::Int(0x8123_4567)));
+        assert_eq!(entry.get("Volume"), Some(&Value::Float(0.75)));
+        assert_eq!(entry.get("LastPlayTime"), Some(&Value::Long(1_700_000_000)));
+        assert_eq!(entry.get("tags").and_then(|tags| tags.get("0")), Some(&Value::String(String::from("favorite"))));
+
+        assert_eq!(serialize(&values), fixture());
+    }
+
+    #[test]
+    fn parse_broken_files() {
+        let fixture = fixture();
+
+        assert!(parse(&fixture[..fixture.len() - 1]).is_err());
+        assert!(parse(&fixture[..20]).is_err());
+        assert!(parse(&[0x05, b'a', 0, TYPE_END]).is_err());
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn shortcut_app_id() {
+        assert_eq!(shortcut("The Honkers Railway").app_id(), 0xBCE6D81B);
+        assert_eq!(shortcut("The Honkers Railway (work)").app_id(), 0x848A0F1D);
+    }
+
+    #[test]
+    fn add_shortcut_keeps_existing_entries() {
+        let folder = std::env::temp_dir().join(format!("steam-shortcuts-test-{}", std::process::id()));
+        let file = folder.join("config/shortcuts.vdf");
+
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, fixture()).unwrap();
+
+        add_shortcut(&file, &shortcut("The Honkers Railway")).unwrap();
+
+        let data = std::fs::read(&file).unwrap();
+        let backup = std::fs::read(file.with_extension("vdf.bak")).unwrap();
+
+        // Adding the same shortcut again replaces it
+        add_shortcut(&file, &shortcut("The Honkers Railway")).unwrap();
+
+        let data_again = std::fs::read(&file).unwrap();
+
+        std::fs::remove_dir_all(&folder).unwrap();
+
+        let fixture = fixture();
+
+        assert_eq!(backup, fixture);
+        assert_eq!(data_again, data);
+
+        // New entry is appended after the existing one which is kept byte for byte
+        let existing = &fixture[..fixture.len() - 2];
+
+        assert_eq!(&data[..existing.len()], existing);
+        assert_eq!(&data[data.len() - 2..], [TYPE_END, TYPE_END]);
+
+        let Some((_, Value::Map(shortcuts))) = parse(&data).unwrap().into_iter().next() else {
+            panic!("Shortcuts are not found");
+        };
+
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[1].0, "1");
+        assert_eq!(shortcuts[1].1.get("appid"), Some(&Value::Int(0xBCE6D81B)));
+        assert_eq!(shortcuts[1].1.get("AppName"), Some(&Value::String(String::from("The Honkers Railway"))));
+        assert_eq!(shortcuts[1].1.get("LaunchOptions"), Some(&Value::String(String::from("--run-game"))));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Mutex;
+
+use gtk::gio;
+use gtk::prelude::*;
+
+use crate::APP_ID;
+
+/// Command forwarded from the launcher started second time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Show the launcher window
+    Show,
+
+    /// Launch the game if it's possible. `--run-game` argument
+    RunGame,
+
+    /// Launch the game even if predownload is available or patch is not. `--just-run-game` argument
+    JustRunGame
+}
+
+impl Command {
+    pub fn from_args<T: AsRef<std::ffi::OsStr>>(args: &[T]) -> Self {
+        let has_arg = |name: &str| args.iter().any(|arg| arg.as_ref() == name);
+
+        if has_arg("--just-run-game") {
+            Self::JustRunGame
+        }
+
+        else if has_arg("--run-game") {
+            Self::RunGame
+        }
+
+        else {
+            Self::Show
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Handler of the forwarded commands. Set by the main window
+    static ref COMMAND_HANDLER: Mutex<Option<Box<dyn Fn(Command) + Send>>> = Mutex::new(None);
+}
+
+/// Register temporary application with the launcher id
+///
+/// It's remote if the launcher is already running. Application
+/// is unregistered and releases the bus name when it's dropped
+fn register() -> anyhow::Result<gio::Application> {
+    let app = gio::Application::new(Some(APP_ID), gio::ApplicationFlags::HANDLES_COMMAND_LINE);
+
+    app.register(gio::Cancellable::NONE)?;
+
+    Ok(app)
+}
+
+/// Check if the launcher is already running
+pub fn is_running() -> anyhow::Result<bool> {
+    Ok(register()?.is_remote())
+}
+
+/// Forward the command line to the already running launcher
+///
+/// Returns `false` if there's no running launcher and this one should start.
+/// Called before logger initialization so errors are printed to stderr
+pub fn forward() -> anyhow::Result<bool> {
+    let app = register()?;
+
+    if !app.is_remote() {
+        return Ok(false);
+    }
+
+    // Primary instance gets arguments in its `command-line` handler
+    let status = app.run_with_args(&std::env::args().collect::<Vec<_>>());
+
+    if status != 0 {
+        anyhow::bail!("Running launcher returned status {status}");
+    }
+
+    Ok(true)
+}
+
+/// Handle command lines forwarded to the application
+///
+/// Should be called before the application is started
+pub fn serve(app: &impl IsA<gio::Application>) {
+    app.connect_command_line(|app, command_line| {
+        let command = Command::from_args(&command_line.arguments());
+
+        tracing::info!("Got forwarded command: {command:?}");
+
+        match COMMAND_HANDLER.lock().unwrap().as_ref() {
+            Some(handler) => handler(command),
+
+            // First run or config recovery window can only be shown
+            None => {
+                if let Some(window) = app.dynamic_cast_ref::<gtk::Application>().and_then(|app| app.active_window()) {
+                    window.present();
+                }
+            }
+        }
+
+        0
+    });
+}
+
+/// Set handler of the forwarded commands
+pub fn set_command_handler(handler: impl Fn(Command) + Send + 'static) {
+    *COMMAND_HANDLER.lock().unwrap() = Some(Box::new(handler));
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Name of the generated service and timer units
+pub const UNIT_NAME: &str = "honkers-railway-launcher-updates";
+
+/// Options of the generated units
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitsOptions {
+    /// `OnCalendar` value of the timer
+    pub schedule: String,
+
+    /// Wait for the network before checking updates
+    pub wait_network: bool,
+
+    /// Run the service when the computer is on battery
+    pub allow_battery: bool,
+
+    /// Download updates on metered connections
+    pub allow_metered: bool
+}
+
+impl Default for UnitsOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            schedule: String::from("*-*-* 03:00:00"),
+            wait_network: true,
+            allow_battery: false,
+            allow_metered: false
+        }
+    }
+}
+
+impl UnitsOptions {
+    /// Parse options from the `systemd-units` command arguments
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--schedule" => match args.next() {
+                    Some(schedule) => options.schedule = schedule,
+                    None => anyhow::bail!("--schedule requires a value")
+                }
+
+                "--no-network-wait" => options.wait_network = false,
+                "--allow-battery"   => options.allow_battery = true,
+                "--allow-metered"   => options.allow_metered = true,
+
+                _ => anyhow::bail!("Unknown argument: {arg}")
+            }
+        }
+
+        if options.schedule.trim().is_empty() || options.schedule.contains('\n') {
+            anyhow::bail!("Wrong schedule: {:?}", options.schedule);
+        }
+
+        Ok(options)
+    }
+}
+
+/// Folder with the user units. Standard is `$HOME/.config/systemd/user`
+pub fn units_folder() -> anyhow::Result<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(config) if !config.is_empty() => PathBuf::from(config),
+
+        _ => match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".config"),
+            None => anyhow::bail!("Failed to find config folder")
+        }
+    };
+
+    Ok(config.join("systemd/user"))
+}
+
+/// Generate the service unit calling headless `check-updates` command
+pub fn service_unit(options: &UnitsOptions, executable: &Path) -> String {
+    // Specifiers start with `%` and quoted strings support C-style escapes
+    let executable = executable.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+
+    let mut command = format!("\"{executable}\" check-updates --predownload --install");
+
+    if options.wait_network {
+        command += " --wait-network";
+    }
+
+    if !options.allow_metered {
+        command += " --skip-metered";
+    }
+
+    let mut unit = String::from("[Unit]\n");
+
+    unit += "Description=The Honkers Railway Launcher background updates\n";
+
+    // User manager doesn't manage network-online.target, so it's only
+    // a hint. The command itself waits for the network
+    if options.wait_network {
+        unit += "Wants=network-online.target\n";
+        unit += "After=network-online.target\n";
+    }
+
+    if !options.allow_battery {
+        unit += "ConditionACPower=true\n";
+    }
+
+    unit += "\n[Service]\n";
+    unit += "Type=oneshot\n";
+    unit += &format!("ExecStart={command}\n");
+    unit += "Nice=10\n";
+    unit += "IOSchedulingClass=idle\n";
+
+    unit
+}
+
+/// Generate the timer unit starting the service by schedule
+pub fn timer_unit(options: &UnitsOptions) -> String {
+    format!("[Unit]\n\
+        Description=The Honkers Railway Launcher background updates\n\
+        \n\
+        [Timer]\n\
+        OnCalendar={}\n\
+        Persistent=true\n\
+        RandomizedDelaySec=15min\n\
+        \n\
+        [Install]\n\
+        WantedBy=timers.target\n",
+        options.schedule.trim()
+    )
+}
+
+fn systemctl(args: &[&str]) -> anyhow::Result<()> {
+    let status = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .status()?;
+
+    if !status.success() {
+        anyhow::bail!("systemctl --user {} failed: {status}", args.join(" "));
+    }
+
+    Ok(())
+}
+
+/// Write units to the user units folder and enable the timer
+///
+/// Returns paths to the written units
+pub fn install(options: &UnitsOptions) -> anyhow::Result<(PathBuf, PathBuf)> {
+    // Units from the sandbox would point to the binary inside of it
+    if std::env::var_os("FLATPAK_ID").is_some() {
+        anyhow::bail!("Systemd units can't be installed from flatpak");
+    }
+
+    let folder = units_folder()?;
+    let executable = std::env::current_exe()?;
+
+    let service = folder.join(format!("{UNIT_NAME}.service"));
+    let timer = folder.join(format!("{UNIT_NAME}.timer"));
+
+    std::fs::create_dir_all(&folder)?;
+
+    std::fs::write(&service, service_unit(options, &executable))?;
+    std::fs::write(&timer, timer_unit(options))?;
+
+    systemctl(&["daemon-reload"])?;
+    systemctl(&["enable", "--now", &format!("{UNIT_NAME}.timer")])?;
+
+    Ok((service, timer))
+}
+
+/// Disable the timer and remove units
+pub fn remove() -> anyhow::Result<()> {
+    let folder = units_folder()?;
+
+    let service = folder.join(format!("{UNIT_NAME}.service"));
+    let timer = folder.join(format!("{UNIT_NAME}.timer"));
+
+    if timer.exists() {
+        if let Err(err) = systemctl(&["disable", "--now", &format!("{UNIT_NAME}.timer")]) {
+            eprintln!("Failed to disable timer: {err}");
+        }
+    }
+
+    for path in [service, timer] {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    systemctl(&["daemon-reload"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_unit_network() {
+        let unit = service_unit(&UnitsOptions::default(), Path::new("/usr/bin/launcher"));
+
+        assert!(unit.contains("Wants=network-online.target\nAfter=network-online.target\n"));
+        assert!(unit.contains("ExecStart=\"/usr/bin/launcher\" check-updates --predownload --install --wait-network --skip-metered\n"));
+
+        let unit = service_unit(&UnitsOptions { wait_network: false, ..UnitsOptions::default() }, Path::new("/usr/bin/launcher"));
+
+        assert!(!unit.contains("network-online.target"));
+    }
+
+    #[test]
+    fn service_unit_escaping() {
+        let unit = service_unit(&UnitsOptions::default(), Path::new("/home/user/100% \"launcher\\\"/launcher"));
+
+        assert!(unit.contains("ExecStart=\"/home/user/100%% \\\"launcher\\\\\\\"/launcher\" check-updates"));
+    }
+}
@@ -0,0 +1,340 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use relm4::prelude::*;
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib::clone;
+
+use anime_launcher_sdk::star_rail::consts::config_file;
+
+use crate::config_validation::{self, ConfigIssue};
+use crate::config_backups;
+use crate::i18n::*;
+
+lazy_static::lazy_static! {
+    /// Launcher executable which should be started after the recovery window is closed
+    static ref RESTART_EXE: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
+/// Get launcher executable to restart if config file was fixed
+///
+/// Should be called after the application is stopped
+pub fn take_restart() -> Option<PathBuf> {
+    RESTART_EXE.lock().unwrap().take()
+}
+
+pub struct ConfigRecoveryApp {
+    toast_overlay: adw::ToastOverlay,
+    issues_group: adw::PreferencesGroup,
+    issues_rows: Vec<adw::ActionRow>,
+
+    has_backup: bool
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfigRecoveryAppMsg {
+    /// Reset given top level config section to its default value
+    ResetSection(String),
+
+    RestoreBackup,
+    OpenConfigFile,
+
+    /// Validate config file again and restart the launcher if it's fine
+    Revalidate,
+
+    Exit,
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for ConfigRecoveryApp {
+    type Init = Vec<ConfigIssue>;
+    type Input = ConfigRecoveryAppMsg;
+    type Output = ();
+
+    view! {
+        window = adw::ApplicationWindow {
+            set_default_size: (780, 560),
+            set_title: Some(&tr("config-recovery")),
+
+            #[local_ref]
+            toast_overlay -> adw::ToastOverlay {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    adw::HeaderBar {
+                        add_css_class: "flat"
+                    },
+
+                    adw::PreferencesPage {
+                        add = &adw::PreferencesGroup {
+                            gtk::Label {
+                                set_label: &tr("config-recovery-title"),
+                                add_css_class: "title-1"
+                            },
+
+                            gtk::Label {
+                                set_label: &tr("config-recovery-message"),
+
+                                set_justify: gtk::Justification::Center,
+                                set_wrap: true,
+                                set_margin_top: 32
+                            }
+                        },
+
+                        #[local_ref]
+                        add = issues_group -> adw::PreferencesGroup {
+                            set_title: &tr("config-recovery-issues")
+                        },
+
+                        add = &adw::PreferencesGroup {
+                            set_valign: gtk::Align::Center,
+                            set_vexpand: true,
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_halign: gtk::Align::Center,
+                                set_spacing: 8,
+
+                                gtk::Button {
+                                    set_label: &tr("restore-backup"),
+                                    set_tooltip_text: Some(&tr("restore-backup-description")),
+                                    set_css_classes: &["suggested-action", "pill"],
+
+                                    set_sensitive: model.has_backup,
+
+                                    connect_clicked => ConfigRecoveryAppMsg::RestoreBackup
+                                },
+
+                                gtk::Button {
+                                    set_label: &tr("config-file"),
+                                    add_css_class: "pill",
+
+                                    connect_clicked => ConfigRecoveryAppMsg::OpenConfigFile
+                                },
+
+                                gtk::Button {
+                                    set_label: &tr("check"),
+                                    add_css_class: "pill",
+
+                                    connect_clicked => ConfigRecoveryAppMsg::Revalidate
+                                },
+
+                                gtk::Button {
+                                    set_label: &tr("exit"),
+                                    add_css_class: "pill",
+
+                                    connect_clicked => ConfigRecoveryAppMsg::Exit
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn init(
+        issues: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        tracing::info!("Initializing config recovery window");
+
+        let mut model = Self {
+            toast_overlay: adw::ToastOverlay::new(),
+            issues_group: adw::PreferencesGroup::new(),
+            issues_rows: Vec::new(),
+
+            has_backup: config_validation::latest_valid_backup().is_some()
+        };
+
+        model.set_issues(&issues, &sender);
+
+        let toast_overlay = &model.toast_overlay;
+        let issues_group = &model.issues_group;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        tracing::debug!("Called config recovery window event: {:?}", msg);
+
+        match msg {
+            ConfigRecoveryAppMsg::ResetSection(section) => {
+                match config_validation::reset_file_section(&section) {
+                    Ok(()) => sender.input(ConfigRecoveryAppMsg::Revalidate),
+
+                    Err(err) => {
+                        tracing::error!("Failed to reset config section '{section}': {err}");
+
+                        sender.input(ConfigRecoveryAppMsg::Toast {
+                            title: tr("config-section-reset-failed"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
+            ConfigRecoveryAppMsg::RestoreBackup => {
+                let result = match config_validation::latest_valid_backup() {
+                    Some(backup) => config_backups::restore(&backup),
+                    None => Err(anyhow::anyhow!("No valid config backup found"))
+                };
+
+                match result {
+                    Ok(()) => sender.input(ConfigRecoveryAppMsg::Revalidate),
+
+                    Err(err) => {
+                        tracing::error!("Failed to restore config backup: {err}");
+
+                        sender.input(ConfigRecoveryAppMsg::Toast {
+                            title: tr("config-backup-restore-failed"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
+            ConfigRecoveryAppMsg::OpenConfigFile => {
+                if let Ok(file) = config_file() {
+                    if let Err(err) = open::that(file) {
+                        sender.input(ConfigRecoveryAppMsg::Toast {
+                            title: tr("config-file-opening-error"),
+                            description: Some(err.to_string())
+                        });
+
+                        tracing::error!("Failed to open config file: {err}");
+                    }
+                }
+            }
+
+            ConfigRecoveryAppMsg::Revalidate => {
+                let issues = config_validation::validate();
+
+                if issues.is_empty() {
+                    tracing::info!("Config file was fixed. Restarting the launcher");
+
+                    match std::env::current_exe() {
+                        Ok(exe) => {
+                            *RESTART_EXE.lock().unwrap() = Some(exe);
+
+                            relm4::main_application().quit();
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to get launcher executable: {err}");
+
+                            sender.input(ConfigRecoveryAppMsg::Toast {
+                                title: tr("launcher-restart-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
+                    }
+                }
+
+                else {
+                    self.set_issues(&issues, &sender);
+
+                    sender.input(ConfigRecoveryAppMsg::Toast {
+                        title: tr("config-still-invalid"),
+                        description: Some(issues.iter()
+                            .map(|issue| format!("- {issue}"))
+                            .collect::<Vec<_>>()
+                            .join("\n"))
+                    });
+                }
+            }
+
+            ConfigRecoveryAppMsg::Exit => relm4::main_application().quit(),
+
+            ConfigRecoveryAppMsg::Toast { title, description } => {
+                let toast = adw::Toast::new(&title);
+
+                toast.set_timeout(4);
+
+                if let Some(description) = description {
+                    toast.set_button_label(Some(&tr("details")));
+
+                    let dialog = adw::MessageDialog::new(
+                        relm4::main_application().active_window().as_ref(),
+                        Some(&title),
+                        Some(&description)
+                    );
+
+                    dialog.add_response("close", &tr("close"));
+
+                    toast.connect_button_clicked(move |_| {
+                        dialog.present();
+                    });
+                }
+
+                self.toast_overlay.add_toast(toast);
+            }
+        }
+    }
+}
+
+impl ConfigRecoveryApp {
+    fn set_issues(&mut self, issues: &[ConfigIssue], sender: &ComponentSender<Self>) {
+        for row in self.issues_rows.drain(..) {
+            self.issues_group.remove(&row);
+        }
+
+        for issue in issues {
+            let row = adw::ActionRow::new();
+
+            match issue {
+                ConfigIssue::Unreadable(err) => {
+                    row.set_title(&tr("config-file-unreadable"));
+                    row.set_subtitle(err);
+                }
+
+                ConfigIssue::Syntax { line, column, message } => {
+                    row.set_title(&tr_args("config-syntax-error", [
+                        ("line", line.to_string().into()),
+                        ("column", column.to_string().into())
+                    ]));
+
+                    row.set_subtitle(message);
+                }
+
+                ConfigIssue::Field { path, message } => {
+                    row.set_title(path);
+                    row.set_subtitle(message);
+                }
+            }
+
+            if let Some(section) = issue.section() {
+                let button = gtk::Button::new();
+
+                button.set_label(&tr_args("reset-config-section", [
+                    ("section", section.to_string().into())
+                ]));
+
+                button.add_css_class("flat");
+                button.set_valign(gtk::Align::Center);
+
+                let section = section.to_string();
+
+                button.connect_clicked(clone!(@strong sender => move |_| {
+                    sender.input(ConfigRecoveryAppMsg::ResetSection(section.clone()));
+                }));
+
+                row.add_suffix(&button);
+            }
+
+            self.issues_group.add(&row);
+            self.issues_rows.push(row);
+        }
+    }
+}
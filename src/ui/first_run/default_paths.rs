@@ -422,6 +422,9 @@ impl DefaultPathsApp {
         config.patch.path       = self.patch.clone();
         config.launcher.temp    = Some(self.temp.clone());
 
+        // Paths are changed so keep the previous ones
+        crate::config_backups::create()?;
+
         Config::update_raw(config)
     }
 }
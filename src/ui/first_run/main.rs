@@ -16,8 +16,6 @@ use super::default_paths::*;
 use super::download_components::*;
 use super::finish::*;
 
-pub static mut MAIN_WINDOW: Option<adw::ApplicationWindow> = None;
-
 // TODO: add special page for launcher style selection
 
 pub struct FirstRunApp {
@@ -28,6 +26,7 @@ pub struct FirstRunApp {
     download_components: AsyncController<DownloadComponentsApp>,
     finish: AsyncController<FinishApp>,
 
+    window: adw::ApplicationWindow,
     toast_overlay: adw::ToastOverlay,
     carousel: adw::Carousel,
 
@@ -133,7 +132,7 @@ impl SimpleComponent for FirstRunApp {
                 .forward(sender.input_sender(), std::convert::identity),
 
             tos_warning: TosWarningApp::builder()
-                .launch(())
+                .launch(root.clone())
                 .forward(sender.input_sender(), std::convert::identity),
 
             dependencies: DependenciesApp::builder()
@@ -152,6 +151,7 @@ impl SimpleComponent for FirstRunApp {
                 .launch(())
                 .forward(sender.input_sender(), std::convert::identity),
 
+            window: root.clone(),
             toast_overlay,
             carousel,
 
@@ -164,13 +164,7 @@ impl SimpleComponent for FirstRunApp {
 
         let widgets = view_output!();
 
-        unsafe {
-            MAIN_WINDOW = Some(widgets.window.clone());
-
-            crate::READY = true;
-        }
-
-        tracing::info!("First run window initialized. App is ready");
+        tracing::info!("First run window initialized");
 
         ComponentParts { model, widgets } // will return soon
     }
@@ -262,7 +256,7 @@ impl SimpleComponent for FirstRunApp {
                 self.carousel.scroll_to(self.finish.widget(), true);
             }
 
-            FirstRunAppMsg::Toast { title, description } => unsafe {
+            FirstRunAppMsg::Toast { title, description } => {
                 let toast = adw::Toast::new(&title);
 
                 toast.set_timeout(4);
@@ -270,7 +264,7 @@ impl SimpleComponent for FirstRunApp {
                 if let Some(description) = description {
                     toast.set_button_label(Some(&tr("details")));
 
-                    let dialog = adw::MessageDialog::new(MAIN_WINDOW.as_ref(), Some(&title), Some(&description));
+                    let dialog = adw::MessageDialog::new(Some(&self.window), Some(&title), Some(&description));
 
                     dialog.add_response("close", &tr("close"));
                     dialog.add_response("save", &tr("save"));
@@ -8,9 +8,9 @@ use anime_launcher_sdk::is_available;
 use crate::i18n::*;
 use super::main::FirstRunAppMsg;
 
-use super::main::MAIN_WINDOW;
-
-pub struct TosWarningApp;
+pub struct TosWarningApp {
+    parent: adw::ApplicationWindow
+}
 
 #[derive(Debug, Clone)]
 pub enum TosWarningAppMsg {
@@ -20,7 +20,7 @@ pub enum TosWarningAppMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for TosWarningApp {
-    type Init = ();
+    type Init = adw::ApplicationWindow;
     type Input = TosWarningAppMsg;
     type Output = FirstRunAppMsg;
 
@@ -74,11 +74,11 @@ impl SimpleAsyncComponent for TosWarningApp {
     }
 
     async fn init(
-        _init: Self::Init,
+        parent: Self::Init,
         root: Self::Root,
         _sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
-        let model = Self;
+        let model = Self { parent };
         let widgets = view_output!();
 
         AsyncComponentParts { model, widgets }
@@ -89,7 +89,7 @@ impl SimpleAsyncComponent for TosWarningApp {
             #[allow(unused_must_use)]
             TosWarningAppMsg::Continue => {
                 let dialog = adw::MessageDialog::new(
-                    unsafe { MAIN_WINDOW.as_ref() },
+                    Some(&self.parent),
                     Some(&tr("tos-dialog-title")),
                     Some(&tr("tos-dialog-message"))
                 );
@@ -28,11 +28,23 @@ pub fn apply_patch(sender: ComponentSender<App>, patch: MainPatch) {
                         description: Some(err.to_string())
                     });
 
+                    sender.input(AppMsg::Notify {
+                        title: tr("game-patching-error"),
+                        body: Some(err.to_string())
+                    });
+
                     // Don't try to apply the patch after state updating
                     // because we just failed to do it
                     apply_patch_if_needed = false;
                 }
 
+                else {
+                    sender.input(AppMsg::Notify {
+                        title: tr("notification-patch-applied"),
+                        body: None
+                    });
+                }
+
                 sender.input(AppMsg::DisableButtons(false));
                 sender.input(AppMsg::UpdateLauncherState {
                     perform_on_download_needed: false,
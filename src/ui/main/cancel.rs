@@ -0,0 +1,87 @@
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the `CancelDownload` D-Bus method and checked by the downloading threads
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Payload of the unwinding started by the cancelled download
+struct Cancelled;
+
+/// Cancel the running download
+///
+/// It's stopped on the next progress update
+#[inline]
+pub fn cancel() {
+    CANCELLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop the download if it was cancelled
+///
+/// Should be called from the progress updaters. The SDK can't interrupt
+/// downloading, so the thread is unwound up to the `cancellable` call
+pub fn check() {
+    if CANCELLED.load(Ordering::Relaxed) {
+        std::panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+/// Run downloading function which can be cancelled
+///
+/// Returns `None` if it was cancelled. Other panics are passed through
+pub fn cancellable<T>(download: impl FnOnce() -> T) -> Option<T> {
+    CANCELLED.store(false, Ordering::Relaxed);
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(download));
+
+    CANCELLED.store(false, Ordering::Relaxed);
+
+    match result {
+        Ok(result) => Some(result),
+        Err(payload) if payload.is::<Cancelled>() => None,
+        Err(payload) => std::panic::resume_unwind(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Flag is global so the cases are checked within a single test
+    #[test]
+    fn cancel_download() {
+        assert_eq!(cancellable(|| 1), Some(1));
+
+        // Updates before cancelling are processed
+        let mut updates = 0;
+
+        let result = cancellable(|| {
+            for _ in 0..10 {
+                check();
+
+                updates += 1;
+
+                if updates == 3 {
+                    cancel();
+                }
+            }
+
+            updates
+        });
+
+        assert_eq!(result, None);
+        assert_eq!(updates, 3);
+
+        // Cancelled flag is not kept for the next download
+        assert_eq!(cancellable(|| { check(); 2 }), Some(2));
+
+        // Flag set before the download started is ignored
+        cancel();
+
+        assert_eq!(cancellable(|| { check(); 3 }), Some(3));
+
+        // Other panics are not caught
+        let result = std::panic::catch_unwind(|| cancellable(|| std::panic::resume_unwind(Box::new("other"))));
+
+        assert_eq!(result.unwrap_err().downcast_ref::<&str>(), Some(&"other"));
+    }
+}
@@ -0,0 +1,443 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use relm4::prelude::*;
+
+use gtk::prelude::*;
+use gtk::gio;
+use gtk::glib::ToVariant;
+
+use crate::*;
+use super::{App, AppMsg};
+
+/// D-Bus interface of the launcher. It's registered on the application's object path
+pub const INTERFACE: &str = "moe.launcher.TheHonkersRailwayLauncher";
+
+const INTERFACE_XML: &str = r#"
+<node>
+    <interface name="moe.launcher.TheHonkersRailwayLauncher">
+        <method name="GetState">
+            <arg name="state" type="s" direction="out"/>
+            <arg name="downloading" type="b" direction="out"/>
+        </method>
+
+        <method name="Show"/>
+        <method name="Launch"/>
+        <method name="StartUpdate"/>
+        <method name="CancelDownload"/>
+
+        <signal name="StateChanged">
+            <arg name="state" type="s"/>
+            <arg name="downloading" type="b"/>
+        </signal>
+
+        <signal name="Progress">
+            <arg name="current" type="t"/>
+            <arg name="total" type="t"/>
+        </signal>
+    </interface>
+</node>
+"#;
+
+lazy_static::lazy_static! {
+    /// Connection and object path used to emit progress signals from the downloading threads,
+    /// and the last emitted progress in permilles
+    static ref PROGRESS_EMITTER: Mutex<Option<(gio::DBusConnection, String, u64)>> = Mutex::new(None);
+}
+
+/// Action performed by the `StartUpdate` method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdateAction {
+    PerformAction,
+    Predownload
+}
+
+/// Launcher state shared with the methods handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ServiceState {
+    state: &'static str,
+    downloading: bool,
+    can_launch: bool,
+    update: Option<UpdateAction>
+}
+
+impl Default for ServiceState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            state: "Loading",
+            downloading: false,
+            can_launch: false,
+            update: None
+        }
+    }
+}
+
+pub struct DBusService {
+    connection: gio::DBusConnection,
+    path: String,
+    registration: Option<gio::RegistrationId>,
+    state: Rc<RefCell<ServiceState>>
+}
+
+impl DBusService {
+    /// Register launcher interface on the application's D-Bus connection
+    pub fn register(app: &gio::Application, sender: ComponentSender<App>) -> anyhow::Result<Self> {
+        let (Some(connection), Some(path)) = (app.dbus_connection(), app.dbus_object_path()) else {
+            anyhow::bail!("Application is not registered on the session bus");
+        };
+
+        Self::register_object(connection, &path, move |msg| sender.input(msg))
+    }
+
+    /// Register launcher interface on the given connection and object path
+    ///
+    /// Messages requested by the method calls are passed to `send`
+    fn register_object(connection: gio::DBusConnection, path: &str, send: impl Fn(AppMsg) + 'static) -> anyhow::Result<Self> {
+        let node = gio::DBusNodeInfo::for_xml(INTERFACE_XML)?;
+
+        let Some(interface) = node.lookup_interface(INTERFACE) else {
+            anyhow::bail!("Failed to parse D-Bus interface");
+        };
+
+        let state = Rc::new(RefCell::new(ServiceState::default()));
+        let handler_state = state.clone();
+
+        let registration = connection.register_object(
+            path,
+            &interface,
+            move |_, _, _, _, method, _, invocation| {
+                let state = handler_state.borrow().clone();
+
+                tracing::debug!("Called D-Bus method {method}");
+
+                match method {
+                    "GetState" => invocation.return_value(Some(&(state.state, state.downloading).to_variant())),
+
+                    "Show" => {
+                        send(AppMsg::ShowWindow);
+
+                        invocation.return_value(None);
+                    }
+
+                    "Launch" => {
+                        if state.can_launch && !state.downloading {
+                            send(AppMsg::PerformAction);
+
+                            invocation.return_value(None);
+                        }
+
+                        else {
+                            invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", &format!("Game can't be launched in {} state", state.state));
+                        }
+                    }
+
+                    "StartUpdate" => match state.update {
+                        Some(_) if state.downloading => {
+                            invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", "Launcher is already downloading something");
+                        }
+
+                        Some(UpdateAction::PerformAction) => {
+                            send(AppMsg::PerformAction);
+
+                            invocation.return_value(None);
+                        }
+
+                        Some(UpdateAction::Predownload) => {
+                            send(AppMsg::PredownloadUpdate);
+
+                            invocation.return_value(None);
+                        }
+
+                        None => {
+                            invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", &format!("Nothing to update in {} state", state.state));
+                        }
+                    }
+
+                    "CancelDownload" => {
+                        if state.downloading {
+                            send(AppMsg::CancelDownload);
+
+                            invocation.return_value(None);
+                        }
+
+                        else {
+                            invocation.return_dbus_error("org.freedesktop.DBus.Error.Failed", "Launcher is not downloading anything");
+                        }
+                    }
+
+                    _ => invocation.return_dbus_error("org.freedesktop.DBus.Error.UnknownMethod", &format!("Unknown method {method}"))
+                }
+            },
+            |_, _, _, _, _| ().to_variant(),
+            |_, _, _, _, _, _| false
+        )?;
+
+        *PROGRESS_EMITTER.lock().unwrap() = Some((connection.clone(), path.to_string(), u64::MAX));
+
+        tracing::info!("Registered D-Bus interface {INTERFACE} on {path}");
+
+        Ok(Self {
+            connection,
+            path: path.to_string(),
+            registration: Some(registration),
+            state
+        })
+    }
+
+    /// Update state returned by the methods and emit `StateChanged` signal if it was changed
+    pub fn set_state(&self, state: Option<&LauncherState>, downloading: bool) {
+        let new_state = ServiceState {
+            state: state_name(state),
+            downloading,
+
+            can_launch: matches!(state, Some(
+                LauncherState::Launch |
+                LauncherState::PredownloadAvailable { .. } |
+                LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. })
+            )),
+
+            update: match state {
+                Some(LauncherState::PredownloadAvailable { .. }) => Some(UpdateAction::Predownload),
+
+                Some(LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. })) => None,
+
+                Some(LauncherState::MainPatchAvailable(_) |
+                     LauncherState::WineNotInstalled |
+                     LauncherState::PrefixNotExists |
+                     LauncherState::GameUpdateAvailable(_) |
+                     LauncherState::GameNotInstalled(_)) => Some(UpdateAction::PerformAction),
+
+                _ => None
+            }
+        };
+
+        if self.state.replace(new_state.clone()) != new_state {
+            let result = self.connection.emit_signal(
+                None,
+                &self.path,
+                INTERFACE,
+                "StateChanged",
+                Some(&(new_state.state, new_state.downloading).to_variant())
+            );
+
+            if let Err(err) = result {
+                tracing::error!("Failed to emit D-Bus signal: {err}");
+            }
+        }
+    }
+}
+
+impl Drop for DBusService {
+    fn drop(&mut self) {
+        *PROGRESS_EMITTER.lock().unwrap() = None;
+
+        if let Some(registration) = self.registration.take() {
+            if let Err(err) = self.connection.unregister_object(registration) {
+                tracing::error!("Failed to unregister D-Bus interface: {err}");
+            }
+        }
+    }
+}
+
+/// Name of the launcher state used by the D-Bus interface
+pub fn state_name(state: Option<&LauncherState>) -> &'static str {
+    match state {
+        None => "Loading",
+
+        Some(LauncherState::Launch)                   => "Launch",
+        Some(LauncherState::PredownloadAvailable(_))  => "PredownloadAvailable",
+        Some(LauncherState::MainPatchAvailable(_))    => "MainPatchAvailable",
+        Some(LauncherState::WineNotInstalled)         => "WineNotInstalled",
+        Some(LauncherState::PrefixNotExists)          => "PrefixNotExists",
+        Some(LauncherState::GameUpdateAvailable(_))   => "GameUpdateAvailable",
+        Some(LauncherState::GameOutdated(_))          => "GameOutdated",
+        Some(LauncherState::GameNotInstalled(_))      => "GameNotInstalled"
+    }
+}
+
+/// Emit `Progress` signal. Can be called from any thread
+///
+/// Signals are emitted only when the progress changes by at least 0.1%
+pub fn emit_progress(current: u64, total: u64) {
+    let Ok(mut emitter) = PROGRESS_EMITTER.lock() else {
+        return;
+    };
+
+    let Some((connection, path, last)) = emitter.as_mut() else {
+        return;
+    };
+
+    let progress = if total == 0 { 0 } else { current.saturating_mul(1000) / total };
+
+    if progress == *last {
+        return;
+    }
+
+    *last = progress;
+
+    if let Err(err) = connection.emit_signal(None, path, INTERFACE, "Progress", Some(&(current, total).to_variant())) {
+        tracing::error!("Failed to emit D-Bus signal: {err}");
+    }
+}
+
+/// Emit `Progress` signal from the installation state
+pub fn emit_state_progress(state: &DiffUpdate) {
+    match state {
+        DiffUpdate::InstallerUpdate(InstallerUpdate::DownloadingProgress(curr, total)) |
+        DiffUpdate::InstallerUpdate(InstallerUpdate::UnpackingProgress(curr, total)) |
+        DiffUpdate::ApplyingHdiffProgress(curr, total) |
+        DiffUpdate::RemovingOutdatedProgress(curr, total) => emit_progress(*curr, *total),
+
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use gtk::glib;
+
+    use super::*;
+
+    const PATH: &str = "/moe/launcher/TheHonkersRailwayLauncher";
+
+    fn connect(address: &str) -> gio::DBusConnection {
+        gio::DBusConnection::for_address_sync(
+            address,
+            gio::DBusConnectionFlags::AUTHENTICATION_CLIENT | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+            None::<&gio::Cancellable>
+        ).expect("Failed to connect to the test bus")
+    }
+
+    fn call(context: &glib::MainContext, client: &gio::DBusConnection, service: &str, method: &str) -> Result<glib::Variant, glib::Error> {
+        context.block_on(client.call_future(
+            Some(service),
+            PATH,
+            INTERFACE,
+            method,
+            None,
+            None,
+            gio::DBusCallFlags::NONE,
+            5000
+        ))
+    }
+
+    /// Run the main context until the condition is true
+    fn wait_for(context: &glib::MainContext, condition: impl Fn() -> bool) {
+        let start = Instant::now();
+
+        while !condition() {
+            assert!(start.elapsed() < Duration::from_secs(5), "Timed out waiting for D-Bus messages");
+
+            if !context.iteration(false) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+
+    // Private bus is shared by all the cases so they're checked within a single test
+    #[test]
+    fn private_bus() {
+        let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+
+        bus.up();
+
+        let address = bus.bus_address().expect("Test bus has no address");
+        let context = glib::MainContext::default();
+
+        let service_connection = connect(&address);
+        let client = connect(&address);
+
+        let service_name = service_connection.unique_name().expect("Connection has no unique name").to_string();
+
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let signals = Rc::new(RefCell::new(Vec::new()));
+
+        let service = {
+            let messages = messages.clone();
+
+            DBusService::register_object(service_connection.clone(), PATH, move |msg| messages.borrow_mut().push(msg))
+                .expect("Failed to register D-Bus interface")
+        };
+
+        let subscription = {
+            let signals = signals.clone();
+
+            client.signal_subscribe(Some(&service_name), Some(INTERFACE), Some("StateChanged"), Some(PATH), None, gio::DBusSignalFlags::NONE,
+                move |_, _, _, _, _, params| signals.borrow_mut().push(params.get::<(String, bool)>().unwrap()))
+        };
+
+        // GetState
+        let state = call(&context, &client, &service_name, "GetState").unwrap();
+
+        assert_eq!(state.get::<(String, bool)>(), Some((String::from("Loading"), false)));
+
+        // Show
+        call(&context, &client, &service_name, "Show").unwrap();
+
+        assert!(matches!(messages.borrow().as_slice(), [AppMsg::ShowWindow]));
+
+        // Launch is not allowed while loading
+        assert!(call(&context, &client, &service_name, "Launch").is_err());
+        assert_eq!(messages.borrow().len(), 1);
+
+        // Unknown methods are rejected
+        assert!(call(&context, &client, &service_name, "StopEverything").is_err());
+
+        // Nothing to cancel
+        assert!(call(&context, &client, &service_name, "CancelDownload").is_err());
+        assert_eq!(messages.borrow().len(), 1);
+
+        // StateChanged
+        service.set_state(Some(&LauncherState::Launch), false);
+
+        wait_for(&context, || signals.borrow().len() == 1);
+
+        assert_eq!(signals.borrow()[0], (String::from("Launch"), false));
+
+        // Same state doesn't emit the signal again
+        service.set_state(Some(&LauncherState::Launch), false);
+        service.set_state(Some(&LauncherState::Launch), true);
+
+        wait_for(&context, || signals.borrow().len() >= 2);
+
+        assert_eq!(signals.borrow().as_slice(), [
+            (String::from("Launch"), false),
+            (String::from("Launch"), true)
+        ]);
+
+        let state = call(&context, &client, &service_name, "GetState").unwrap();
+
+        assert_eq!(state.get::<(String, bool)>(), Some((String::from("Launch"), true)));
+
+        // Launch is not allowed while downloading
+        assert!(call(&context, &client, &service_name, "Launch").is_err());
+
+        // CancelDownload
+        call(&context, &client, &service_name, "CancelDownload").unwrap();
+
+        assert!(matches!(messages.borrow().as_slice(), [AppMsg::ShowWindow, AppMsg::CancelDownload]));
+
+        service.set_state(Some(&LauncherState::Launch), false);
+
+        call(&context, &client, &service_name, "Launch").unwrap();
+
+        assert!(matches!(messages.borrow().as_slice(), [AppMsg::ShowWindow, AppMsg::CancelDownload, AppMsg::PerformAction]));
+
+        client.signal_unsubscribe(subscription);
+
+        drop(service);
+
+        // Interface is unregistered with the service
+        assert!(call(&context, &client, &service_name, "GetState").is_err());
+
+        client.close_sync(None::<&gio::Cancellable>).unwrap();
+        service_connection.close_sync(None::<&gio::Cancellable>).unwrap();
+
+        bus.down();
+    }
+}
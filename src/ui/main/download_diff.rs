@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use relm4::{
     prelude::*,
     Sender
@@ -22,11 +25,21 @@ pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
             diff = diff.with_temp_folder(temp);
         }
 
-        let result = diff.install_to(game_path, clone!(@strong sender => move |state| {
+        // Installer reports its errors only to the updater
+        let failed = Arc::new(AtomicBool::new(false));
+
+        // Update can be installed by the `check-updates` command at the same time
+        let lock = update_check::InstallLock::acquire();
+
+        let result = super::cancel::cancellable(|| lock.and_then(|_lock| diff.install_to(game_path, clone!(@strong sender, @strong failed => move |state| {
+            super::cancel::check();
+
             match &state {
                 DiffUpdate::InstallerUpdate(InstallerUpdate::DownloadingError(err)) => {
                     tracing::error!("Downloading failed: {err}");
 
+                    failed.store(true, Ordering::Relaxed);
+
                     sender.input(AppMsg::Toast {
                         title: tr("downloading-failed"),
                         description: Some(err.to_string())
@@ -36,6 +49,8 @@ pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                 DiffUpdate::InstallerUpdate(InstallerUpdate::UnpackingError(err)) => {
                     tracing::error!("Unpacking failed: {err}");
 
+                    failed.store(true, Ordering::Relaxed);
+
                     sender.input(AppMsg::Toast {
                         title: tr("unpacking-failed"),
                         description: Some(err.clone())
@@ -45,13 +60,33 @@ pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                 _ => ()
             }
 
+            super::dbus::emit_state_progress(&state);
+
             #[allow(unused_must_use)] {
                 progress_bar_input.send(ProgressBarMsg::UpdateFromState(state));
             }
-        }));
+        })).map_err(anyhow::Error::from)));
 
         let mut perform_on_download_needed = true;
 
+        let Some(result) = result else {
+            tracing::info!("Downloading cancelled");
+
+            sender.input(AppMsg::Toast {
+                title: tr("downloading-cancelled"),
+                description: None
+            });
+
+            sender.input(AppMsg::SetDownloading(false));
+            sender.input(AppMsg::UpdateLauncherState {
+                perform_on_download_needed: false,
+                apply_patch_if_needed: false,
+                show_status_page: false
+            });
+
+            return;
+        };
+
         if let Err(err) = result {
             tracing::error!("Downloading failed: {err}");
 
@@ -60,11 +95,30 @@ pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                 description: Some(err.to_string())
             });
 
+            sender.input(AppMsg::Notify {
+                title: tr("downloading-failed"),
+                body: Some(err.to_string())
+            });
+
             // Don't try to download something after state updating
             // because we just failed to do it
             perform_on_download_needed = false;
         }
 
+        else if failed.load(Ordering::Relaxed) {
+            sender.input(AppMsg::Notify {
+                title: tr("downloading-failed"),
+                body: None
+            });
+        }
+
+        else {
+            sender.input(AppMsg::Notify {
+                title: tr("notification-download-finished"),
+                body: None
+            });
+        }
+
         sender.input(AppMsg::SetDownloading(false));
         sender.input(AppMsg::UpdateLauncherState {
             perform_on_download_needed,
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use relm4::{
     prelude::*,
     Sender
@@ -54,11 +57,18 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                         sender.input(AppMsg::SetDownloading(true));
 
                         std::thread::spawn(clone!(@strong sender => move || {
-                            installer.install(&config.game.wine.builds, clone!(@strong sender => move |state| {
+                            // Installer reports its errors only to the updater
+                            let failed = Arc::new(AtomicBool::new(false));
+
+                            let result = super::cancel::cancellable(|| installer.install(&config.game.wine.builds, clone!(@strong sender, @strong failed => move |state| {
+                                super::cancel::check();
+
                                 match &state {
                                     InstallerUpdate::DownloadingError(err) => {
                                         tracing::error!("Downloading failed: {err}");
 
+                                        failed.store(true, Ordering::Relaxed);
+
                                         sender.input(AppMsg::Toast {
                                             title: tr("downloading-failed"),
                                             description: Some(err.to_string())
@@ -68,6 +78,8 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                                     InstallerUpdate::UnpackingError(err) => {
                                         tracing::error!("Unpacking failed: {err}");
 
+                                        failed.store(true, Ordering::Relaxed);
+
                                         sender.input(AppMsg::Toast {
                                             title: tr("unpacking-failed"),
                                             description: Some(err.clone())
@@ -77,10 +89,41 @@ pub fn download_wine(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
                                     _ => ()
                                 }
 
+                                let state = DiffUpdate::InstallerUpdate(state);
+
+                                super::dbus::emit_state_progress(&state);
+
                                 #[allow(unused_must_use)] {
-                                    progress_bar_input.send(ProgressBarMsg::UpdateFromState(DiffUpdate::InstallerUpdate(state)));
+                                    progress_bar_input.send(ProgressBarMsg::UpdateFromState(state));
                                 }
-                            }));
+                            })));
+
+                            if result.is_none() {
+                                tracing::info!("Wine downloading cancelled");
+
+                                sender.input(AppMsg::Toast {
+                                    title: tr("downloading-cancelled"),
+                                    description: None
+                                });
+
+                                sender.input(AppMsg::SetDownloading(false));
+                                sender.input(AppMsg::UpdateLauncherState {
+                                    perform_on_download_needed: false,
+                                    apply_patch_if_needed: false,
+                                    show_status_page: true
+                                });
+
+                                return;
+                            }
+
+                            sender.input(AppMsg::Notify {
+                                title: if failed.load(Ordering::Relaxed) {
+                                    tr("wine-install-failed")
+                                } else {
+                                    tr("notification-wine-installed")
+                                },
+                                body: None
+                            });
 
                             config.game.wine.selected = Some(wine.name.clone());
 
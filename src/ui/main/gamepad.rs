@@ -0,0 +1,134 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use relm4::prelude::*;
+
+use gilrs::{Gilrs, Event, EventType, Button, Axis};
+
+use super::{App, AppMsg};
+
+/// Stick position after which it's counted as pressed
+const STICK_THRESHOLD: f32 = 0.6;
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadAction {
+    Up,
+    Down,
+    Left,
+    Right,
+
+    /// Activate focused widget
+    Activate,
+
+    /// Close the focused dialog
+    Back,
+
+    /// Perform the main window's action
+    Start
+}
+
+impl GamepadAction {
+    fn from_button(button: Button) -> Option<Self> {
+        match button {
+            Button::DPadUp    => Some(Self::Up),
+            Button::DPadDown  => Some(Self::Down),
+            Button::DPadLeft  => Some(Self::Left),
+            Button::DPadRight => Some(Self::Right),
+
+            Button::South => Some(Self::Activate),
+            Button::East  => Some(Self::Back),
+            Button::Start => Some(Self::Start),
+
+            _ => None
+        }
+    }
+
+    /// Get focus direction of the navigation actions
+    pub fn direction(&self) -> Option<gtk::DirectionType> {
+        match self {
+            Self::Up    => Some(gtk::DirectionType::Up),
+            Self::Down  => Some(gtk::DirectionType::Down),
+            Self::Left  => Some(gtk::DirectionType::Left),
+            Self::Right => Some(gtk::DirectionType::Right),
+
+            _ => None
+        }
+    }
+}
+
+/// Start reading gamepads input in a separate thread
+///
+/// Does nothing if it's already started
+pub fn spawn(sender: ComponentSender<App>) {
+    if STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let mut gilrs = match Gilrs::new() {
+            Ok(gilrs) => gilrs,
+
+            Err(err) => {
+                tracing::error!("Failed to initialize gamepads input: {err}");
+
+                STARTED.store(false, Ordering::Relaxed);
+
+                return;
+            }
+        };
+
+        tracing::info!("Gamepads input initialized");
+
+        // Sticks work as d-pad, so only their crossing of the threshold is sent
+        let mut stick = (0_i8, 0_i8);
+
+        loop {
+            while let Some(Event { event, .. }) = gilrs.next_event() {
+                let action = match event {
+                    EventType::ButtonPressed(button, _) => GamepadAction::from_button(button),
+
+                    EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                        stick_moved(&mut stick.0, value, GamepadAction::Right, GamepadAction::Left)
+                    }
+
+                    // Y axis goes up
+                    EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                        stick_moved(&mut stick.1, value, GamepadAction::Up, GamepadAction::Down)
+                    }
+
+                    _ => None
+                };
+
+                if let Some(action) = action {
+                    sender.input(AppMsg::Gamepad(action));
+                }
+            }
+
+            std::thread::sleep(Duration::from_millis(16));
+        }
+    });
+}
+
+/// Update stick position and get action if the stick crossed the threshold
+fn stick_moved(position: &mut i8, value: f32, positive: GamepadAction, negative: GamepadAction) -> Option<GamepadAction> {
+    let new_position = if value > STICK_THRESHOLD {
+        1
+    } else if value < -STICK_THRESHOLD {
+        -1
+    } else {
+        0
+    };
+
+    if std::mem::replace(position, new_position) == new_position {
+        return None;
+    }
+
+    match new_position {
+        1  => Some(positive),
+        -1 => Some(negative),
+
+        _ => None
+    }
+}
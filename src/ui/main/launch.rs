@@ -4,10 +4,23 @@ use crate::i18n::*;
 use super::{App, AppMsg};
 
 pub fn launch(sender: ComponentSender<App>) {
+    sender.input(AppMsg::SetGameRunning(true));
     sender.input(AppMsg::HideWindow);
 
     std::thread::spawn(move || {
-        if let Err(err) = anime_launcher_sdk::star_rail::game::run() {
+        let result = crate::hooks::run_game(|err| {
+            sender.input(AppMsg::Toast {
+                title: tr("game-language-apply-failed"),
+                description: Some(err.to_string())
+            });
+        }, |hook, err| {
+            sender.input(AppMsg::Toast {
+                title: tr(hook.failed_locale_key()),
+                description: Some(err.to_string())
+            });
+        });
+
+        if let Err(err) = result {
             tracing::error!("Failed to launch game: {err}");
 
             sender.input(AppMsg::Toast {
@@ -16,6 +29,12 @@ pub fn launch(sender: ComponentSender<App>) {
             });
         }
 
-        sender.input(AppMsg::ShowWindow);
+        sender.input(AppMsg::SetGameRunning(false));
     });
 }
+
+/// Kill running game process
+#[inline]
+pub fn kill() -> anyhow::Result<()> {
+    crate::hooks::kill_game()
+}
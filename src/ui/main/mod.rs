@@ -1,20 +1,24 @@
 use relm4::{
     prelude::*,
     component::*,
-    actions::*,
-    MessageBroker
+    actions::*
 };
 
 use gtk::prelude::*;
 use adw::prelude::*;
 
 use gtk::glib::clone;
+use gtk::gio;
 
 mod apply_patch;
 mod download_wine;
 mod create_prefix;
 mod download_diff;
 mod launch;
+mod tray;
+mod gamepad;
+mod cancel;
+pub mod dbus;
 
 use anime_launcher_sdk::components::loader::ComponentsLoader;
 
@@ -29,9 +33,20 @@ use anime_launcher_sdk::star_rail::consts::*;
 use crate::*;
 use crate::i18n::*;
 use crate::ui::components::*;
+use crate::config_watcher::{self, ConfigReload};
+use crate::warps::url::WarpUrl;
+use crate::settings::Settings;
 
+use tray::LauncherTray;
+use dbus::DBusService;
+use gamepad::GamepadAction;
+
+use unic_langid::LanguageIdentifier;
+
+use super::preferences::ReadyFlag;
 use super::preferences::main::*;
 use super::about::*;
+use super::warps::*;
 
 relm4::new_action_group!(WindowActionGroup, "win");
 
@@ -40,14 +55,22 @@ relm4::new_stateless_action!(GameFolder, WindowActionGroup, "game_folder");
 relm4::new_stateless_action!(ConfigFile, WindowActionGroup, "config_file");
 relm4::new_stateless_action!(DebugFile, WindowActionGroup, "debug_file");
 relm4::new_stateless_action!(WishUrl, WindowActionGroup, "wish_url");
+relm4::new_stateless_action!(Warps, WindowActionGroup, "warps");
+relm4::new_stateless_action!(DryRun, WindowActionGroup, "dry_run");
 
 relm4::new_stateless_action!(About, WindowActionGroup, "about");
 
-pub static mut MAIN_WINDOW: Option<adw::ApplicationWindow> = None;
-pub static mut PREFERENCES_WINDOW: Option<AsyncController<PreferencesApp>> = None;
-pub static mut ABOUT_DIALOG: Option<Controller<AboutDialog>> = None;
-
 pub struct App {
+    window: adw::ApplicationWindow,
+    preferences_window: AsyncController<PreferencesApp>,
+
+    /// Allows preferences widgets to update the config. Replaced with the preferences window
+    preferences_ready: ReadyFlag,
+
+    about_dialog: Controller<AboutDialog>,
+    warps_window: Controller<WarpsApp>,
+
+    main_menu: gio::Menu,
     progress_bar: AsyncController<ProgressBar>,
 
     toast_overlay: adw::ToastOverlay,
@@ -56,6 +79,30 @@ pub struct App {
     style: LauncherStyle,
     state: Option<LauncherState>,
 
+    game_diff: Option<VersionDiff>,
+    main_patch: Option<MainPatch>,
+
+    /// Keeps config file watching alive
+    _config_monitor: Option<gio::FileMonitor>,
+    _settings_monitor: Option<gio::FileMonitor>,
+
+    /// Tray icon shown while the window is hidden
+    tray: Option<ksni::Handle<LauncherTray>>,
+
+    /// Launcher control interface on the session bus
+    dbus: Option<DBusService>,
+
+    game_running: bool,
+    quit_after_game: bool,
+
+    last_update_check: std::time::Instant,
+
+    /// Fullscreen layout with gamepad navigation
+    big_picture: bool,
+
+    /// Launcher finished loading
+    ready: bool,
+
     downloading: bool,
     disabled_buttons: bool
 }
@@ -85,20 +132,73 @@ pub enum AppMsg {
     /// Supposed to be called automatically on app's run when the launcher state was chosen
     SetLauncherState(Option<LauncherState>),
 
+    /// Supposed to be called automatically on app's run when all the launcher data is loaded
+    SetReady,
+
     SetLauncherStyle(LauncherStyle),
+    SetBigPicture(bool),
     SetLoadingStatus(Option<Option<String>>),
 
     SetDownloading(bool),
     DisableButtons(bool),
 
     OpenPreferences,
+    OpenAbout,
+    OpenWarps,
+
+    /// Show found warps URL with copy and open buttons
+    ShowWarpUrl(WarpUrl),
+
+    /// Show the game launching command without starting anything
+    ShowDryRun,
+
+    /// Change launcher language and re-render the UI
+    SetLanguage(LanguageIdentifier),
+
+    /// Load config file changes made outside of the launcher
+    ReloadConfig,
+
+    /// Update widgets showing launcher settings if they were edited outside of the launcher
+    ReloadSettings,
 
     PredownloadUpdate,
+
+    /// Stop the running download on its next progress update
+    CancelDownload,
+
+    /// Start predownload if it's available and not downloaded yet
+    PredownloadInBackground,
+
+    /// Called every minute to check updates with the interval from the settings
+    ScheduledUpdateCheck,
     PerformAction,
 
     HideWindow,
     ShowWindow,
 
+    /// Command forwarded from the launcher started second time
+    HandleCommand(single_instance::Command),
+
+    /// Supposed to be called by the game launching thread
+    SetGameRunning(bool),
+
+    /// Stop the running game process
+    KillGame,
+
+    /// Close the launcher when the game will be closed
+    SetQuitAfterGame(bool),
+
+    Quit,
+
+    /// Supposed to be called by the gamepads input thread
+    Gamepad(GamepadAction),
+
+    /// Send desktop notification if the window is not focused
+    Notify {
+        title: String,
+        body: Option<String>
+    },
+
     Toast {
         title: String,
         description: Option<String>
@@ -111,29 +211,13 @@ impl SimpleComponent for App {
     type Input = AppMsg;
     type Output = ();
 
-    menu! {
-        main_menu: {
-            section! {
-                &tr("launcher-folder") => LauncherFolder,
-                &tr("game-folder") => GameFolder,
-                &tr("config-file") => ConfigFile,
-                &tr("debug-file") => DebugFile,
-            },
-
-            section! {
-                &tr("wish-url") => WishUrl
-            },
-
-            section! {
-                &tr("about") => About
-            }
-        }
-    }
-
     view! {
         main_window = adw::ApplicationWindow {
             set_icon_name: Some(APP_ID),
 
+            #[watch]
+            set_fullscreened: model.big_picture,
+
             #[watch]
             set_default_size: (
                 match model.style {
@@ -172,6 +256,9 @@ impl SimpleComponent for App {
                     set_orientation: gtk::Orientation::Vertical,
 
                     adw::HeaderBar {
+                        #[watch]
+                        set_visible: !model.big_picture,
+
                         #[watch]
                         set_css_classes: match model.style {
                             LauncherStyle::Modern => &[""],
@@ -189,11 +276,12 @@ impl SimpleComponent for App {
 
                         pack_end = &gtk::MenuButton {
                             set_icon_name: "open-menu-symbolic",
-                            set_menu_model: Some(&main_menu)
+                            set_menu_model: Some(&model.main_menu)
                         }
                     },
 
                     adw::StatusPage {
+                        #[watch]
                         set_title: &tr("loading-data"),
                         set_icon_name: Some(APP_ID),
                         set_vexpand: true,
@@ -279,6 +367,9 @@ impl SimpleComponent for App {
                             set_vexpand: true,
 
                             gtk::Box {
+                                #[watch]
+                                set_visible: !model.big_picture,
+
                                 #[watch]
                                 set_halign: match model.style {
                                     LauncherStyle::Modern => gtk::Align::Center,
@@ -357,132 +448,169 @@ impl SimpleComponent for App {
                                     gtk::Button {
                                         adw::ButtonContent {
                                             #[watch]
-                                            set_icon_name: match &model.state {
-                                                Some(LauncherState::Launch) |
-                                                Some(LauncherState::PredownloadAvailable { .. }) => "media-playback-start-symbolic",
-
-                                                Some(LauncherState::WineNotInstalled) |
-                                                Some(LauncherState::PrefixNotExists) => "document-save-symbolic",
+                                            set_icon_name: action_icon(model.state.as_ref()),
 
-                                                Some(LauncherState::GameUpdateAvailable(_)) |
-                                                Some(LauncherState::GameNotInstalled(_)) => "document-save-symbolic",
+                                            #[watch]
+                                            set_label: &action_label(model.state.as_ref())
+                                        },
 
-                                                Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
-                                                    PatchStatus::NotAvailable |
-                                                    PatchStatus::Outdated { .. } => "window-close-symbolic",
+                                        #[watch]
+                                        set_sensitive: !model.disabled_buttons && action_sensitive(model.state.as_ref()),
 
-                                                    PatchStatus::Testing { .. } |
-                                                    PatchStatus::Available { .. } => "document-save-symbolic"
-                                                }
+                                        #[watch]
+                                        set_css_classes: &[action_css_class(model.state.as_ref()), "pill"],
 
-                                                Some(LauncherState::GameOutdated(_)) |
-                                                None => "window-close-symbolic"
-                                            },
+                                        #[watch]
+                                        set_tooltip_text: Some(&action_tooltip(model.state.as_ref())),
 
-                                            #[watch]
-                                            set_label: &match &model.state {
-                                                Some(LauncherState::Launch) |
-                                                Some(LauncherState::PredownloadAvailable { .. }) => tr("launch"),
+                                        set_hexpand: false,
+                                        set_width_request: 200,
 
-                                                Some(LauncherState::MainPatchAvailable(_)) => tr("apply-patch"),
+                                        connect_clicked => AppMsg::PerformAction
+                                    }
+                                },
 
-                                                Some(LauncherState::WineNotInstalled) => tr("download-wine"),
-                                                Some(LauncherState::PrefixNotExists)  => tr("create-prefix"),
+                                adw::Bin {
+                                    set_css_classes: &["background", "round-bin"],
 
-                                                Some(LauncherState::GameUpdateAvailable(diff)) |
-                                                Some(LauncherState::GameOutdated(diff)) => {
-                                                    match (Config::get(), diff.file_name()) {
-                                                        (Ok(config), Some(filename)) => {
-                                                            let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+                                    gtk::Button {
+                                        #[watch]
+                                        set_sensitive: !model.disabled_buttons,
 
-                                                            if temp.join(filename).exists() {
-                                                                tr("resume")
-                                                            }
+                                        set_width_request: 44,
 
-                                                            else {
-                                                                tr("update")
-                                                            }
-                                                        }
+                                        add_css_class: "circular",
+                                        set_icon_name: "emblem-system-symbolic",
 
-                                                        _ => tr("update")
-                                                    }
-                                                },
+                                        connect_clicked => AppMsg::OpenPreferences
+                                    }
+                                }
+                            }
 
-                                                Some(LauncherState::GameNotInstalled(_)) => tr("download"),
+                            // Large tiles for gamepad navigation
+                            gtk::Box {
+                                #[watch]
+                                set_visible: model.big_picture,
 
-                                                None => String::from("...")
-                                            }
-                                        },
+                                set_halign: gtk::Align::Center,
+                                set_margin_top: 64,
+                                set_spacing: 24,
 
-                                        #[watch]
-                                        set_sensitive: !model.disabled_buttons && match &model.state {
-                                            Some(LauncherState::GameOutdated { .. }) => false,
+                                gtk::Button {
+                                    set_css_classes: &["card", "big-picture-tile"],
 
-                                            Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
-                                                PatchStatus::NotAvailable |
-                                                PatchStatus::Outdated { .. } => false,
+                                    #[watch]
+                                    set_visible: match model.state.as_ref() {
+                                        Some(LauncherState::PredownloadAvailable(game)) => update_check::predownload_needed(game),
+                                        _ => false
+                                    },
 
-                                                PatchStatus::Testing { .. } |
-                                                PatchStatus::Available { .. } => true
-                                            },
+                                    #[watch]
+                                    set_sensitive: !model.disabled_buttons,
 
-                                            Some(_) => true,
+                                    gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_valign: gtk::Align::Center,
+                                        set_spacing: 16,
 
-                                            None => false
+                                        gtk::Image {
+                                            set_icon_name: Some("document-save-symbolic"),
+                                            set_pixel_size: 64
                                         },
 
-                                        #[watch]
-                                        set_css_classes: match &model.state {
-                                            Some(LauncherState::GameOutdated { .. }) => &["warning", "pill"],
+                                        gtk::Label {
+                                            add_css_class: "title-2",
+
+                                            #[watch]
+                                            set_label: &tr("predownload")
+                                        }
+                                    },
+
+                                    connect_clicked => AppMsg::PredownloadUpdate
+                                },
+
+                                big_picture_action = gtk::Button {
+                                    #[watch]
+                                    set_css_classes: &["card", "big-picture-tile", action_css_class(model.state.as_ref())],
 
-                                            Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
-                                                PatchStatus::NotAvailable |
-                                                PatchStatus::Outdated { .. } => &["error", "pill"],
+                                    #[watch]
+                                    set_sensitive: !model.disabled_buttons && action_sensitive(model.state.as_ref()),
 
-                                                PatchStatus::Testing { .. } => &["warning", "pill"],
-                                                PatchStatus::Available { .. } => &["suggested-action", "pill"]
-                                            },
+                                    #[watch]
+                                    set_tooltip_text: Some(&action_tooltip(model.state.as_ref())),
 
-                                            Some(_) => &["suggested-action", "pill"],
+                                    gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_valign: gtk::Align::Center,
+                                        set_spacing: 16,
 
-                                            None => &["pill"]
+                                        gtk::Image {
+                                            #[watch]
+                                            set_icon_name: Some(action_icon(model.state.as_ref())),
+
+                                            set_pixel_size: 64
                                         },
 
-                                        #[watch]
-                                        set_tooltip_text: Some(&match &model.state {
-                                            Some(LauncherState::GameOutdated { .. }) => tr("main-window--version-outdated-tooltip"),
+                                        gtk::Label {
+                                            add_css_class: "title-2",
+
+                                            #[watch]
+                                            set_label: &action_label(model.state.as_ref())
+                                        }
+                                    },
 
-                                            Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
-                                                PatchStatus::NotAvailable => tr("main-window--patch-unavailable-tooltip"),
-                                                PatchStatus::Outdated { .. } => tr("main-window--patch-outdated-tooltip"),
+                                    connect_clicked => AppMsg::PerformAction
+                                },
 
-                                                _ => String::new()
-                                            },
+                                gtk::Button {
+                                    set_css_classes: &["card", "big-picture-tile"],
 
-                                            _ => String::new()
-                                        }),
+                                    #[watch]
+                                    set_sensitive: !model.disabled_buttons,
 
-                                        set_hexpand: false,
-                                        set_width_request: 200,
+                                    gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_valign: gtk::Align::Center,
+                                        set_spacing: 16,
 
-                                        connect_clicked => AppMsg::PerformAction
-                                    }
+                                        gtk::Image {
+                                            set_icon_name: Some("emblem-system-symbolic"),
+                                            set_pixel_size: 64
+                                        },
+
+                                        gtk::Label {
+                                            add_css_class: "title-2",
+
+                                            #[watch]
+                                            set_label: &tr("preferences")
+                                        }
+                                    },
+
+                                    connect_clicked => AppMsg::OpenPreferences
                                 },
 
-                                adw::Bin {
-                                    set_css_classes: &["background", "round-bin"],
+                                gtk::Button {
+                                    set_css_classes: &["card", "big-picture-tile"],
 
-                                    gtk::Button {
-                                        #[watch]
-                                        set_sensitive: !model.disabled_buttons,
+                                    gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_valign: gtk::Align::Center,
+                                        set_spacing: 16,
 
-                                        set_width_request: 44,
+                                        gtk::Image {
+                                            set_icon_name: Some("application-exit-symbolic"),
+                                            set_pixel_size: 64
+                                        },
 
-                                        add_css_class: "circular",
-                                        set_icon_name: "emblem-system-symbolic",
+                                        gtk::Label {
+                                            add_css_class: "title-2",
 
-                                        connect_clicked => AppMsg::OpenPreferences
-                                    }
+                                            #[watch]
+                                            set_label: &tr("exit")
+                                        }
+                                    },
+
+                                    connect_clicked => AppMsg::Quit
                                 }
                             }
                         }
@@ -491,13 +619,20 @@ impl SimpleComponent for App {
             },
 
             connect_close_request[sender] => move |_| {
-                if let Err(err) = Config::flush() {
+                if let Err(err) = crate::config_backups::flush() {
                     sender.input(AppMsg::Toast {
                         title: tr("config-update-error"),
                         description: Some(err.to_string())
                     });
                 }
 
+                // Keep the launcher running in the tray
+                if Settings::get().map(|settings| settings.launcher.keep_in_background).unwrap_or_default() {
+                    sender.input(AppMsg::HideWindow);
+
+                    return gtk::Inhibit(true);
+                }
+
                 gtk::Inhibit::default()
             }
         }
@@ -510,7 +645,80 @@ impl SimpleComponent for App {
     ) -> ComponentParts<Self> {
         tracing::info!("Initializing main window");
 
+        let config_monitor = config_watcher::watch(clone!(@strong sender => move || {
+            sender.input(AppMsg::ReloadConfig);
+        }));
+
+        let config_monitor = match config_monitor {
+            Ok(monitor) => Some(monitor),
+
+            Err(err) => {
+                tracing::error!("Failed to watch config file: {err}");
+
+                None
+            }
+        };
+
+        // Remember current settings to not to treat them as changed
+        if let Err(err) = config_watcher::reload_settings() {
+            tracing::warn!("Failed to read launcher settings: {err}");
+        }
+
+        let settings_monitor = config_watcher::watch_settings(clone!(@strong sender => move || {
+            sender.input(AppMsg::ReloadSettings);
+        }));
+
+        let settings_monitor = match settings_monitor {
+            Ok(monitor) => Some(monitor),
+
+            Err(err) => {
+                tracing::error!("Failed to watch launcher settings file: {err}");
+
+                None
+            }
+        };
+
+        single_instance::set_command_handler({
+            let sender = sender.clone();
+
+            move |command| sender.input(AppMsg::HandleCommand(command))
+        });
+
+        let dbus = gio::Application::default().and_then(|app| {
+            match DBusService::register(&app, sender.clone()) {
+                Ok(service) => Some(service),
+
+                Err(err) => {
+                    tracing::error!("Failed to register D-Bus interface: {err}");
+
+                    None
+                }
+            }
+        });
+
+        let preferences_ready = ReadyFlag::default();
+
         let model = App {
+            window: root.clone(),
+
+            preferences_window: PreferencesApp::builder()
+                .launch((root.clone().into(), preferences_ready.clone()))
+                .forward(sender.input_sender(), std::convert::identity),
+
+            preferences_ready,
+
+            about_dialog: AboutDialog::builder()
+                .transient_for(root)
+                .launch(())
+                .detach(),
+
+            warps_window: WarpsApp::builder()
+                .transient_for(root)
+                .launch(())
+                .detach(),
+
+            main_menu: gio::Menu::new(),
+
             progress_bar: ProgressBar::builder()
                 .launch(ProgressBarInit {
                     caption: None,
@@ -526,10 +734,36 @@ impl SimpleComponent for App {
             style: CONFIG.launcher.style,
             state: None,
 
+            game_diff: None,
+            main_patch: None,
+
+            _config_monitor: config_monitor,
+            _settings_monitor: settings_monitor,
+
+            tray: None,
+            dbus,
+
+            game_running: false,
+            quit_after_game: false,
+
+            last_update_check: std::time::Instant::now(),
+
+            big_picture: Settings::get().unwrap_or_default().launcher.big_picture.is_enabled(),
+
+            ready: false,
+
             downloading: false,
             disabled_buttons: false
         };
 
+        fill_main_menu(&model.main_menu);
+
+        gtk::glib::timeout_add_seconds_local(60, clone!(@strong sender => move || {
+            sender.input(AppMsg::ScheduledUpdateCheck);
+
+            gtk::glib::Continue(true)
+        }));
+
         model.progress_bar.widget().set_halign(gtk::Align::Center);
         model.progress_bar.widget().set_width_request(360);
 
@@ -537,21 +771,6 @@ impl SimpleComponent for App {
 
         let widgets = view_output!();
 
-        let about_dialog_broker: MessageBroker<AboutDialogMsg> = MessageBroker::new();
-
-        unsafe {
-            MAIN_WINDOW = Some(widgets.main_window.clone());
-
-            PREFERENCES_WINDOW = Some(PreferencesApp::builder()
-                .launch(widgets.main_window.clone().into())
-                .forward(sender.input_sender(), std::convert::identity));
-
-            ABOUT_DIALOG = Some(AboutDialog::builder()
-                .transient_for(widgets.main_window.clone())
-                .launch_with_broker((), &about_dialog_broker)
-                .detach());
-        }
-
         let mut group = RelmActionGroup::<WindowActionGroup>::new();
 
         // TODO: reduce code somehow
@@ -607,72 +826,52 @@ impl SimpleComponent for App {
             }
         })));
 
+        group.add_action::<DryRun>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::ShowDryRun);
+        })));
+
         group.add_action::<WishUrl>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
             std::thread::spawn(clone!(@strong sender => move || {
-                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
-
-                let web_cache = config.game.path.for_edition(config.launcher.edition)
-                    .join(config.launcher.edition.data_folder())
-                    .join("webCaches/Cache/Cache_Data/data_2");
-
-                if !web_cache.exists() {
-                    tracing::error!("Couldn't find wishes URL: cache file doesn't exist");
-
-                    sender.input(AppMsg::Toast {
-                        title: tr("wish-url-search-failed"),
-                        description: None
-                    });
-                }
+                match crate::warps::url::get() {
+                    Ok(Some(url)) => sender.input(AppMsg::ShowWarpUrl(url)),
 
-                else {
-                    match std::fs::read(&web_cache) {
-                        Ok(web_cache) => {
-                            let web_cache = String::from_utf8_lossy(&web_cache);
+                    Ok(None) => {
+                        tracing::error!("Couldn't find wishes URL: no url found");
 
-                            // https://webstatic-sea.[ho-yo-ver-se].com/hkrpg/event/e20211215gacha-v2/index.html?......
-                            if let Some(url) = web_cache.lines().rev().find(|line| line.contains("gacha-v2/index.html")) {
-                                let url_begin_pos = url.find("https://").unwrap();
-                                let url_end_pos = url_begin_pos + url[url_begin_pos..].find("\0\0\0\0").unwrap();
-
-                                if let Err(err) = open::that(format!("{}#/log", &url[url_begin_pos..url_end_pos])) {
-                                    tracing::error!("Failed to open wishes URL: {err}");
-    
-                                    sender.input(AppMsg::Toast {
-                                        title: tr("wish-url-opening-error"),
-                                        description: Some(err.to_string())
-                                    });
-                                }
-                            }
-
-                            else {
-                                tracing::error!("Couldn't find wishes URL: no url found");
-
-                                sender.input(AppMsg::Toast {
-                                    title: tr("wish-url-search-failed"),
-                                    description: None
-                                });
-                            }
-                        }
+                        sender.input(AppMsg::Toast {
+                            title: tr("wish-url-search-failed"),
+                            description: None
+                        });
+                    }
 
-                        Err(err) => {
-                            tracing::error!("Couldn't find wishes URL: failed to open cache file: {err}");
+                    Err(err) => {
+                        tracing::error!("Couldn't find wishes URL: {err}");
 
-                            sender.input(AppMsg::Toast {
-                                title: tr("wish-url-search-failed"),
-                                description: Some(err.to_string())
-                            });
-                        }
+                        sender.input(AppMsg::Toast {
+                            title: tr("wish-url-search-failed"),
+                            description: Some(err.to_string())
+                        });
                     }
                 }
             }));
         })));
 
-        group.add_action::<About>(RelmAction::new_stateless(move |_| {
-            about_dialog_broker.send(AboutDialogMsg::Show);
-        }));
+        group.add_action::<Warps>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::OpenWarps);
+        })));
+
+        group.add_action::<About>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::OpenAbout);
+        })));
 
         widgets.main_window.insert_action_group("win", Some(&group.into_action_group()));
 
+        if model.big_picture {
+            gamepad::spawn(sender.clone());
+
+            widgets.big_picture_action.grab_focus();
+        }
+
         tracing::info!("Main window initialized");
 
         let download_picture = model.style == LauncherStyle::Classic && !KEEP_BACKGROUND_FILE.exists();
@@ -829,11 +1028,7 @@ impl SimpleComponent for App {
             });
 
             // Mark app as loaded
-            unsafe {
-                crate::READY = true;
-            }
-
-            tracing::info!("App is ready");
+            sender.input(AppMsg::SetReady);
         });
 
         ComponentParts { model, widgets }
@@ -901,106 +1096,665 @@ impl SimpleComponent for App {
             }
 
             #[allow(unused_must_use)]
-            AppMsg::SetGameDiff(diff) => unsafe {
-                PREFERENCES_WINDOW.as_ref().unwrap_unchecked().sender().send(PreferencesAppMsg::SetGameDiff(diff));
+            AppMsg::SetGameDiff(diff) => {
+                // First diff is got on startup, so only its changes are notified
+                if let (Some(_), Some(body)) = (&self.game_diff, diff_notification(&diff)) {
+                    if diff_notification(&self.game_diff).as_ref() != Some(&body) {
+                        self.notify(tr("notification-game-update"), Some(body));
+                    }
+                }
+
+                self.game_diff = diff.clone();
+
+                self.preferences_window.sender().send(PreferencesAppMsg::SetGameDiff(diff));
             }
 
             #[allow(unused_must_use)]
-            AppMsg::SetMainPatch(patch) => unsafe {
-                PREFERENCES_WINDOW.as_ref().unwrap_unchecked().sender().send(PreferencesAppMsg::SetMainPatch(patch));
+            AppMsg::SetMainPatch(patch) => {
+                if let (Some(old), Some(new)) = (patch_notification(&self.main_patch), patch_notification(&patch)) {
+                    if old != new {
+                        self.notify(tr("notification-patch-status"), Some(new));
+                    }
+                }
+
+                self.main_patch = patch.clone();
+
+                self.preferences_window.sender().send(PreferencesAppMsg::SetMainPatch(patch));
             }
 
             AppMsg::SetLauncherState(state) => {
                 self.state = state;
+
+                if let Some(dbus) = &self.dbus {
+                    dbus.set_state(self.state.as_ref(), self.downloading);
+                }
             }
 
             AppMsg::SetLoadingStatus(status) => {
                 self.loading = status;
             }
 
+            AppMsg::SetReady => {
+                self.ready = true;
+                self.preferences_ready.set(true);
+
+                tracing::info!("App is ready");
+            }
+
             AppMsg::SetLauncherStyle(style) => {
                 self.style = style;
             }
 
+            AppMsg::SetBigPicture(enabled) => {
+                self.big_picture = enabled;
+
+                if enabled {
+                    gamepad::spawn(sender.clone());
+                }
+            }
+
             AppMsg::SetDownloading(state) => {
                 self.downloading = state;
+
+                if let Some(dbus) = &self.dbus {
+                    dbus.set_state(self.state.as_ref(), self.downloading);
+                }
             }
 
             AppMsg::DisableButtons(state) => {
                 self.disabled_buttons = state;
             }
 
-            AppMsg::OpenPreferences => unsafe {
-                PREFERENCES_WINDOW.as_ref().unwrap_unchecked().widget().present();
+            AppMsg::OpenPreferences => {
+                self.preferences_window.widget().present();
             }
 
-            #[allow(unused_must_use)]
-            AppMsg::PredownloadUpdate => {
-                if let Some(LauncherState::PredownloadAvailable(mut game)) = self.state.clone() {
-                    let tmp = Config::get().unwrap().launcher.temp.unwrap_or_else(std::env::temp_dir);
+            AppMsg::OpenAbout => {
+                self.about_dialog.emit(AboutDialogMsg::Show);
+            }
 
-                    self.downloading = true;
+            AppMsg::OpenWarps => {
+                self.warps_window.emit(WarpsAppMsg::Show);
+            }
 
-                    let progress_bar_input = self.progress_bar.sender().clone();
+            AppMsg::ShowWarpUrl(url) => {
+                let body = match url.expires_in() {
+                    Some(expires_in) => tr_args("wish-url-age", [
+                        ("age", format_duration(url.age()).into()),
+                        ("expires", format_duration(expires_in).into())
+                    ]),
 
-                    progress_bar_input.send(ProgressBarMsg::UpdateCaption(Some(tr("downloading"))));
+                    None => tr_args("wish-url-expired", [
+                        ("age", format_duration(url.age()).into())
+                    ])
+                };
 
-                    std::thread::spawn(move || {
-                        let result = game.download_to(&tmp, clone!(@strong progress_bar_input => move |curr, total| {
-                            progress_bar_input.send(ProgressBarMsg::UpdateProgress(curr, total));
-                        }));
+                let dialog = adw::MessageDialog::new(
+                    Some(&self.window),
+                    Some(&tr("wish-url")),
+                    Some(&body)
+                );
 
-                        if let Err(err) = result {
-                            sender.input(AppMsg::Toast {
-                                title: tr("downloading-failed"),
-                                description: Some(err.to_string())
-                            });
+                let entry = gtk::Entry::new();
 
-                            tracing::error!("Failed to predownload update: {err}");
-                        }
+                entry.set_text(&url.url);
+                entry.set_editable(false);
 
-                        sender.input(AppMsg::SetDownloading(false));
-                        sender.input(AppMsg::UpdateLauncherState {
-                            perform_on_download_needed: false,
-                            apply_patch_if_needed: false,
-                            show_status_page: true
-                        });
-                    });
-                }
-            }
+                dialog.set_extra_child(Some(&entry));
 
-            AppMsg::PerformAction => unsafe {
-                match self.state.as_ref().unwrap_unchecked() {
-                    LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. }) |
-                    LauncherState::PredownloadAvailable { .. } |
-                    LauncherState::Launch => launch::launch(sender),
+                dialog.add_response("close", &tr("close"));
+                dialog.add_response("open", &tr("open"));
+                dialog.add_response("copy", &tr("copy"));
 
-                    LauncherState::MainPatchAvailable(patch) => apply_patch::apply_patch(sender, patch.to_owned()),
-                    LauncherState::WineNotInstalled => download_wine::download_wine(sender, self.progress_bar.sender().to_owned()),
-                    LauncherState::PrefixNotExists => create_prefix::create_prefix(sender),
+                dialog.set_response_appearance("copy", adw::ResponseAppearance::Suggested);
 
-                    LauncherState::GameUpdateAvailable(diff) |
-                    LauncherState::GameNotInstalled(diff) =>
-                        download_diff::download_diff(sender, self.progress_bar.sender().to_owned(), diff.to_owned()),
+                let clipboard = self.window.clipboard();
 
-                    LauncherState::GameOutdated(_) => ()
-                }
-            }
+                dialog.connect_response(Some("copy"), clone!(@strong sender, @strong url => move |_, _| {
+                    clipboard.set_text(&url.url);
 
-            AppMsg::HideWindow => unsafe {
-                MAIN_WINDOW.as_ref().unwrap_unchecked().set_visible(false);
-            }
+                    sender.input(AppMsg::Toast {
+                        title: tr("wish-url-copied"),
+                        description: None
+                    });
+                }));
+
+                dialog.connect_response(Some("open"), clone!(@strong sender => move |_, _| {
+                    if let Err(err) = open::that(url.page_url()) {
+                        tracing::error!("Failed to open wishes URL: {err}");
+
+                        sender.input(AppMsg::Toast {
+                            title: tr("wish-url-opening-error"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }));
+
+                dialog.present();
+            }
+
+            AppMsg::ShowDryRun => {
+                let report = match launch_command::dry_run_report() {
+                    Ok(report) => report,
+
+                    Err(err) => {
+                        tracing::error!("Failed to build the game command: {err}");
+
+                        self.toast(tr("dry-run-failed"), Some(err.to_string()));
+
+                        return;
+                    }
+                };
+
+                let dialog = adw::MessageDialog::new(
+                    Some(&self.window),
+                    Some(&tr("dry-run")),
+                    Some(&tr("dry-run-description"))
+                );
+
+                let label = gtk::Label::new(Some(&report));
+
+                label.set_selectable(true);
+                label.set_wrap(true);
+                label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+                label.set_xalign(0.0);
+                label.add_css_class("monospace");
+
+                let scrolled = gtk::ScrolledWindow::new();
+
+                scrolled.set_child(Some(&label));
+                scrolled.set_min_content_height(360);
+                scrolled.set_min_content_width(640);
+
+                dialog.set_extra_child(Some(&scrolled));
+
+                dialog.add_response("close", &tr("close"));
+                dialog.add_response("copy", &tr("copy"));
+
+                dialog.set_response_appearance("copy", adw::ResponseAppearance::Suggested);
+
+                let clipboard = self.window.clipboard();
+
+                dialog.connect_response(Some("copy"), clone!(@strong sender => move |_, _| {
+                    clipboard.set_text(&report);
+
+                    sender.input(AppMsg::Toast {
+                        title: tr("dry-run-copied"),
+                        description: None
+                    });
+                }));
+
+                dialog.present();
+            }
+
+            AppMsg::SetLanguage(lang) => {
+                // Re-read user translations so they can be tested without restart
+                if let Err(err) = overrides::load() {
+                    tracing::error!("Failed to load user locales: {err}");
+                }
+
+                if let Err(err) = set_lang(lang) {
+                    tracing::error!("Failed to set launcher language: {err}");
+
+                    self.toast(tr("language-change-failed"), Some(err.to_string()));
+
+                    return;
+                }
+
+                tracing::info!("Set UI language to {}", get_lang());
+
+                self.main_menu.remove_all();
+
+                fill_main_menu(&self.main_menu);
+
+                // Widgets which are not re-rendered by the messages
+                // are re-created with the new language
+                let about_dialog = AboutDialog::builder()
+                    .transient_for(&self.window)
+                    .launch(())
+                    .detach();
+
+                std::mem::replace(&mut self.about_dialog, about_dialog)
+                    .widget()
+                    .destroy();
+
+                let warps_window = WarpsApp::builder()
+                    .transient_for(&self.window)
+                    .launch(())
+                    .detach();
+
+                std::mem::replace(&mut self.warps_window, warps_window)
+                    .widget()
+                    .destroy();
+
+                self.recreate_preferences(&sender);
+            }
+
+            AppMsg::ReloadSettings => match config_watcher::reload_settings() {
+                Ok(false) => (),
+
+                Ok(true) => {
+                    self.toast(tr("settings-reloaded"), None);
+
+                    let big_picture = Settings::get().unwrap_or_default().launcher.big_picture.is_enabled();
+
+                    if big_picture != self.big_picture {
+                        sender.input(AppMsg::SetBigPicture(big_picture));
+                    }
+
+                    // Re-create preferences window to display new values
+                    self.recreate_preferences(&sender);
+                }
+
+                Err(err) => {
+                    tracing::warn!("Edited launcher settings file is invalid: {err}");
+
+                    self.toast(tr("settings-reload-invalid"), Some(err.to_string()));
+                }
+            }
+
+            AppMsg::ReloadConfig => match config_watcher::reload() {
+                Ok(ConfigReload::Unchanged) => (),
+
+                Ok(ConfigReload::Invalid(issues)) => {
+                    tracing::warn!("Edited config file is invalid: {:?}", issues);
+
+                    let issues = issues.iter()
+                        .map(|issue| issue.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+
+                    self.toast(tr("config-reload-invalid"), Some(issues));
+                }
+
+                Ok(ConfigReload::Reloaded { conflicts }) => {
+                    if conflicts.is_empty() {
+                        self.toast(tr("config-reloaded"), None);
+                    }
+
+                    else {
+                        let conflicts = conflicts.iter()
+                            .map(|change| format!("{}: {change}", change.path))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        self.toast(tr("config-reload-conflicts"), Some(conflicts));
+                    }
+
+                    if let Ok(config) = Config::get() {
+                        self.style = config.launcher.style;
+
+                        match config.launcher.language.parse::<LanguageIdentifier>() {
+                            Ok(lang) if negotiate_lang(&[lang.clone()], &supported_languages()) != Some(get_lang()) => {
+                                sender.input(AppMsg::SetLanguage(lang));
+                            }
+
+                            // Re-create preferences window to display new values
+                            _ => self.recreate_preferences(&sender)
+                        }
+                    }
+
+                    sender.input(AppMsg::UpdateLauncherState {
+                        perform_on_download_needed: false,
+                        apply_patch_if_needed: false,
+                        show_status_page: false
+                    });
+                }
+
+                Err(err) => {
+                    tracing::error!("Failed to reload config file: {err}");
+
+                    self.toast(tr("config-reload-failed"), Some(err.to_string()));
+                }
+            }
+
+            #[allow(unused_must_use)]
+            AppMsg::PredownloadUpdate => {
+                if let Some(LauncherState::PredownloadAvailable(mut game)) = self.state.clone() {
+                    let tmp = Config::get().unwrap().launcher.temp.unwrap_or_else(std::env::temp_dir);
+
+                    self.downloading = true;
+
+                    if let Some(dbus) = &self.dbus {
+                        dbus.set_state(self.state.as_ref(), self.downloading);
+                    }
+
+                    let progress_bar_input = self.progress_bar.sender().clone();
+
+                    progress_bar_input.send(ProgressBarMsg::UpdateCaption(Some(tr("downloading"))));
+
+                    std::thread::spawn(move || {
+                        let result = cancel::cancellable(|| game.download_to(&tmp, clone!(@strong progress_bar_input => move |curr, total| {
+                            cancel::check();
+
+                            dbus::emit_progress(curr, total);
+
+                            progress_bar_input.send(ProgressBarMsg::UpdateProgress(curr, total));
+                        })));
+
+                        match result {
+                            Some(Ok(_)) => sender.input(AppMsg::Notify {
+                                title: tr("notification-predownload-finished"),
+                                body: None
+                            }),
+
+                            None => {
+                                tracing::info!("Predownload cancelled");
+
+                                sender.input(AppMsg::Toast {
+                                    title: tr("downloading-cancelled"),
+                                    description: None
+                                });
+                            }
+
+                            Some(Err(err)) => {
+                                sender.input(AppMsg::Toast {
+                                    title: tr("downloading-failed"),
+                                    description: Some(err.to_string())
+                                });
+
+                                sender.input(AppMsg::Notify {
+                                    title: tr("downloading-failed"),
+                                    body: Some(err.to_string())
+                                });
+
+                                tracing::error!("Failed to predownload update: {err}");
+                            }
+                        }
+
+                        sender.input(AppMsg::SetDownloading(false));
+                        sender.input(AppMsg::UpdateLauncherState {
+                            perform_on_download_needed: false,
+                            apply_patch_if_needed: false,
+                            show_status_page: true
+                        });
+                    });
+                }
+            }
+
+            AppMsg::CancelDownload => {
+                if self.downloading {
+                    cancel::cancel();
+                }
+            }
+
+            AppMsg::PerformAction => {
+                let Some(state) = self.state.as_ref() else {
+                    return;
+                };
+
+                match state {
+                    LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. }) |
+                    LauncherState::PredownloadAvailable { .. } |
+                    LauncherState::Launch => launch::launch(sender),
+
+                    LauncherState::MainPatchAvailable(patch) => apply_patch::apply_patch(sender, patch.to_owned()),
+                    LauncherState::WineNotInstalled => download_wine::download_wine(sender, self.progress_bar.sender().to_owned()),
+                    LauncherState::PrefixNotExists => create_prefix::create_prefix(sender),
+
+                    LauncherState::GameUpdateAvailable(diff) |
+                    LauncherState::GameNotInstalled(diff) =>
+                        download_diff::download_diff(sender, self.progress_bar.sender().to_owned(), diff.to_owned()),
+
+                    LauncherState::GameOutdated(_) => ()
+                }
+            }
+
+            AppMsg::HideWindow => {
+                self.window.set_visible(false);
+
+                let settings = Settings::get().unwrap_or_default();
+
+                if settings.launcher.tray_icon && self.tray.is_none() {
+                    self.tray = Some(LauncherTray::spawn(sender.clone(), self.game_running, self.quit_after_game));
+                }
+
+                // Download the update while the launcher is in background
+                if settings.launcher.keep_in_background {
+                    sender.input(AppMsg::PredownloadInBackground);
+                }
+            }
+
+            AppMsg::PredownloadInBackground => {
+                if let Some(LauncherState::PredownloadAvailable(game)) = &self.state {
+                    if !self.downloading && update_check::predownload_needed(game) {
+                        tracing::info!("Starting predownload in background");
+
+                        sender.input(AppMsg::PredownloadUpdate);
+                    }
+                }
+            }
+
+            AppMsg::ScheduledUpdateCheck => {
+                let settings = Settings::get().unwrap_or_default().updates;
+
+                if settings.check_interval == 0 || self.last_update_check.elapsed().as_secs() < settings.check_interval * 60 {
+                    return;
+                }
 
-            AppMsg::ShowWindow => unsafe {
-                MAIN_WINDOW.as_ref().unwrap_unchecked().present();
+                // Don't change the state while the launcher is busy
+                if !self.ready || self.downloading || self.disabled_buttons || self.game_running {
+                    return;
+                }
+
+                self.last_update_check = std::time::Instant::now();
+
+                tracing::info!("Checking updates");
+
+                std::thread::spawn(move || {
+                    let status = match update_check::check() {
+                        Ok(status) => status,
+
+                        Err(err) => {
+                            tracing::error!("Failed to check updates: {err}");
+
+                            return;
+                        }
+                    };
+
+                    if status.patch.is_some() {
+                        sender.input(AppMsg::SetMainPatch(status.patch));
+                    }
+
+                    if status.diff.is_some() {
+                        sender.input(AppMsg::SetGameDiff(status.diff));
+                    }
+
+                    match LauncherState::get_from_config(|_| {}) {
+                        Ok(state) => sender.input(AppMsg::SetLauncherState(Some(state))),
+
+                        Err(err) => {
+                            tracing::error!("Failed to update launcher state: {err}");
+
+                            return;
+                        }
+                    }
+
+                    if settings.auto_predownload && update_check::is_predownload_time(&settings) {
+                        sender.input(AppMsg::PredownloadInBackground);
+                    }
+                });
+            }
+
+            AppMsg::ShowWindow => {
+                if let Some(tray) = self.tray.take() {
+                    tray.shutdown();
+                }
+
+                self.window.present();
+            }
+
+            AppMsg::HandleCommand(command) => {
+                let launch = !self.downloading && !self.game_running && match (command, self.state.as_ref()) {
+                    (single_instance::Command::RunGame | single_instance::Command::JustRunGame, Some(LauncherState::Launch)) => true,
+
+                    (single_instance::Command::JustRunGame, Some(
+                        LauncherState::PredownloadAvailable { .. } |
+                        LauncherState::MainPatchAvailable(MainPatch { status: PatchStatus::NotAvailable, .. })
+                    )) => true,
+
+                    _ => false
+                };
+
+                // Show the launcher so user can see why the game can't be launched
+                if launch {
+                    sender.input(AppMsg::PerformAction);
+                } else {
+                    sender.input(AppMsg::ShowWindow);
+                }
+            }
+
+            AppMsg::SetGameRunning(running) => {
+                self.game_running = running;
+
+                if let Some(tray) = &self.tray {
+                    tray.update(|tray| tray.game_running = running);
+                }
+
+                if !running {
+                    if self.quit_after_game {
+                        sender.input(AppMsg::Quit);
+                    }
+
+                    else if !Settings::get().map(|settings| settings.launcher.keep_in_background).unwrap_or_default() {
+                        sender.input(AppMsg::ShowWindow);
+                    }
+                }
             }
 
+            AppMsg::KillGame => {
+                if let Err(err) = launch::kill() {
+                    tracing::error!("Failed to kill the game: {err}");
+
+                    self.toast(tr("game-kill-failed"), Some(err.to_string()));
+                }
+            }
+
+            AppMsg::SetQuitAfterGame(quit) => {
+                self.quit_after_game = quit;
+            }
+
+            AppMsg::Quit => {
+                if let Err(err) = crate::config_backups::flush() {
+                    tracing::error!("Failed to save config: {err}");
+                }
+
+                if let Some(tray) = self.tray.take() {
+                    tray.shutdown();
+                }
+
+                if let Some(app) = self.window.application() {
+                    app.quit();
+                }
+            }
+
+            AppMsg::Gamepad(action) => {
+                if self.big_picture {
+                    self.gamepad_action(&sender, action);
+                }
+            }
+
+            AppMsg::Notify { title, body } => self.notify(title, body),
+
             AppMsg::Toast { title, description } => self.toast(title, description)
         }
     }
 }
 
 impl App {
+    /// Replace preferences window by the new one
+    /// 
+    /// Used to display changes which can't be applied to the existing widgets
+    #[allow(unused_must_use)]
+    fn recreate_preferences(&mut self, sender: &ComponentSender<Self>) {
+        // Prevent new preferences widgets from updating the config while they're initializing
+        self.preferences_ready = ReadyFlag::default();
+
+        let window = PreferencesApp::builder()
+            .launch((self.window.clone().into(), self.preferences_ready.clone()))
+            .forward(sender.input_sender(), std::convert::identity);
+
+        window.sender().send(PreferencesAppMsg::SetGameDiff(self.game_diff.clone()));
+        window.sender().send(PreferencesAppMsg::SetMainPatch(self.main_patch.clone()));
+
+        let old_window = std::mem::replace(&mut self.preferences_window, window);
+
+        if old_window.widget().is_visible() {
+            self.preferences_window.widget().present();
+        }
+
+        old_window.widget().destroy();
+
+        // Launcher is still loading otherwise and will mark preferences as ready itself
+        if self.ready {
+            let ready = self.preferences_ready.clone();
+
+            gtk::glib::idle_add_local_once(move || ready.set(true));
+        }
+    }
+
+    /// Send desktop notification. Toasts are enough when the window is focused
+    pub fn notify(&self, title: impl AsRef<str>, body: Option<String>) {
+        if self.window.is_active() {
+            return;
+        }
+
+        if !Settings::get().map(|settings| settings.launcher.notifications).unwrap_or(true) {
+            return;
+        }
+
+        let Some(app) = self.window.application() else {
+            return;
+        };
+
+        let notification = gio::Notification::new(title.as_ref());
+
+        if let Some(body) = body {
+            notification.set_body(Some(&body));
+        }
+
+        notification.set_icon(&gio::ThemedIcon::new(APP_ID));
+
+        app.send_notification(None, &notification);
+    }
+
+    /// Move focus or activate widgets of the focused window by the gamepad
+    fn gamepad_action(&self, sender: &ComponentSender<Self>, action: GamepadAction) {
+        let window = gtk::Window::list_toplevels()
+            .into_iter()
+            .filter_map(|window| window.downcast::<gtk::Window>().ok())
+            .find(|window| window.is_active() && window.is_visible());
+
+        let Some(window) = window else {
+            return;
+        };
+
+        let is_main = &window == self.window.upcast_ref::<gtk::Window>();
+
+        if let Some(direction) = action.direction() {
+            window.child_focus(direction);
+
+            return;
+        }
+
+        match action {
+            GamepadAction::Activate => {
+                if let Some(widget) = gtk::prelude::RootExt::focus(&window) {
+                    widget.activate();
+                }
+            }
+
+            // Main window can be closed only by the exit tile
+            GamepadAction::Back if !is_main => window.close(),
+
+            GamepadAction::Start if is_main && !self.disabled_buttons && action_sensitive(self.state.as_ref()) => {
+                sender.input(AppMsg::PerformAction);
+            }
+
+            _ => ()
+        }
+    }
+
     pub fn toast<T: AsRef<str>>(&mut self, title: T, description: Option<T>) {
         let toast = adw::Toast::new(title.as_ref());
 
@@ -1010,7 +1764,7 @@ impl App {
             toast.set_button_label(Some(&tr("details")));
 
             let dialog = adw::MessageDialog::new(
-                Some(unsafe { MAIN_WINDOW.as_ref().unwrap_unchecked() }),
+                Some(&self.window),
                 Some(title.as_ref()),
                 Some(description.as_ref())
             );
@@ -1034,3 +1788,187 @@ impl App {
         self.toast_overlay.add_toast(toast);
     }
 }
+
+fn fill_main_menu(menu: &gio::Menu) {
+    let section = gio::Menu::new();
+
+    section.append(Some(&tr("launcher-folder")), Some(&LauncherFolder::action_name()));
+    section.append(Some(&tr("game-folder")), Some(&GameFolder::action_name()));
+    section.append(Some(&tr("config-file")), Some(&ConfigFile::action_name()));
+    section.append(Some(&tr("debug-file")), Some(&DebugFile::action_name()));
+    section.append(Some(&tr("dry-run")), Some(&DryRun::action_name()));
+
+    menu.append_section(None, &section);
+
+    let section = gio::Menu::new();
+
+    section.append(Some(&tr("wish-url")), Some(&WishUrl::action_name()));
+    section.append(Some(&tr("warps")), Some(&Warps::action_name()));
+
+    menu.append_section(None, &section);
+
+    let section = gio::Menu::new();
+
+    section.append(Some(&tr("about")), Some(&About::action_name()));
+
+    menu.append_section(None, &section);
+}
+
+/// Format duration as hours and minutes
+fn format_duration(duration: std::time::Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+
+    tr_args("duration-hours-minutes", [
+        ("hours", (minutes / 60).to_string().into()),
+        ("minutes", (minutes % 60).to_string().into())
+    ])
+}
+
+/// Get notification text about available game update or predownload
+fn diff_notification(diff: &Option<VersionDiff>) -> Option<String> {
+    match diff {
+        Some(VersionDiff::Diff { latest, .. }) => Some(tr_args("notification-update-available", [
+            ("version", latest.to_string().into())
+        ])),
+
+        Some(VersionDiff::Predownload { latest, .. }) => Some(tr_args("notification-predownload-available", [
+            ("version", latest.to_string().into())
+        ])),
+
+        _ => None
+    }
+}
+
+/// Get notification text about the main patch status
+fn patch_notification(patch: &Option<MainPatch>) -> Option<String> {
+    Some(match patch.as_ref()?.status() {
+        PatchStatus::NotAvailable => tr("notification-patch-not-available"),
+
+        PatchStatus::Outdated { current, .. } => tr_args("notification-patch-outdated", [
+            ("version", current.to_string().into())
+        ]),
+
+        PatchStatus::Testing { version, .. } => tr_args("notification-patch-testing", [
+            ("version", version.to_string().into())
+        ]),
+
+        PatchStatus::Available { version, .. } => tr_args("notification-patch-available", [
+            ("version", version.to_string().into())
+        ])
+    })
+}
+
+/// Get icon of the main action button
+fn action_icon(state: Option<&LauncherState>) -> &'static str {
+    match state {
+        Some(LauncherState::Launch) |
+        Some(LauncherState::PredownloadAvailable { .. }) => "media-playback-start-symbolic",
+
+        Some(LauncherState::WineNotInstalled) |
+        Some(LauncherState::PrefixNotExists) => "document-save-symbolic",
+
+        Some(LauncherState::GameUpdateAvailable(_)) |
+        Some(LauncherState::GameNotInstalled(_)) => "document-save-symbolic",
+
+        Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
+            PatchStatus::NotAvailable |
+            PatchStatus::Outdated { .. } => "window-close-symbolic",
+
+            PatchStatus::Testing { .. } |
+            PatchStatus::Available { .. } => "document-save-symbolic"
+        }
+
+        Some(LauncherState::GameOutdated(_)) |
+        None => "window-close-symbolic"
+    }
+}
+
+/// Get label of the main action button
+fn action_label(state: Option<&LauncherState>) -> String {
+    match state {
+        Some(LauncherState::Launch) |
+        Some(LauncherState::PredownloadAvailable { .. }) => tr("launch"),
+
+        Some(LauncherState::MainPatchAvailable(_)) => tr("apply-patch"),
+
+        Some(LauncherState::WineNotInstalled) => tr("download-wine"),
+        Some(LauncherState::PrefixNotExists)  => tr("create-prefix"),
+
+        Some(LauncherState::GameUpdateAvailable(diff)) |
+        Some(LauncherState::GameOutdated(diff)) => {
+            match (Config::get(), diff.file_name()) {
+                (Ok(config), Some(filename)) => {
+                    let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+
+                    if temp.join(filename).exists() {
+                        tr("resume")
+                    }
+
+                    else {
+                        tr("update")
+                    }
+                }
+
+                _ => tr("update")
+            }
+        },
+
+        Some(LauncherState::GameNotInstalled(_)) => tr("download"),
+
+        None => String::from("...")
+    }
+}
+
+/// Check if the main action can be performed in the given state
+fn action_sensitive(state: Option<&LauncherState>) -> bool {
+    match state {
+        Some(LauncherState::GameOutdated { .. }) => false,
+
+        Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
+            PatchStatus::NotAvailable |
+            PatchStatus::Outdated { .. } => false,
+
+            PatchStatus::Testing { .. } |
+            PatchStatus::Available { .. } => true
+        },
+
+        Some(_) => true,
+
+        None => false
+    }
+}
+
+/// Get style class of the main action button
+fn action_css_class(state: Option<&LauncherState>) -> &'static str {
+    match state {
+        Some(LauncherState::GameOutdated { .. }) => "warning",
+
+        Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
+            PatchStatus::NotAvailable |
+            PatchStatus::Outdated { .. } => "error",
+
+            PatchStatus::Testing { .. } => "warning",
+            PatchStatus::Available { .. } => "suggested-action"
+        },
+
+        Some(_) => "suggested-action",
+
+        None => ""
+    }
+}
+
+/// Get tooltip of the main action button
+fn action_tooltip(state: Option<&LauncherState>) -> String {
+    match state {
+        Some(LauncherState::GameOutdated { .. }) => tr("main-window--version-outdated-tooltip"),
+
+        Some(LauncherState::MainPatchAvailable(MainPatch { status, .. })) => match status {
+            PatchStatus::NotAvailable => tr("main-window--patch-unavailable-tooltip"),
+            PatchStatus::Outdated { .. } => tr("main-window--patch-outdated-tooltip"),
+
+            _ => String::new()
+        },
+
+        _ => String::new()
+    }
+}
@@ -0,0 +1,113 @@
+use relm4::prelude::*;
+
+use ksni::menu::*;
+
+use crate::i18n::*;
+use super::{App, AppMsg};
+
+/// Tray icon shown while the launcher window is hidden
+pub struct LauncherTray {
+    pub sender: ComponentSender<App>,
+    pub game_running: bool,
+    pub quit_after_game: bool
+}
+
+impl LauncherTray {
+    /// Spawn tray icon service
+    pub fn spawn(sender: ComponentSender<App>, game_running: bool, quit_after_game: bool) -> ksni::Handle<Self> {
+        let service = ksni::TrayService::new(Self {
+            sender,
+            game_running,
+            quit_after_game
+        });
+
+        let handle = service.handle();
+
+        service.spawn();
+
+        handle
+    }
+}
+
+impl ksni::Tray for LauncherTray {
+    fn id(&self) -> String {
+        crate::APP_ID.to_string()
+    }
+
+    fn icon_name(&self) -> String {
+        crate::APP_ID.to_string()
+    }
+
+    fn title(&self) -> String {
+        String::from("The Honkers Railway Launcher")
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            title: self.title(),
+            description: if self.game_running {
+                tr("tray-game-running")
+            } else {
+                tr("tray-background")
+            },
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        self.sender.input(AppMsg::ShowWindow);
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        vec![
+            StandardItem {
+                label: tr("tray-show-launcher"),
+                activate: Box::new(|tray: &mut Self| tray.sender.input(AppMsg::ShowWindow)),
+                ..Default::default()
+            }.into(),
+
+            StandardItem {
+                label: tr("tray-kill-game"),
+                enabled: self.game_running,
+                activate: Box::new(|tray: &mut Self| tray.sender.input(AppMsg::KillGame)),
+                ..Default::default()
+            }.into(),
+
+            StandardItem {
+                label: tr("debug-file"),
+                activate: Box::new(|tray: &mut Self| {
+                    if let Err(err) = open::that(crate::DEBUG_FILE.as_os_str()) {
+                        tracing::error!("Failed to open debug file: {err}");
+
+                        tray.sender.input(AppMsg::Toast {
+                            title: tr("debug-file-opening-error"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }),
+                ..Default::default()
+            }.into(),
+
+            MenuItem::Separator,
+
+            CheckmarkItem {
+                label: tr("tray-quit-after-game"),
+                enabled: self.game_running,
+                checked: self.quit_after_game,
+                activate: Box::new(|tray: &mut Self| {
+                    tray.quit_after_game = !tray.quit_after_game;
+
+                    tray.sender.input(AppMsg::SetQuitAfterGame(tray.quit_after_game));
+                }),
+                ..Default::default()
+            }.into(),
+
+            StandardItem {
+                label: tr("tray-quit"),
+                enabled: !self.game_running,
+                activate: Box::new(|tray: &mut Self| tray.sender.input(AppMsg::Quit)),
+                ..Default::default()
+            }.into()
+        ]
+    }
+}
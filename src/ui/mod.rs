@@ -4,3 +4,5 @@ pub mod preferences;
 pub mod components;
 pub mod first_run;
 pub mod migrate_installation;
+pub mod config_recovery;
+pub mod warps;
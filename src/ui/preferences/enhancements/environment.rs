@@ -2,22 +2,31 @@ use relm4::prelude::*;
 use relm4::component::*;
 use relm4::factory::*;
 
+use gtk::prelude::*;
 use adw::prelude::*;
 
+use gtk::glib::clone;
+
 use super::EnhancementsAppMsg;
+use crate::ui::preferences::ReadyFlag;
 
-use crate::i18n::tr;
+use crate::i18n::{tr, tr_args};
+use crate::settings::Settings;
+use crate::hooks::TIMEOUTS;
+use crate::launch_command::{self, LaunchCommand, TemplateIssue, PLACEHOLDERS};
+use crate::environment::{self, PRESETS};
 use crate::*;
 
 #[derive(Debug)]
 struct Variable {
     key: String,
-    value: String
+    value: String,
+    enabled: bool
 }
 
 #[relm4::factory(async)]
 impl AsyncFactoryComponent for Variable {
-    type Init = (String, String);
+    type Init = (String, String, bool);
     type Input = EnvironmentPageMsg;
     type Output = EnvironmentPageMsg;
     type CommandOutput = ();
@@ -29,6 +38,17 @@ impl AsyncFactoryComponent for Variable {
             set_title: &self.key,
             set_subtitle: &self.value,
 
+            add_suffix = &gtk::Switch {
+                set_valign: gtk::Align::Center,
+                set_tooltip_text: Some(&tr("variable-enabled")),
+
+                set_state: self.enabled,
+
+                connect_state_notify[sender, index] => move |switch| {
+                    sender.output(EnvironmentPageMsg::Toggle(index.clone(), switch.state()));
+                }
+            },
+
             add_suffix = &gtk::Button {
                 set_icon_name: "user-trash-symbolic",
                 add_css_class: "flat",
@@ -48,7 +68,8 @@ impl AsyncFactoryComponent for Variable {
     ) -> Self {
         Self {
             key: init.0,
-            value: init.1
+            value: init.1,
+            enabled: init.2
         }
     }
 
@@ -59,20 +80,53 @@ impl AsyncFactoryComponent for Variable {
 
 pub struct EnvironmentPage {
     variables: AsyncFactoryVecDeque<Variable>,
+    preset_switches: Vec<gtk::Switch>,
 
+    command_entry: adw::EntryRow,
     name_entry: adw::EntryRow,
-    value_entry: adw::EntryRow
+    value_entry: adw::EntryRow,
+
+    pre_launch_entry: adw::EntryRow,
+    post_exit_entry: adw::EntryRow,
+
+    ready: ReadyFlag,
+
+    /// Problems of the game command template
+    command_issues: Vec<String>,
+
+    /// Expanded command which will be executed to launch the game
+    command_preview: String
 }
 
 #[derive(Debug, Clone)]
 pub enum EnvironmentPageMsg {
     Add,
-    Remove(DynamicIndex)
+    Remove(DynamicIndex),
+
+    /// Enable or disable variable without removing it
+    Toggle(DynamicIndex, bool),
+
+    TogglePreset(usize, bool),
+
+    ImportFile,
+    ExportFile,
+
+    /// Add variables read from the `.env` file
+    Import(Vec<(String, String, bool)>),
+
+    /// Save hooks commands. Called when their entries are applied or lose focus
+    SaveHooks,
+
+    /// Validate the game command and update its preview
+    UpdateCommand,
+
+    InsertPlaceholder(&'static str),
+    CopyCommand
 }
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for EnvironmentPage {
-    type Init = ();
+    type Init = ReadyFlag;
     type Input = EnvironmentPageMsg;
     type Output = EnhancementsAppMsg;
 
@@ -103,11 +157,27 @@ impl SimpleAsyncComponent for EnvironmentPage {
                     set_title: &tr("game-command"),
                     set_description: Some(&tr("game-command-description")),
 
-                    adw::EntryRow {
+                    #[local_ref]
+                    command_entry -> adw::EntryRow {
                         set_title: "%command%",
-                        set_text: CONFIG.game.command.as_ref().unwrap_or(&String::new()).trim(),
+                        set_text: config.game.command.as_ref().unwrap_or(&String::new()).trim(),
+
+                        add_suffix = &gtk::MenuButton {
+                            set_icon_name: "list-add-symbolic",
+                            set_tooltip_text: Some(&tr("insert-placeholder")),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+
+                            #[wrap(Some)]
+                            set_popover = &gtk::Popover {
+                                #[local_ref]
+                                placeholders_box -> gtk::Box {
+                                    set_orientation: gtk::Orientation::Vertical
+                                }
+                            }
+                        },
 
-                        connect_changed => |entry| {
+                        connect_changed[sender] => move |entry| {
                             if let Ok(mut config) = Config::get() {
                                 let command = entry.text().trim().to_string();
 
@@ -119,10 +189,135 @@ impl SimpleAsyncComponent for EnvironmentPage {
 
                                 Config::update(config);
                             }
+
+                            sender.input(EnvironmentPageMsg::UpdateCommand);
+                        }
+                    },
+
+                    adw::ActionRow {
+                        set_title: &tr("game-command-issues"),
+                        add_css_class: "warning",
+
+                        add_prefix = &gtk::Image {
+                            set_icon_name: Some("dialog-warning-symbolic")
+                        },
+
+                        #[watch]
+                        set_subtitle: &model.command_issues.join("\n"),
+
+                        #[watch]
+                        set_visible: !model.command_issues.is_empty()
+                    },
+
+                    adw::ExpanderRow {
+                        set_title: &tr("final-command"),
+                        set_subtitle: &tr("final-command-description"),
+
+                        add_action = &gtk::Button {
+                            set_icon_name: "edit-copy-symbolic",
+                            set_tooltip_text: Some(&tr("copy")),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+
+                            connect_clicked => EnvironmentPageMsg::CopyCommand
+                        },
+
+                        add_row = &gtk::Label {
+                            set_margin_top: 12,
+                            set_margin_bottom: 12,
+                            set_margin_start: 12,
+                            set_margin_end: 12,
+                            set_xalign: 0.0,
+
+                            set_wrap: true,
+                            set_wrap_mode: gtk::pango::WrapMode::WordChar,
+                            set_selectable: true,
+
+                            add_css_class: "monospace",
+
+                            #[watch]
+                            set_label: &model.command_preview
                         }
                     }
                 },
 
+                add = &adw::PreferencesGroup {
+                    set_title: &tr("hooks"),
+                    set_description: Some(&tr("hooks-description")),
+
+                    #[local_ref]
+                    pre_launch_entry -> adw::EntryRow {
+                        set_title: &tr("pre-launch-hook"),
+                        set_text: settings.hooks.pre_launch.as_deref().unwrap_or_default(),
+                        set_show_apply_button: true,
+
+                        add_controller = &gtk::EventControllerFocus {
+                            connect_leave => EnvironmentPageMsg::SaveHooks
+                        },
+
+                        connect_apply => EnvironmentPageMsg::SaveHooks
+                    },
+
+                    #[local_ref]
+                    post_exit_entry -> adw::EntryRow {
+                        set_title: &tr("post-exit-hook"),
+                        set_text: settings.hooks.post_exit.as_deref().unwrap_or_default(),
+                        set_show_apply_button: true,
+
+                        add_controller = &gtk::EventControllerFocus {
+                            connect_leave => EnvironmentPageMsg::SaveHooks
+                        },
+
+                        connect_apply => EnvironmentPageMsg::SaveHooks
+                    },
+
+                    adw::ComboRow {
+                        set_title: &tr("hooks-timeout"),
+
+                        set_model: Some(&gtk::StringList::new(&TIMEOUTS.iter()
+                            .map(|timeout| timeout_name(*timeout))
+                            .collect::<Vec<_>>()
+                            .iter()
+                            .map(String::as_str)
+                            .collect::<Vec<_>>())),
+
+                        set_selected: TIMEOUTS.iter()
+                            .position(|timeout| *timeout == settings.hooks.timeout)
+                            .unwrap_or(0) as u32,
+
+                        connect_selected_notify[ready] => move |row| {
+                            if ready.get() {
+                                if let Some(timeout) = TIMEOUTS.get(row.selected() as usize) {
+                                    update_settings(|settings| settings.hooks.timeout = *timeout);
+                                }
+                            }
+                        }
+                    },
+
+                    adw::ActionRow {
+                        set_title: &tr("abort-on-hook-failure"),
+                        set_subtitle: &tr("abort-on-hook-failure-description"),
+
+                        add_suffix = &gtk::Switch {
+                            set_valign: gtk::Align::Center,
+
+                            set_state: settings.hooks.abort_on_failure,
+
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
+                                    update_settings(|settings| settings.hooks.abort_on_failure = switch.state());
+                                }
+                            }
+                        }
+                    }
+                },
+
+                #[local_ref]
+                add = presets_group -> adw::PreferencesGroup {
+                    set_title: &tr("env-presets"),
+                    set_description: Some(&tr("env-presets-description"))
+                },
+
                 add = &adw::PreferencesGroup {
                     set_title: &tr("new-variable"),
 
@@ -152,55 +347,182 @@ impl SimpleAsyncComponent for EnvironmentPage {
                 },
 
                 #[local_ref]
-                add = variables -> adw::PreferencesGroup {}
+                add = variables -> adw::PreferencesGroup {
+                    set_title: &tr("variables"),
+
+                    #[wrap(Some)]
+                    set_header_suffix = &gtk::Box {
+                        set_spacing: 4,
+
+                        gtk::Button {
+                            set_icon_name: "document-open-symbolic",
+                            set_tooltip_text: Some(&tr("import-env-file")),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+
+                            connect_clicked => EnvironmentPageMsg::ImportFile
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "document-save-symbolic",
+                            set_tooltip_text: Some(&tr("export-env-file")),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+
+                            connect_clicked => EnvironmentPageMsg::ExportFile
+                        }
+                    }
+                }
             }
         }
     }
 
     async fn init(
-        _init: Self::Init,
+        ready: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing environment settings");
 
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+        let settings = Settings::get().unwrap_or_default();
+
         let mut model = Self {
             variables: AsyncFactoryVecDeque::new(adw::PreferencesGroup::new(), sender.input_sender()),
+            preset_switches: Vec::new(),
 
+            command_entry: adw::EntryRow::new(),
             name_entry: adw::EntryRow::new(),
-            value_entry: adw::EntryRow::new()
+            value_entry: adw::EntryRow::new(),
+
+            pre_launch_entry: adw::EntryRow::new(),
+            post_exit_entry: adw::EntryRow::new(),
+
+            ready: ready.clone(),
+
+            command_issues: Vec::new(),
+            command_preview: String::new()
         };
 
-        for (name, value) in &CONFIG.game.environment {
-            model.variables.guard().push_back((name.trim().to_string(), value.trim().to_string()));
+        let presets_group = adw::PreferencesGroup::new();
+
+        for (i, preset) in PRESETS.iter().enumerate() {
+            let row = adw::ActionRow::new();
+
+            row.set_title(&tr(preset.locale_key));
+
+            row.set_subtitle(&preset.variables.iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join(" "));
+
+            let switch = gtk::Switch::new();
+
+            switch.set_valign(gtk::Align::Center);
+            switch.set_state(preset.is_enabled(&config.game.environment));
+
+            switch.connect_state_notify(clone!(@strong sender => move |switch| {
+                sender.input(EnvironmentPageMsg::TogglePreset(i, switch.state()));
+            }));
+
+            row.add_suffix(&switch);
+            row.set_activatable_widget(Some(&switch));
+
+            presets_group.add(&row);
+
+            model.preset_switches.push(switch);
         }
 
+        let presets_group = &presets_group;
+
+        model.load_variables();
+
         let variables = model.variables.widget();
 
+        let command_entry = &model.command_entry;
         let name_entry = &model.name_entry;
         let value_entry = &model.value_entry;
 
+        let pre_launch_entry = &model.pre_launch_entry;
+        let post_exit_entry = &model.post_exit_entry;
+
+        let placeholders_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        for &(placeholder, description) in PLACEHOLDERS {
+            let button = gtk::Button::new();
+
+            button.add_css_class("flat");
+
+            let content = gtk::Box::new(gtk::Orientation::Vertical, 2);
+
+            let title = gtk::Label::new(Some(placeholder));
+            let subtitle = gtk::Label::new(Some(&tr(description)));
+
+            title.set_xalign(0.0);
+            title.add_css_class("monospace");
+
+            subtitle.set_xalign(0.0);
+            subtitle.add_css_class("dim-label");
+            subtitle.add_css_class("caption");
+
+            content.append(&title);
+            content.append(&subtitle);
+
+            button.set_child(Some(&content));
+
+            button.connect_clicked(clone!(@strong sender => move |button| {
+                if let Some(popover) = button.ancestor(gtk::Popover::static_type()) {
+                    popover.downcast::<gtk::Popover>().unwrap().popdown();
+                }
+
+                sender.input(EnvironmentPageMsg::InsertPlaceholder(placeholder));
+            }));
+
+            placeholders_box.append(&button);
+        }
+
+        let placeholders_box = &placeholders_box;
+
         let widgets = view_output!();
 
+        sender.input(EnvironmentPageMsg::UpdateCommand);
+
         AsyncComponentParts { model, widgets }
     }
 
-    async fn update(&mut self, msg: Self::Input, _sender: AsyncComponentSender<Self>) {
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
         match msg {
             EnvironmentPageMsg::Add => {
                 let name = self.name_entry.text().trim().to_string();
                 let value = self.value_entry.text().trim().to_string();
 
+                if !name.is_empty() && !environment::is_valid_name(&name) {
+                    sender.output(EnhancementsAppMsg::Toast {
+                        title: tr("env-variable-add-failed"),
+                        description: Some(tr_args("env-variable-wrong-name", [
+                            ("name", name.into())
+                        ]))
+                    }).unwrap();
+
+                    return;
+                }
+
                 if !name.is_empty() && !value.is_empty() {
                     if let Ok(mut config) = Config::get() {
                         self.name_entry.set_text("");
                         self.value_entry.set_text("");
 
-                        config.game.environment.insert(name.clone(), value.clone());
+                        update_settings(|settings| {
+                            settings.environment.disabled.remove(&name);
+                        });
+
+                        config.game.environment.insert(name, value);
 
                         Config::update(config);
 
-                        self.variables.guard().push_back((name, value));
+                        self.load_variables();
+
+                        sender.input(EnvironmentPageMsg::UpdateCommand);
                     }
                 }
             }
@@ -208,14 +530,316 @@ impl SimpleAsyncComponent for EnvironmentPage {
             EnvironmentPageMsg::Remove(index) => {
                 if let Ok(mut config) = Config::get() {
                     if let Some(var) = self.variables.guard().get(index.current_index()) {
-                        config.game.environment.remove(&var.key);
-
-                        Config::update(config);
+                        if var.enabled {
+                            config.game.environment.remove(&var.key);
+
+                            Config::update(config);
+                        } else {
+                            update_settings(|settings| {
+                                settings.environment.disabled.remove(&var.key);
+                            });
+                        }
                     }
 
                     self.variables.guard().remove(index.current_index());
+
+                    self.update_presets();
+
+                    sender.input(EnvironmentPageMsg::UpdateCommand);
+                }
+            }
+
+            EnvironmentPageMsg::Toggle(index, enabled) => {
+                if let Ok(mut config) = Config::get() {
+                    let mut guard = self.variables.guard();
+
+                    let Some(var) = guard.get_mut(index.current_index()) else {
+                        return;
+                    };
+
+                    // Switches notify about their initial state as well
+                    if var.enabled == enabled {
+                        return;
+                    }
+
+                    var.enabled = enabled;
+
+                    let key = var.key.clone();
+                    let value = var.value.clone();
+
+                    drop(guard);
+
+                    if enabled {
+                        update_settings(|settings| {
+                            settings.environment.disabled.remove(&key);
+                        });
+
+                        config.game.environment.insert(key, value);
+                    } else {
+                        config.game.environment.remove(&key);
+
+                        update_settings(|settings| {
+                            settings.environment.disabled.insert(key, value);
+                        });
+                    }
+
+                    Config::update(config);
+
+                    self.update_presets();
+
+                    sender.input(EnvironmentPageMsg::UpdateCommand);
+                }
+            }
+
+            EnvironmentPageMsg::TogglePreset(index, enabled) => {
+                let Some(preset) = PRESETS.get(index) else {
+                    return;
+                };
+
+                if let Ok(mut config) = Config::get() {
+                    if preset.is_enabled(&config.game.environment) == enabled {
+                        return;
+                    }
+
+                    update_settings(|settings| {
+                        if enabled {
+                            preset.enable(&mut config.game.environment, &mut settings.environment.overridden);
+
+                            for (key, _) in preset.variables {
+                                settings.environment.disabled.remove(*key);
+                            }
+                        } else {
+                            preset.disable(&mut config.game.environment, &mut settings.environment.overridden);
+                        }
+                    });
+
+                    Config::update(config);
+
+                    self.load_variables();
+
+                    sender.input(EnvironmentPageMsg::UpdateCommand);
+                }
+            }
+
+            EnvironmentPageMsg::ImportFile => {
+                std::thread::spawn(move || {
+                    let path = rfd::FileDialog::new().pick_file();
+
+                    let Some(path) = path else {
+                        return;
+                    };
+
+                    match environment::import_file(&path) {
+                        Ok(variables) => sender.input(EnvironmentPageMsg::Import(variables)),
+
+                        Err(err) => {
+                            tracing::error!("Failed to import environment variables: {err}");
+
+                            sender.output(EnhancementsAppMsg::Toast {
+                                title: tr("env-file-import-failed"),
+                                description: Some(err.to_string())
+                            }).unwrap();
+                        }
+                    }
+                });
+            }
+
+            EnvironmentPageMsg::ExportFile => {
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+                let settings = Settings::get().unwrap_or_default();
+
+                std::thread::spawn(move || {
+                    let path = rfd::FileDialog::new()
+                        .set_file_name(".env")
+                        .save_file();
+
+                    let Some(path) = path else {
+                        return;
+                    };
+
+                    match environment::export_file(&path, &config.game.environment, &settings.environment.disabled) {
+                        Ok(()) => {
+                            sender.output(EnhancementsAppMsg::Toast {
+                                title: tr_args("env-file-exported", [
+                                    ("path", path.to_string_lossy().to_string().into())
+                                ]),
+                                description: None
+                            }).unwrap();
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to export environment variables: {err}");
+
+                            sender.output(EnhancementsAppMsg::Toast {
+                                title: tr("env-file-export-failed"),
+                                description: Some(err.to_string())
+                            }).unwrap();
+                        }
+                    }
+                });
+            }
+
+            EnvironmentPageMsg::Import(variables) => {
+                if let Ok(mut config) = Config::get() {
+                    let count = variables.len();
+
+                    update_settings(|settings| {
+                        for (key, value, enabled) in variables {
+                            if enabled {
+                                settings.environment.disabled.remove(&key);
+
+                                config.game.environment.insert(key, value);
+                            } else {
+                                config.game.environment.remove(&key);
+
+                                settings.environment.disabled.insert(key, value);
+                            }
+                        }
+                    });
+
+                    Config::update(config);
+
+                    self.load_variables();
+
+                    sender.input(EnvironmentPageMsg::UpdateCommand);
+
+                    sender.output(EnhancementsAppMsg::Toast {
+                        title: tr_args("env-file-imported", [
+                            ("count", count.to_string().into())
+                        ]),
+                        description: None
+                    }).unwrap();
+                }
+            }
+
+            EnvironmentPageMsg::SaveHooks => {
+                if !self.ready.get() {
+                    return;
+                }
+
+                let command = |entry: &adw::EntryRow| {
+                    let command = entry.text().trim().to_string();
+
+                    if command.is_empty() {
+                        None
+                    } else {
+                        Some(command)
+                    }
+                };
+
+                let pre_launch = command(&self.pre_launch_entry);
+                let post_exit = command(&self.post_exit_entry);
+
+                let hooks = Settings::get().map(|settings| settings.hooks).unwrap_or_default();
+
+                // Focus leaves the entries much more often than they're edited
+                if hooks.pre_launch != pre_launch || hooks.post_exit != post_exit {
+                    update_settings(|settings| {
+                        settings.hooks.pre_launch = pre_launch;
+                        settings.hooks.post_exit = post_exit;
+                    });
                 }
             }
+
+            EnvironmentPageMsg::UpdateCommand => {
+                self.command_issues = launch_command::validate(&self.command_entry.text())
+                    .into_iter()
+                    .map(|issue| match issue {
+                        TemplateIssue::NoCommand => tr("game-command-no-command"),
+
+                        TemplateIssue::UnknownPlaceholder(placeholder) => tr_args("game-command-unknown-placeholder", [
+                            ("placeholder", placeholder.into())
+                        ]),
+
+                        TemplateIssue::UnclosedQuote(quote) => tr_args("game-command-unclosed-quote", [
+                            ("quote", quote.to_string().into())
+                        ])
+                    })
+                    .collect();
+
+                self.command_preview = match LaunchCommand::get() {
+                    Ok(command) => command.to_string(),
+                    Err(err) => tr_args("final-command-failed", [
+                        ("error", err.to_string().into())
+                    ])
+                };
+            }
+
+            EnvironmentPageMsg::InsertPlaceholder(placeholder) => {
+                let mut position = self.command_entry.position();
+
+                self.command_entry.insert_text(placeholder, &mut position);
+                self.command_entry.set_position(position);
+            }
+
+            EnvironmentPageMsg::CopyCommand => {
+                self.command_entry.clipboard().set_text(&self.command_preview);
+            }
+        }
+    }
+}
+
+impl EnvironmentPage {
+    /// Fill variables list from the config and disabled variables from the launcher settings
+    fn load_variables(&mut self) {
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+        let settings = Settings::get().unwrap_or_default();
+
+        let mut variables = settings.environment.disabled.into_iter()
+            .filter(|(key, _)| !config.game.environment.contains_key(key))
+            .map(|(key, value)| (key, value, false))
+            .chain(config.game.environment.clone().into_iter().map(|(key, value)| (key, value, true)))
+            .collect::<Vec<_>>();
+
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut guard = self.variables.guard();
+
+        guard.clear();
+
+        for (key, value, enabled) in variables {
+            guard.push_back((key.trim().to_string(), value.trim().to_string(), enabled));
         }
+
+        drop(guard);
+
+        self.update_presets();
+    }
+
+    /// Sync presets switches with current variables
+    fn update_presets(&self) {
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+        for (preset, switch) in PRESETS.iter().zip(&self.preset_switches) {
+            switch.set_state(preset.is_enabled(&config.game.environment));
+        }
+    }
+}
+
+/// Update launcher settings file
+fn update_settings(update: impl FnOnce(&mut Settings)) {
+    let result = Settings::get().and_then(|mut settings| {
+        update(&mut settings);
+
+        Settings::update(&settings)
+    });
+
+    if let Err(err) = result {
+        tracing::error!("Failed to update launcher settings: {err}");
+    }
+}
+
+/// Get name of the hooks timeout
+fn timeout_name(seconds: u64) -> String {
+    match seconds {
+        0 => tr("hooks-no-timeout"),
+
+        seconds if seconds < 60 => tr_args("hooks-timeout-seconds", [
+            ("seconds", seconds.to_string().into())
+        ]),
+
+        seconds => tr_args("hooks-timeout-minutes", [
+            ("minutes", (seconds / 60).to_string().into())
+        ])
     }
 }
@@ -9,6 +9,7 @@ use anime_launcher_sdk::star_rail::sessions::Sessions;
 
 use super::EnhancementsAppMsg;
 
+use crate::ui::preferences::ReadyFlag;
 use crate::i18n::tr;
 use crate::*;
 
@@ -97,7 +98,7 @@ pub enum GamePageMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for GamePage {
-    type Init = ();
+    type Init = ReadyFlag;
     type Input = GamePageMsg;
     type Output = EnhancementsAppMsg;
 
@@ -132,8 +133,8 @@ impl SimpleAsyncComponent for GamePage {
                         set_title: &tr("active-sessions"),
                         set_subtitle: &tr("active-session-description"),
     
-                        connect_selected_notify[sender] => move |row| {
-                            if is_ready() {
+                        connect_selected_notify[sender, ready] => move |row| {
+                            if ready.get() {
                                 sender.input(GamePageMsg::SetCurrent(row.selected()));
                             }
                         }
@@ -163,7 +164,7 @@ impl SimpleAsyncComponent for GamePage {
     }
 
     async fn init(
-        _init: Self::Init,
+        ready: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
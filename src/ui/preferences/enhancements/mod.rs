@@ -22,8 +22,11 @@ use crate::*;
 
 use super::gamescope::*;
 use super::main::PreferencesAppMsg;
+use super::ReadyFlag;
 
 pub struct EnhancementsApp {
+    window: adw::PreferencesWindow,
+
     gamescope: AsyncController<GamescopeApp>,
     game_page: AsyncController<GamePage>,
     sandbox_page: AsyncController<SandboxPage>,
@@ -32,8 +35,6 @@ pub struct EnhancementsApp {
 
 #[derive(Debug)]
 pub enum EnhancementsAppMsg {
-    SetGamescopeParent(adw::PreferencesWindow),
-
     OpenGamescope,
     OpenMainPage,
     OpenGameSettingsPage,
@@ -48,7 +49,7 @@ pub enum EnhancementsAppMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for EnhancementsApp {
-    type Init = ();
+    type Init = (adw::PreferencesWindow, ReadyFlag);
     type Input = EnhancementsAppMsg;
     type Output = PreferencesAppMsg;
 
@@ -115,10 +116,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                         "FSync"
                     ]),
 
-                    set_selected: CONFIG.game.wine.sync.ordinal() as u32,
+                    set_selected: config.game.wine.sync.ordinal() as u32,
 
-                    connect_selected_notify => |row| unsafe {
-                        if is_ready() {
+                    connect_selected_notify[ready] => move |row| unsafe {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.game.wine.sync = WineSync::from_ordinal_unsafe(row.selected() as i8);
 
@@ -147,10 +148,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                         "한국어"
                     ]),
 
-                    set_selected: CONFIG.game.wine.language.ordinal() as u32,
+                    set_selected: config.game.wine.language.ordinal() as u32,
 
-                    connect_selected_notify => |row| unsafe {
-                        if is_ready() {
+                    connect_selected_notify[ready] => move |row| unsafe {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.game.wine.language = WineLang::from_ordinal_unsafe(row.selected() as i8);
 
@@ -166,10 +167,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.game.wine.borderless,
+                        set_state: config.game.wine.borderless,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.wine.borderless = switch.state();
 
@@ -193,10 +194,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                         &tr("custom")
                     ]),
 
-                    set_selected: CONFIG.game.wine.virtual_desktop.get_resolution().into(),
+                    set_selected: config.game.wine.virtual_desktop.get_resolution().into(),
 
-                    connect_selected_notify => |row| {
-                        if is_ready() {
+                    connect_selected_notify[ready] => move |row| {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 let (width, height) = Resolution::try_from(row.selected()).unwrap().get_pair();
 
@@ -211,10 +212,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.game.wine.virtual_desktop.enabled,
+                        set_state: config.game.wine.virtual_desktop.enabled,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.wine.virtual_desktop.enabled = switch.state();
 
@@ -239,10 +240,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                         "MangoHud"
                     ]),
 
-                    set_selected: CONFIG.game.enhancements.hud.ordinal() as u32,
+                    set_selected: config.game.enhancements.hud.ordinal() as u32,
 
-                    connect_selected_notify => |row| unsafe {
-                        if is_ready() {
+                    connect_selected_notify[ready] => move |row| unsafe {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.game.enhancements.hud = HUD::from_ordinal_unsafe(row.selected() as i8);
 
@@ -264,10 +265,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                         &tr("performance")
                     ]),
 
-                    set_selected: CONFIG.game.enhancements.fsr.quality.ordinal() as u32,
+                    set_selected: config.game.enhancements.fsr.quality.ordinal() as u32,
 
-                    connect_selected_notify => |row| unsafe {
-                        if is_ready() {
+                    connect_selected_notify[ready] => move |row| unsafe {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.game.enhancements.fsr.quality = FsrQuality::from_ordinal_unsafe(row.selected() as i8);
 
@@ -279,10 +280,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.game.enhancements.fsr.enabled,
+                        set_state: config.game.enhancements.fsr.enabled,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.fsr.enabled = switch.state();
 
@@ -302,10 +303,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.game.enhancements.gamemode,
+                        set_state: config.game.enhancements.gamemode,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamemode = switch.state();
 
@@ -334,10 +335,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.game.enhancements.gamescope.enabled,
+                        set_state: config.game.enhancements.gamescope.enabled,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.enabled = switch.state();
 
@@ -358,10 +359,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
 
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
-                        set_state: CONFIG.launcher.discord_rpc.enabled,
+                        set_state: config.launcher.discord_rpc.enabled,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.launcher.discord_rpc.enabled = switch.state();
 
@@ -374,10 +375,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
 
                 adw::EntryRow {
                     set_title: &tr("title"),
-                    set_text: &CONFIG.launcher.discord_rpc.title,
+                    set_text: &config.launcher.discord_rpc.title,
 
-                    connect_changed: |row| {
-                        if is_ready() {
+                    connect_changed[ready] => move |row| {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.launcher.discord_rpc.title = row.text().to_string();
 
@@ -389,10 +390,10 @@ impl SimpleAsyncComponent for EnhancementsApp {
 
                 adw::EntryRow {
                     set_title: &tr("description"),
-                    set_text: &CONFIG.launcher.discord_rpc.subtitle,
+                    set_text: &config.launcher.discord_rpc.subtitle,
 
-                    connect_changed: |row| {
-                        if is_ready() {
+                    connect_changed[ready] => move |row| {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
                                 config.launcher.discord_rpc.subtitle = row.text().to_string();
 
@@ -415,27 +416,34 @@ impl SimpleAsyncComponent for EnhancementsApp {
     }
 
     async fn init(
-        _init: Self::Init,
+        init: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing enhancements settings");
 
+        let (window, ready) = init;
+
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
         let model = Self {
             gamescope: GamescopeApp::builder()
-                .launch(())
+                .transient_for(&window)
+                .launch(ready.clone())
                 .detach(),
 
+            window,
+
             game_page: GamePage::builder()
-                .launch(())
+                .launch(ready.clone())
                 .forward(sender.input_sender(), std::convert::identity),
 
             sandbox_page: SandboxPage::builder()
-                .launch(())
+                .launch(ready.clone())
                 .forward(sender.input_sender(), std::convert::identity),
 
             environment_page: EnvironmentPage::builder()
-                .launch(())
+                .launch(ready)
                 .forward(sender.input_sender(), std::convert::identity)
         };
 
@@ -450,40 +458,24 @@ impl SimpleAsyncComponent for EnhancementsApp {
 
     async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
         match msg {
-            EnhancementsAppMsg::SetGamescopeParent(parent) => {
-                self.gamescope.widget().set_transient_for(Some(&parent));
-            }
-
             EnhancementsAppMsg::OpenGamescope => {
                 self.gamescope.widget().present();
             }
 
-            EnhancementsAppMsg::OpenMainPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .close_subpage();
+            EnhancementsAppMsg::OpenMainPage => {
+                self.window.close_subpage();
             }
 
-            EnhancementsAppMsg::OpenGameSettingsPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .present_subpage(self.game_page.widget());
+            EnhancementsAppMsg::OpenGameSettingsPage => {
+                self.window.present_subpage(self.game_page.widget());
             }
 
-            EnhancementsAppMsg::OpenSandboxSettingsPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .present_subpage(self.sandbox_page.widget());
+            EnhancementsAppMsg::OpenSandboxSettingsPage => {
+                self.window.present_subpage(self.sandbox_page.widget());
             }
 
-            EnhancementsAppMsg::OpenEnvironmentSettingsPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .present_subpage(self.environment_page.widget());
+            EnhancementsAppMsg::OpenEnvironmentSettingsPage => {
+                self.window.present_subpage(self.environment_page.widget());
             }
 
             EnhancementsAppMsg::Toast { title, description } => {
@@ -8,6 +8,7 @@ use anime_launcher_sdk::is_available;
 
 use super::EnhancementsAppMsg;
 
+use crate::ui::preferences::ReadyFlag;
 use crate::i18n::tr;
 use crate::*;
 
@@ -99,7 +100,7 @@ pub enum SandboxPageMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for SandboxPage {
-    type Init = ();
+    type Init = ReadyFlag;
     type Input = SandboxPageMsg;
     type Output = EnhancementsAppMsg;
 
@@ -139,10 +140,10 @@ impl SimpleAsyncComponent for SandboxPage {
                         add_suffix = &gtk::Switch {
                             set_valign: gtk::Align::Center,
 
-                            set_state: CONFIG.sandbox.enabled,
+                            set_state: config.sandbox.enabled,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.sandbox.enabled = switch.state();
 
@@ -160,10 +161,10 @@ impl SimpleAsyncComponent for SandboxPage {
                         add_suffix = &gtk::Switch {
                             set_valign: gtk::Align::Center,
 
-                            set_state: CONFIG.sandbox.isolate_home,
+                            set_state: config.sandbox.isolate_home,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.sandbox.isolate_home = switch.state();
 
@@ -176,7 +177,7 @@ impl SimpleAsyncComponent for SandboxPage {
 
                     adw::EntryRow {
                         set_title: &tr("hostname"),
-                        set_text: CONFIG.sandbox.hostname.as_ref().unwrap_or(&String::new()).trim(),
+                        set_text: config.sandbox.hostname.as_ref().unwrap_or(&String::new()).trim(),
 
                         connect_changed => |entry| {
                             if let Ok(mut config) = Config::get() {
@@ -195,7 +196,7 @@ impl SimpleAsyncComponent for SandboxPage {
 
                     adw::EntryRow {
                         set_title: &tr("additional-arguments"),
-                        set_text: CONFIG.sandbox.args.as_ref().unwrap_or(&String::new()).trim(),
+                        set_text: config.sandbox.args.as_ref().unwrap_or(&String::new()).trim(),
 
                         connect_changed => |entry| {
                             if let Ok(mut config) = Config::get() {
@@ -329,12 +330,14 @@ impl SimpleAsyncComponent for SandboxPage {
     }
 
     async fn init(
-        _init: Self::Init,
+        ready: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing sandbox settings");
 
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
         let mut model = Self {
             private_paths: AsyncFactoryVecDeque::new(adw::PreferencesGroup::new(), sender.input_sender()),
             shared_paths: AsyncFactoryVecDeque::new(adw::PreferencesGroup::new(), sender.input_sender()),
@@ -350,25 +353,25 @@ impl SimpleAsyncComponent for SandboxPage {
             symlink_path_to_entry: adw::EntryRow::new()
         };
 
-        for path in &CONFIG.sandbox.private {
+        for path in &config.sandbox.private {
             model.private_paths.guard().push_back((path.trim().to_string(), None));
         }
 
-        for (from, to) in &CONFIG.sandbox.mounts.read_only {
+        for (from, to) in &config.sandbox.mounts.read_only {
             model.shared_paths.guard().push_back((
                 from.trim().to_string(),
                 Some(format!("[read-only] {}", to.trim()))
             ));
         }
 
-        for (from, to) in &CONFIG.sandbox.mounts.bind {
+        for (from, to) in &config.sandbox.mounts.bind {
             model.shared_paths.guard().push_back((
                 from.trim().to_string(),
                 Some(to.trim().to_string())
             ));
         }
 
-        for (from, to) in &CONFIG.sandbox.mounts.symlinks {
+        for (from, to) in &config.sandbox.mounts.symlinks {
             model.symlink_paths.guard().push_back((
                 from.trim().to_string(),
                 Some(to.trim().to_string())
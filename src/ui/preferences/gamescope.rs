@@ -8,6 +8,7 @@ use anime_launcher_sdk::star_rail::config::Config;
 
 use anime_launcher_sdk::config::schema_blanks::prelude::*;
 
+use super::ReadyFlag;
 use crate::i18n::tr;
 use crate::*;
 
@@ -15,7 +16,7 @@ pub struct GamescopeApp;
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for GamescopeApp {
-    type Init = ();
+    type Init = ReadyFlag;
     type Input = ();
     type Output = ();
 
@@ -37,14 +38,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("width"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.game.width > 0 {
-                            CONFIG.game.enhancements.gamescope.game.width.to_string()
+                        set_text: &if config.game.enhancements.gamescope.game.width > 0 {
+                            config.game.enhancements.gamescope.game.width.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.game.width = row.text().parse().unwrap_or_default();
     
@@ -58,14 +59,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("height"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.game.height > 0 {
-                            CONFIG.game.enhancements.gamescope.game.height.to_string()
+                        set_text: &if config.game.enhancements.gamescope.game.height > 0 {
+                            config.game.enhancements.gamescope.game.height.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.game.height = row.text().parse().unwrap_or_default();
     
@@ -83,14 +84,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("width"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.gamescope.width > 0 {
-                            CONFIG.game.enhancements.gamescope.gamescope.width.to_string()
+                        set_text: &if config.game.enhancements.gamescope.gamescope.width > 0 {
+                            config.game.enhancements.gamescope.gamescope.width.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.gamescope.width = row.text().parse().unwrap_or_default();
     
@@ -104,14 +105,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("height"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.gamescope.height > 0 {
-                            CONFIG.game.enhancements.gamescope.gamescope.height.to_string()
+                        set_text: &if config.game.enhancements.gamescope.gamescope.height > 0 {
+                            config.game.enhancements.gamescope.gamescope.height.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.gamescope.height = row.text().parse().unwrap_or_default();
     
@@ -131,10 +132,10 @@ impl SimpleAsyncComponent for GamescopeApp {
 
                         add_suffix = &gtk::Switch {
                             set_valign: gtk::Align::Center,
-                            set_state: CONFIG.game.enhancements.gamescope.integer_scaling,
+                            set_state: config.game.enhancements.gamescope.integer_scaling,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.game.enhancements.gamescope.integer_scaling = switch.state();
         
@@ -151,10 +152,10 @@ impl SimpleAsyncComponent for GamescopeApp {
 
                         add_suffix = &gtk::Switch {
                             set_valign: gtk::Align::Center,
-                            set_state: CONFIG.game.enhancements.gamescope.fsr,
+                            set_state: config.game.enhancements.gamescope.fsr,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.game.enhancements.gamescope.fsr = switch.state();
         
@@ -171,10 +172,10 @@ impl SimpleAsyncComponent for GamescopeApp {
 
                         add_suffix = &gtk::Switch {
                             set_valign: gtk::Align::Center,
-                            set_state: CONFIG.game.enhancements.gamescope.nis,
+                            set_state: config.game.enhancements.gamescope.nis,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.game.enhancements.gamescope.nis = switch.state();
         
@@ -195,14 +196,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("framerate-limit"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.framerate.focused > 0 {
-                            CONFIG.game.enhancements.gamescope.framerate.focused.to_string()
+                        set_text: &if config.game.enhancements.gamescope.framerate.focused > 0 {
+                            config.game.enhancements.gamescope.framerate.focused.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.framerate.focused = row.text().parse().unwrap_or_default();
     
@@ -216,14 +217,14 @@ impl SimpleAsyncComponent for GamescopeApp {
                         set_title: &tr("unfocused-framerate-limit"),
                         set_input_purpose: gtk::InputPurpose::Digits,
 
-                        set_text: &if CONFIG.game.enhancements.gamescope.framerate.unfocused > 0 {
-                            CONFIG.game.enhancements.gamescope.framerate.unfocused.to_string()
+                        set_text: &if config.game.enhancements.gamescope.framerate.unfocused > 0 {
+                            config.game.enhancements.gamescope.framerate.unfocused.to_string()
                         } else {
                             String::new()
                         },
 
-                        connect_changed => |row| {
-                            if is_ready() {
+                        connect_changed[ready] => move |row| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.framerate.unfocused = row.text().parse().unwrap_or_default();
     
@@ -242,10 +243,10 @@ impl SimpleAsyncComponent for GamescopeApp {
                             &tr("fullscreen")
                         ]),
 
-                        set_selected: CONFIG.game.enhancements.gamescope.window_type.ordinal() as u32,
+                        set_selected: config.game.enhancements.gamescope.window_type.ordinal() as u32,
 
-                        connect_selected_notify => |row| unsafe {
-                            if is_ready() {
+                        connect_selected_notify[ready] => move |row| unsafe {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.game.enhancements.gamescope.window_type = WindowType::from_ordinal_unsafe(row.selected() as i8);
     
@@ -260,12 +261,14 @@ impl SimpleAsyncComponent for GamescopeApp {
     }
 
     async fn init(
-        _init: Self::Init,
+        ready: Self::Init,
         root: Self::Root,
         _sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing gamescope settings");
 
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
         let model = Self;
         let widgets = view_output!();
 
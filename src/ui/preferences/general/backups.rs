@@ -0,0 +1,229 @@
+use relm4::prelude::*;
+use relm4::component::*;
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib::clone;
+
+use crate::config_backups::{self, Backup};
+
+use super::GeneralAppMsg;
+
+use crate::i18n::*;
+use crate::*;
+
+pub struct BackupsPage {
+    backups: Vec<Backup>,
+
+    backups_group: adw::PreferencesGroup,
+    backups_rows: Vec<adw::ExpanderRow>
+}
+
+#[derive(Debug, Clone)]
+pub enum BackupsPageMsg {
+    /// Read backups list and compare them with the current config
+    Refresh,
+
+    Create,
+    Restore(usize)
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for BackupsPage {
+    type Init = ();
+    type Input = BackupsPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            adw::HeaderBar {
+                #[wrap(Some)]
+                set_title_widget = &adw::WindowTitle {
+                    set_title: &tr("config-backups")
+                },
+
+                pack_start = &gtk::Button {
+                    set_icon_name: "go-previous-symbolic",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.output(GeneralAppMsg::OpenMainPage).unwrap();
+                    }
+                }
+            },
+
+            adw::PreferencesPage {
+                #[local_ref]
+                add = backups_group -> adw::PreferencesGroup {
+                    set_title: &tr("config-backups"),
+                    set_description: Some(&tr("config-backups-description")),
+
+                    #[wrap(Some)]
+                    set_header_suffix = &gtk::Button {
+                        add_css_class: "flat",
+
+                        set_valign: gtk::Align::Center,
+
+                        adw::ButtonContent {
+                            set_icon_name: "document-save-symbolic",
+                            set_label: &tr("create-backup")
+                        },
+
+                        connect_clicked => BackupsPageMsg::Create
+                    }
+                }
+            }
+        }
+    }
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing general settings -> config backups page");
+
+        let model = Self {
+            backups: Vec::new(),
+
+            backups_group: adw::PreferencesGroup::new(),
+            backups_rows: Vec::new()
+        };
+
+        let backups_group = &model.backups_group;
+
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        tracing::debug!("Called config backups page event: {:?}", msg);
+
+        match msg {
+            BackupsPageMsg::Refresh => {
+                for row in self.backups_rows.drain(..) {
+                    self.backups_group.remove(&row);
+                }
+
+                self.backups = match config_backups::list() {
+                    Ok(backups) => backups,
+
+                    Err(err) => {
+                        tracing::error!("Failed to list config backups: {err}");
+
+                        sender.output(GeneralAppMsg::Toast {
+                            title: tr("config-backups-list-failed"),
+                            description: Some(err.to_string())
+                        }).unwrap();
+
+                        return;
+                    }
+                };
+
+                let current = Config::get()
+                    .and_then(|config| Ok(serde_json::to_value(config)?))
+                    .unwrap_or_default();
+
+                for (i, backup) in self.backups.iter().enumerate() {
+                    let row = adw::ExpanderRow::new();
+
+                    row.set_title(&backup.date());
+
+                    // Show what will be changed if this backup is restored
+                    let changes = match backup.read() {
+                        Ok(config) => config_backups::diff(&current, &config),
+
+                        Err(err) => {
+                            row.set_subtitle(&err.to_string());
+                            row.set_sensitive(false);
+
+                            vec![]
+                        }
+                    };
+
+                    if row.is_sensitive() {
+                        row.set_subtitle(&if changes.is_empty() {
+                            tr("config-backup-identical")
+                        } else {
+                            tr_args("config-backup-changes", [
+                                ("count", changes.len().to_string().into())
+                            ])
+                        });
+                    }
+
+                    for change in changes {
+                        let change_row = adw::ActionRow::new();
+
+                        change_row.set_title(&change.path);
+                        change_row.set_subtitle(&change.to_string());
+
+                        row.add_row(&change_row);
+                    }
+
+                    let button = gtk::Button::new();
+
+                    button.set_icon_name("edit-undo-symbolic");
+                    button.set_tooltip_text(Some(&tr("restore-backup")));
+                    button.add_css_class("flat");
+                    button.set_valign(gtk::Align::Center);
+
+                    button.connect_clicked(clone!(@strong sender => move |_| {
+                        sender.input(BackupsPageMsg::Restore(i));
+                    }));
+
+                    row.add_action(&button);
+
+                    self.backups_group.add(&row);
+                    self.backups_rows.push(row);
+                }
+            }
+
+            BackupsPageMsg::Create => {
+                // Backup is made from the file so save current changes first
+                let result = config_backups::flush()
+                    .and_then(|_| config_backups::create());
+
+                if let Err(err) = result {
+                    tracing::error!("Failed to create config backup: {err}");
+
+                    sender.output(GeneralAppMsg::Toast {
+                        title: tr("config-backup-create-failed"),
+                        description: Some(err.to_string())
+                    }).unwrap();
+                }
+
+                sender.input(BackupsPageMsg::Refresh);
+            }
+
+            BackupsPageMsg::Restore(index) => {
+                if let Some(backup) = self.backups.get(index) {
+                    match config_backups::restore(backup) {
+                        Ok(()) => {
+                            sender.output(GeneralAppMsg::Toast {
+                                title: tr("config-backup-restored"),
+                                description: None
+                            }).unwrap();
+
+                            // Restored files are loaded like external edits
+                            sender.output(GeneralAppMsg::ReloadConfig).unwrap();
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to restore config backup: {err}");
+
+                            sender.output(GeneralAppMsg::Toast {
+                                title: tr("config-backup-restore-failed"),
+                                description: Some(err.to_string())
+                            }).unwrap();
+                        }
+                    }
+
+                    sender.input(BackupsPageMsg::Refresh);
+                }
+            }
+        }
+    }
+}
@@ -14,6 +14,7 @@ use super::GeneralAppMsg;
 use crate::ui::components;
 use crate::ui::components::*;
 
+use crate::ui::preferences::ReadyFlag;
 use crate::i18n::*;
 use crate::*;
 
@@ -49,7 +50,7 @@ pub enum ComponentsPageMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for ComponentsPage {
-    type Init = ();
+    type Init = ReadyFlag;
     type Input = ComponentsPageMsg;
     type Output = GeneralAppMsg;
 
@@ -90,8 +91,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                         #[watch]
                         set_activatable: !model.selecting_wine_version,
 
-                        connect_selected_notify[sender] => move |row| {
-                            if is_ready() {
+                        connect_selected_notify[sender, ready] => move |row| {
+                            if ready.get() {
                                 sender.input(ComponentsPageMsg::SelectWine(row.selected() as usize));
                             }
                         } @wine_selected_notify,
@@ -114,8 +115,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                             #[block_signal(wine_recommended_notify)]
                             set_state: true,
 
-                            connect_state_notify[sender] => move |switch| {
-                                if is_ready() {
+                            connect_state_notify[sender, ready] => move |switch| {
+                                if ready.get() {
                                     sender.input(ComponentsPageMsg::WineRecommendedOnly(switch.state()));
                                 }
                             } @wine_recommended_notify
@@ -138,10 +139,10 @@ impl SimpleAsyncComponent for ComponentsPage {
                             set_valign: gtk::Align::Center,
 
                             #[block_signal(wine_shared_libraries_notify)]
-                            set_state: CONFIG.game.wine.shared_libraries.wine,
+                            set_state: config.game.wine.shared_libraries.wine,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.game.wine.shared_libraries.wine = switch.state();
 
@@ -160,10 +161,10 @@ impl SimpleAsyncComponent for ComponentsPage {
                             set_valign: gtk::Align::Center,
 
                             #[block_signal(gstreamer_shared_libraries_notify)]
-                            set_state: CONFIG.game.wine.shared_libraries.gstreamer,
+                            set_state: config.game.wine.shared_libraries.gstreamer,
 
-                            connect_state_notify => |switch| {
-                                if is_ready() {
+                            connect_state_notify[ready] => move |switch| {
+                                if ready.get() {
                                     if let Ok(mut config) = Config::get() {
                                         config.game.wine.shared_libraries.gstreamer = switch.state();
 
@@ -202,8 +203,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                         #[watch]
                         set_activatable: !model.selecting_dxvk_version,
 
-                        connect_selected_notify[sender] => move |row| {
-                            if is_ready() {
+                        connect_selected_notify[sender, ready] => move |row| {
+                            if ready.get() {
                                 sender.input(ComponentsPageMsg::SelectDxvk(row.selected() as usize));
                             }
                         } @dxvk_selected_notify,
@@ -226,8 +227,8 @@ impl SimpleAsyncComponent for ComponentsPage {
                             #[block_signal(dxvk_recommended_notify)]
                             set_state: true,
 
-                            connect_state_notify[sender] => move |switch| {
-                                if is_ready() {
+                            connect_state_notify[sender, ready] => move |switch| {
+                                if ready.get() {
                                     sender.input(ComponentsPageMsg::DxvkRecommendedOnly(switch.state()));
                                 }
                             } @dxvk_recommended_notify
@@ -246,18 +247,20 @@ impl SimpleAsyncComponent for ComponentsPage {
     }
 
     async fn init(
-        _init: Self::Init,
+        ready: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing general settings -> components page");
 
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
         let model = Self {
             wine_components: ComponentsList::builder()
                 .launch(ComponentsListInit {
                     pattern: ComponentsListPattern {
-                        download_folder: CONFIG.game.wine.builds.clone(),
-                        groups: wine::get_groups(&CONFIG.components.path).unwrap_or_default()
+                        download_folder: config.game.wine.builds.clone(),
+                        groups: wine::get_groups(&config.components.path).unwrap_or_default()
                             .into_iter()
                             .map(|mut group| {
                                 group.versions = group.versions.into_iter().take(12).collect();
@@ -287,8 +290,8 @@ impl SimpleAsyncComponent for ComponentsPage {
             dxvk_components: ComponentsList::builder()
                 .launch(ComponentsListInit {
                     pattern: ComponentsListPattern {
-                        download_folder: CONFIG.game.dxvk.builds.clone(),
-                        groups: dxvk::get_groups(&CONFIG.components.path).unwrap_or_default()
+                        download_folder: config.game.dxvk.builds.clone(),
+                        groups: dxvk::get_groups(&config.components.path).unwrap_or_default()
                             .into_iter()
                             .map(|mut group| {
                                 group.versions = group.versions.into_iter().take(12).collect();
@@ -318,8 +321,8 @@ impl SimpleAsyncComponent for ComponentsPage {
             downloaded_wine_versions: vec![],
             downloaded_dxvk_versions: vec![],
 
-            allow_dxvk_selection: match &CONFIG.game.wine.selected {
-                Some(version) => match wine::Group::find_in(&CONFIG.components.path, version) {
+            allow_dxvk_selection: match &config.game.wine.selected {
+                Some(version) => match wine::Group::find_in(&config.components.path, version) {
                     Ok(Some(group)) => group.features.unwrap_or_default().need_dxvk,
                     _ => true
                 }
@@ -354,7 +357,9 @@ impl SimpleAsyncComponent for ComponentsPage {
             }
 
             ComponentsPageMsg::UpdateDownloadedWine => {
-                self.downloaded_wine_versions = wine::get_downloaded(&CONFIG.components.path, &CONFIG.game.wine.builds)
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+                self.downloaded_wine_versions = wine::get_downloaded(&config.components.path, &config.game.wine.builds)
                     .unwrap_or_default()
                     .into_iter()
                     .flat_map(|group| group.versions.clone().into_iter()
@@ -365,7 +370,7 @@ impl SimpleAsyncComponent for ComponentsPage {
                         })
                     ).collect();
 
-                self.selected_wine_version = if let Some(selected) = &CONFIG.game.wine.selected {
+                self.selected_wine_version = if let Some(selected) = &config.game.wine.selected {
                     let mut index = 0;
 
                     for (i, (version, _)) in self.downloaded_wine_versions.iter().enumerate() {
@@ -385,13 +390,15 @@ impl SimpleAsyncComponent for ComponentsPage {
             }
 
             ComponentsPageMsg::UpdateDownloadedDxvk => {
-                self.downloaded_dxvk_versions = dxvk::get_downloaded(&CONFIG.components.path, &CONFIG.game.dxvk.builds)
+                let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+                self.downloaded_dxvk_versions = dxvk::get_downloaded(&config.components.path, &config.game.dxvk.builds)
                     .unwrap_or_default()
                     .into_iter()
                     .flat_map(|group| group.versions)
                     .collect();
 
-                self.selected_dxvk_version = if let Ok(Some(selected)) = CONFIG.get_selected_dxvk() {
+                self.selected_dxvk_version = if let Ok(Some(selected)) = config.get_selected_dxvk() {
                     let mut index = 0;
 
                     for (i, version) in self.downloaded_dxvk_versions.iter().enumerate() {
@@ -13,18 +13,36 @@ use anime_launcher_sdk::star_rail::config::Config;
 use anime_launcher_sdk::star_rail::config::schema::launcher::LauncherStyle;
 
 pub mod components;
+pub mod backups;
 
 use components::*;
+use backups::*;
+
+use unic_langid::LanguageIdentifier;
 
 use super::main::PreferencesAppMsg;
+use super::ReadyFlag;
 
 use crate::ui::migrate_installation::MigrateInstallationApp;
+use crate::settings::{Settings, BigPictureMode};
+use crate::game_language::{GameTextLanguage, GameVoiceLanguage};
+use crate::update_check::CHECK_INTERVALS;
+use crate::shortcuts::ShortcutKind;
 use crate::i18n::*;
 use crate::*;
 
+const HOURS: [&str; 24] = [
+    "00:00", "01:00", "02:00", "03:00", "04:00", "05:00", "06:00", "07:00",
+    "08:00", "09:00", "10:00", "11:00", "12:00", "13:00", "14:00", "15:00",
+    "16:00", "17:00", "18:00", "19:00", "20:00", "21:00", "22:00", "23:00"
+];
+
 pub struct GeneralApp {
+    window: adw::PreferencesWindow,
+
     migrate_installation: Controller<MigrateInstallationApp>,
     components_page: AsyncController<ComponentsPage>,
+    backups_page: AsyncController<BackupsPage>,
 
     game_diff: Option<VersionDiff>,
     main_patch: Option<MainPatch>,
@@ -51,11 +69,39 @@ pub enum GeneralAppMsg {
 
     OpenMainPage,
     OpenComponentsPage,
+    OpenBackupsPage,
+
+    UpdateLauncherState,
+
+    /// Load config and launcher settings files changed by the launcher itself
+    ReloadConfig,
 
     UpdateLauncherStyle(LauncherStyle),
+    SetBigPictureMode(BigPictureMode),
+
+    SetLanguage(LanguageIdentifier),
+
+    SetGameTextLanguage(Option<GameTextLanguage>),
+    SetGameVoiceLanguage(Option<GameVoiceLanguage>),
+
+    SetTrayIcon(bool),
+    SetKeepInBackground(bool),
+    SetNotifications(bool),
+
+    SetUpdateCheckInterval(u64),
+    SetAutoPredownload(bool),
+    SetPredownloadFrom(u8),
+    SetPredownloadTo(u8),
 
     WineOpen(&'static [&'static str]),
 
+    CreateShortcut(ShortcutKind),
+
+    /// Create game shortcut for the current session and each of the game sessions
+    CreateGameShortcuts,
+
+    AddSteamShortcut,
+
     Toast {
         title: String,
         description: Option<String>
@@ -64,7 +110,7 @@ pub enum GeneralAppMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for GeneralApp {
-    type Init = ();
+    type Init = (adw::PreferencesWindow, ReadyFlag);
     type Input = GeneralAppMsg;
     type Output = PreferencesAppMsg;
 
@@ -137,6 +183,32 @@ impl SimpleAsyncComponent for GeneralApp {
                 }
             },
 
+            add = &adw::PreferencesGroup {
+                adw::ComboRow {
+                    set_title: &tr("big-picture"),
+                    set_subtitle: &tr("big-picture-description"),
+
+                    set_model: Some(&gtk::StringList::new(&BigPictureMode::LIST.iter()
+                        .map(|mode| tr(mode.locale_key()))
+                        .collect::<Vec<_>>()
+                        .iter()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>())),
+
+                    set_selected: BigPictureMode::LIST.iter()
+                        .position(|mode| mode == &settings.launcher.big_picture)
+                        .unwrap_or(0) as u32,
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            if let Some(mode) = BigPictureMode::LIST.get(row.selected() as usize) {
+                                sender.input(GeneralAppMsg::SetBigPictureMode(*mode));
+                            }
+                        }
+                    }
+                }
+            },
+
             add = &adw::PreferencesGroup {
                 #[watch]
                 set_visible: model.style == LauncherStyle::Classic,
@@ -166,26 +238,30 @@ impl SimpleAsyncComponent for GeneralApp {
 
                 adw::ComboRow {
                     set_title: &tr("launcher-language"),
-                    set_subtitle: &tr("launcher-language-description"),
 
                     set_model: Some(&gtk::StringList::new(&model.languages.iter().map(|lang| lang.as_str()).collect::<Vec<&str>>())),
 
                     set_selected: {
-                        let selected = crate::i18n::get_lang().language;
+                        let selected = crate::i18n::get_lang();
 
-                        SUPPORTED_LANGUAGES.iter()
-                            .position(|lang| lang.language == selected)
+                        supported_languages().iter()
+                            .position(|lang| lang == &selected)
                             .unwrap_or(0) as u32
                     },
 
-                    connect_selected_notify => |row| {
-                        if is_ready() {
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
                             if let Ok(mut config) = Config::get() {
-                                config.launcher.language = crate::i18n::format_lang(SUPPORTED_LANGUAGES
+                                let lang = supported_languages()
                                     .get(row.selected() as usize)
-                                    .unwrap_or(&SUPPORTED_LANGUAGES[0]));
-    
+                                    .cloned()
+                                    .unwrap_or(FALLBACK_LANGUAGE);
+
+                                config.launcher.language = crate::i18n::format_lang(&lang);
+
                                 Config::update(config);
+
+                                sender.input(GeneralAppMsg::SetLanguage(lang));
                             }
                         }
                     }
@@ -199,13 +275,13 @@ impl SimpleAsyncComponent for GeneralApp {
                         &tr("china")
                     ])),
 
-                    set_selected: match CONFIG.launcher.edition {
+                    set_selected: match config.launcher.edition {
                         GameEdition::Global => 0,
                         GameEdition::China => 1
                     },
 
-                    connect_selected_notify[sender] => move |row| {
-                        if is_ready() {
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
                             #[allow(unused_must_use)]
                             if let Ok(mut config) = Config::get() {
                                 config.launcher.edition = match row.selected() {
@@ -237,6 +313,65 @@ impl SimpleAsyncComponent for GeneralApp {
                 }
             },
 
+            add = &adw::PreferencesGroup {
+                set_title: &tr("game-language"),
+                set_description: Some(&tr("game-language-description")),
+
+                adw::ComboRow {
+                    set_title: &tr("game-text-language"),
+
+                    set_model: Some(&gtk::StringList::new(&std::iter::once(tr("game-language-keep"))
+                        .chain(GameTextLanguage::LIST.iter().map(|lang| tr(&lang.locale_key())))
+                        .collect::<Vec<_>>()
+                        .iter()
+                        .map(|lang| lang.as_str())
+                        .collect::<Vec<_>>())),
+
+                    set_selected: settings.game.text_language
+                        .and_then(|selected| GameTextLanguage::LIST.iter().position(|lang| *lang == selected))
+                        .map(|i| i as u32 + 1)
+                        .unwrap_or(0),
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            let lang = (row.selected() as usize)
+                                .checked_sub(1)
+                                .and_then(|i| GameTextLanguage::LIST.get(i))
+                                .copied();
+
+                            sender.input(GeneralAppMsg::SetGameTextLanguage(lang));
+                        }
+                    }
+                },
+
+                adw::ComboRow {
+                    set_title: &tr("game-voice-language"),
+
+                    set_model: Some(&gtk::StringList::new(&std::iter::once(tr("game-language-keep"))
+                        .chain(GameVoiceLanguage::LIST.iter().map(|lang| tr(&lang.locale_key())))
+                        .collect::<Vec<_>>()
+                        .iter()
+                        .map(|lang| lang.as_str())
+                        .collect::<Vec<_>>())),
+
+                    set_selected: settings.game.voice_language
+                        .and_then(|selected| GameVoiceLanguage::LIST.iter().position(|lang| *lang == selected))
+                        .map(|i| i as u32 + 1)
+                        .unwrap_or(0),
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            let lang = (row.selected() as usize)
+                                .checked_sub(1)
+                                .and_then(|i| GameVoiceLanguage::LIST.get(i))
+                                .copied();
+
+                            sender.input(GeneralAppMsg::SetGameVoiceLanguage(lang));
+                        }
+                    }
+                }
+            },
+
             add = &adw::PreferencesGroup {
                 set_title: &tr("status"),
 
@@ -319,13 +454,13 @@ impl SimpleAsyncComponent for GeneralApp {
                                 PatchStatus::Outdated { .. } |
                                 PatchStatus::Testing { .. } => &["warning"],
 
-                                PatchStatus::Available { .. } => unsafe {
+                                PatchStatus::Available { .. } => {
                                     let path = match Config::get() {
                                         Ok(config) => config.game.path.for_edition(config.launcher.edition).to_path_buf(),
                                         Err(_) => CONFIG.game.path.for_edition(CONFIG.launcher.edition).to_path_buf(),
                                     };
 
-                                    if let Ok(true) = model.main_patch.as_ref().unwrap_unchecked().is_applied(path) {
+                                    if let Ok(true) = patch.is_applied(path) {
                                         &["success"]
                                     } else {
                                         &["warning"]
@@ -345,13 +480,13 @@ impl SimpleAsyncComponent for GeneralApp {
                                     ("latest", latest.to_string().into())
                                 ]),
                                 PatchStatus::Testing { .. } => tr("patch-testing-tooltip"),
-                                PatchStatus::Available { .. } => unsafe {
+                                PatchStatus::Available { .. } => {
                                     let path = match Config::get() {
                                         Ok(config) => config.game.path.for_edition(config.launcher.edition).to_path_buf(),
                                         Err(_) => CONFIG.game.path.for_edition(CONFIG.launcher.edition).to_path_buf(),
                                     };
 
-                                    if let Ok(true) = model.main_patch.as_ref().unwrap_unchecked().is_applied(path) {
+                                    if let Ok(true) = patch.is_applied(path) {
                                         String::new()
                                     } else {
                                         tr("patch-not-applied-tooltip")
@@ -373,10 +508,10 @@ impl SimpleAsyncComponent for GeneralApp {
                     add_suffix = &gtk::Switch {
                         set_valign: gtk::Align::Center,
 
-                        set_state: CONFIG.patch.root,
+                        set_state: config.patch.root,
 
-                        connect_state_notify => |switch| {
-                            if is_ready() {
+                        connect_state_notify[ready] => move |switch| {
+                            if ready.get() {
                                 if let Ok(mut config) = Config::get() {
                                     config.patch.root = switch.state();
 
@@ -388,6 +523,133 @@ impl SimpleAsyncComponent for GeneralApp {
                 }
             },
 
+            add = &adw::PreferencesGroup {
+                set_title: &tr("background-mode"),
+
+                adw::ActionRow {
+                    set_title: &tr("tray-icon"),
+                    set_subtitle: &tr("tray-icon-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+
+                        set_state: settings.launcher.tray_icon,
+
+                        connect_state_notify[sender, ready] => move |switch| {
+                            if ready.get() {
+                                sender.input(GeneralAppMsg::SetTrayIcon(switch.state()));
+                            }
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("keep-in-background"),
+                    set_subtitle: &tr("keep-in-background-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+
+                        set_state: settings.launcher.keep_in_background,
+
+                        connect_state_notify[sender, ready] => move |switch| {
+                            if ready.get() {
+                                sender.input(GeneralAppMsg::SetKeepInBackground(switch.state()));
+                            }
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("notifications"),
+                    set_subtitle: &tr("notifications-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+
+                        set_state: settings.launcher.notifications,
+
+                        connect_state_notify[sender, ready] => move |switch| {
+                            if ready.get() {
+                                sender.input(GeneralAppMsg::SetNotifications(switch.state()));
+                            }
+                        }
+                    }
+                }
+            },
+
+            add = &adw::PreferencesGroup {
+                set_title: &tr("update-checks"),
+                set_description: Some(&tr("update-checks-description")),
+
+                adw::ComboRow {
+                    set_title: &tr("update-check-interval"),
+
+                    set_model: Some(&gtk::StringList::new(&CHECK_INTERVALS.iter()
+                        .map(|interval| interval_name(*interval))
+                        .collect::<Vec<_>>()
+                        .iter()
+                        .map(|interval| interval.as_str())
+                        .collect::<Vec<_>>())),
+
+                    set_selected: CHECK_INTERVALS.iter()
+                        .position(|interval| *interval == settings.updates.check_interval)
+                        .unwrap_or(0) as u32,
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            if let Some(interval) = CHECK_INTERVALS.get(row.selected() as usize) {
+                                sender.input(GeneralAppMsg::SetUpdateCheckInterval(*interval));
+                            }
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("auto-predownload"),
+                    set_subtitle: &tr("auto-predownload-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+
+                        set_state: settings.updates.auto_predownload,
+
+                        connect_state_notify[sender, ready] => move |switch| {
+                            if ready.get() {
+                                sender.input(GeneralAppMsg::SetAutoPredownload(switch.state()));
+                            }
+                        }
+                    }
+                },
+
+                adw::ComboRow {
+                    set_title: &tr("predownload-from"),
+
+                    set_model: Some(&gtk::StringList::new(&HOURS)),
+                    set_selected: settings.updates.predownload_from as u32,
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            sender.input(GeneralAppMsg::SetPredownloadFrom(row.selected() as u8));
+                        }
+                    }
+                },
+
+                adw::ComboRow {
+                    set_title: &tr("predownload-to"),
+                    set_subtitle: &tr("predownload-to-description"),
+
+                    set_model: Some(&gtk::StringList::new(&HOURS)),
+                    set_selected: settings.updates.predownload_to as u32,
+
+                    connect_selected_notify[sender, ready] => move |row| {
+                        if ready.get() {
+                            sender.input(GeneralAppMsg::SetPredownloadTo(row.selected() as u8));
+                        }
+                    }
+                }
+            },
+
             add = &adw::PreferencesGroup {
                 set_title: &tr("options"),
 
@@ -404,6 +666,19 @@ impl SimpleAsyncComponent for GeneralApp {
                     connect_activated => GeneralAppMsg::OpenComponentsPage
                 },
 
+                adw::ActionRow {
+                    set_title: &tr("config-backups"),
+                    set_subtitle: &tr("config-backups-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => GeneralAppMsg::OpenBackupsPage
+                },
+
                 adw::ExpanderRow {
                     set_title: &tr("wine-tools"),
 
@@ -461,38 +736,95 @@ impl SimpleAsyncComponent for GeneralApp {
                         connect_activated => GeneralAppMsg::WineOpen(&["start", "winedbg"])
                     }
                 }
+
+                adw::ExpanderRow {
+                    set_title: &tr("shortcuts"),
+                    set_subtitle: &tr("shortcuts-description"),
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr("launcher-shortcut"),
+                        set_subtitle: &tr("launcher-shortcut-description"),
+
+                        set_activatable: true,
+
+                        connect_activated => GeneralAppMsg::CreateShortcut(ShortcutKind::Launcher)
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr("game-shortcut"),
+                        set_subtitle: &tr("game-shortcut-description"),
+
+                        set_activatable: true,
+
+                        connect_activated => GeneralAppMsg::CreateGameShortcuts
+                    },
+
+                    add_row = &adw::ActionRow {
+                        set_title: &tr("steam-shortcut"),
+                        set_subtitle: &tr("steam-shortcut-description"),
+
+                        set_activatable: true,
+
+                        connect_activated => GeneralAppMsg::AddSteamShortcut
+                    }
+                }
             }
         },
 
         #[local_ref]
-        components_page -> gtk::Box {}
+        components_page -> gtk::Box {},
+
+        #[local_ref]
+        backups_page -> gtk::Box {}
     }
 
     async fn init(
-        _init: Self::Init,
+        init: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing general settings");
 
+        let (window, ready) = init;
+
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+        let settings = Settings::get().unwrap_or_default();
+
         let model = Self {
             migrate_installation: MigrateInstallationApp::builder()
+                .transient_for(&window)
                 .launch(())
                 .detach(),
 
+            window,
+
             components_page: ComponentsPage::builder()
+                .launch(ready.clone())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            backups_page: BackupsPage::builder()
                 .launch(())
                 .forward(sender.input_sender(), std::convert::identity),
 
             game_diff: None,
             main_patch: None,
 
-            style: CONFIG.launcher.style,
+            style: config.launcher.style,
 
-            languages: SUPPORTED_LANGUAGES.iter().map(|lang| tr(format_lang(lang).as_str())).collect()
+            languages: supported_languages().iter().map(|lang| {
+                let key = format_lang(lang);
+
+                // User locales may have no name message, so use their own one or the language tag
+                if has_message(&get_lang(), &key) {
+                    tr(&key)
+                } else {
+                    overrides::lookup(lang, &key, None).unwrap_or_else(|| lang.to_string())
+                }
+            }).collect()
         };
 
         let components_page = model.components_page.widget();
+        let backups_page = model.backups_page.widget();
 
         let widgets = view_output!();
 
@@ -523,26 +855,89 @@ impl SimpleAsyncComponent for GeneralApp {
                     .unwrap();
             }
 
-            GeneralAppMsg::OpenMigrateInstallation => unsafe {
-                if let Some(window) = crate::ui::main::PREFERENCES_WINDOW.as_ref() {
-                    self.migrate_installation.widget().set_transient_for(Some(window.widget()));
+            GeneralAppMsg::OpenMigrateInstallation => {
+                self.migrate_installation.widget().show();
+            }
+
+            GeneralAppMsg::OpenMainPage => {
+                self.window.close_subpage();
+            }
+
+            GeneralAppMsg::OpenComponentsPage => {
+                self.window.present_subpage(self.components_page.widget());
+            }
+
+            GeneralAppMsg::OpenBackupsPage => {
+                self.backups_page.sender()
+                    .send(BackupsPageMsg::Refresh)
+                    .unwrap();
+
+                self.window.present_subpage(self.backups_page.widget());
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::UpdateLauncherState => {
+                sender.output(Self::Output::UpdateLauncherState);
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::ReloadConfig => {
+                sender.output(Self::Output::ReloadConfig);
+            }
+
+            #[allow(unused_must_use)]
+            GeneralAppMsg::SetLanguage(lang) => {
+                sender.output(Self::Output::SetLanguage(lang));
+            }
+
+            GeneralAppMsg::SetGameTextLanguage(lang) => {
+                self.update_game_settings(sender, |settings| settings.game.text_language = lang);
+            }
+
+            GeneralAppMsg::SetGameVoiceLanguage(lang) => {
+                // Game downloads missing voice pack itself on the next launch
+                if let (Some(lang), Ok(config)) = (lang, Config::get()) {
+                    let game_path = config.game.path.for_edition(config.launcher.edition);
+
+                    if game_path.exists() && !lang.is_installed(game_path, config.launcher.edition) {
+                        sender.input(GeneralAppMsg::Toast {
+                            title: tr_args("game-voice-pack-missing", [
+                                ("language", tr(&lang.locale_key()).into())
+                            ]),
+                            description: None
+                        });
+                    }
                 }
 
-                self.migrate_installation.widget().show();
+                self.update_game_settings(sender, |settings| settings.game.voice_language = lang);
+            }
+
+            GeneralAppMsg::SetTrayIcon(enabled) => {
+                self.update_settings(&sender, |settings| settings.launcher.tray_icon = enabled);
+            }
+
+            GeneralAppMsg::SetKeepInBackground(enabled) => {
+                self.update_settings(&sender, |settings| settings.launcher.keep_in_background = enabled);
+            }
+
+            GeneralAppMsg::SetNotifications(enabled) => {
+                self.update_settings(&sender, |settings| settings.launcher.notifications = enabled);
+            }
+
+            GeneralAppMsg::SetUpdateCheckInterval(interval) => {
+                self.update_settings(&sender, |settings| settings.updates.check_interval = interval);
+            }
+
+            GeneralAppMsg::SetAutoPredownload(enabled) => {
+                self.update_settings(&sender, |settings| settings.updates.auto_predownload = enabled);
             }
 
-            GeneralAppMsg::OpenMainPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .close_subpage();
+            GeneralAppMsg::SetPredownloadFrom(hour) => {
+                self.update_settings(&sender, |settings| settings.updates.predownload_from = hour);
             }
 
-            GeneralAppMsg::OpenComponentsPage => unsafe {
-                PREFERENCES_WINDOW.as_ref()
-                    .unwrap_unchecked()
-                    .widget()
-                    .present_subpage(self.components_page.widget());
+            GeneralAppMsg::SetPredownloadTo(hour) => {
+                self.update_settings(&sender, |settings| settings.updates.predownload_to = hour);
             }
 
             #[allow(unused_must_use)]
@@ -571,6 +966,13 @@ impl SimpleAsyncComponent for GeneralApp {
                 sender.output(Self::Output::SetLauncherStyle(style));
             }
 
+            #[allow(unused_must_use)]
+            GeneralAppMsg::SetBigPictureMode(mode) => {
+                if self.update_settings(&sender, |settings| settings.launcher.big_picture = mode).is_some() {
+                    sender.output(Self::Output::SetBigPicture(mode.is_enabled()));
+                }
+            }
+
             GeneralAppMsg::WineOpen(executable) => {
                 let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
 
@@ -595,6 +997,76 @@ impl SimpleAsyncComponent for GeneralApp {
                 }
             }
 
+            GeneralAppMsg::CreateShortcut(kind) => {
+                match crate::shortcuts::create_desktop_entry(&kind) {
+                    Ok(path) => sender.input(GeneralAppMsg::Toast {
+                        title: tr("shortcut-created"),
+                        description: Some(path.to_string_lossy().to_string())
+                    }),
+
+                    Err(err) => {
+                        tracing::error!("Failed to create {kind:?} shortcut: {err}");
+
+                        sender.input(GeneralAppMsg::Toast {
+                            title: tr("shortcut-creation-failed"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
+            GeneralAppMsg::CreateGameShortcuts => {
+                let result = crate::shortcuts::game_shortcuts().iter()
+                    .map(|kind| crate::shortcuts::create_desktop_entry(kind).map(|path| path.to_string_lossy().to_string()))
+                    .collect::<anyhow::Result<Vec<_>>>();
+
+                match result {
+                    Ok(paths) => sender.input(GeneralAppMsg::Toast {
+                        title: tr("shortcut-created"),
+                        description: Some(paths.join("\n"))
+                    }),
+
+                    Err(err) => {
+                        tracing::error!("Failed to create game shortcuts: {err}");
+
+                        sender.input(GeneralAppMsg::Toast {
+                            title: tr("shortcut-creation-failed"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
+            GeneralAppMsg::AddSteamShortcut => {
+                // Steam overwrites shortcuts file when it's closed
+                if crate::shortcuts::steam::is_running() {
+                    sender.input(GeneralAppMsg::Toast {
+                        title: tr("shortcut-creation-failed"),
+                        description: Some(tr("steam-is-running"))
+                    });
+
+                    return;
+                }
+
+                match crate::shortcuts::add_to_steam() {
+                    Ok(users) => sender.input(GeneralAppMsg::Toast {
+                        title: tr("shortcut-created"),
+                        description: Some(tr_args("steam-shortcut-added", [
+                            ("users", users.to_string().into())
+                        ]))
+                    }),
+
+                    Err(err) => {
+                        tracing::error!("Failed to add Steam shortcut: {err}");
+
+                        sender.input(GeneralAppMsg::Toast {
+                            title: tr("shortcut-creation-failed"),
+                            description: Some(err.to_string())
+                        });
+                    }
+                }
+            }
+
             #[allow(unused_must_use)]
             GeneralAppMsg::Toast { title, description } => {
                 sender.output(Self::Output::Toast { title, description });
@@ -602,3 +1074,63 @@ impl SimpleAsyncComponent for GeneralApp {
         }
     }
 }
+
+impl GeneralApp {
+    /// Update launcher settings file
+    fn update_settings(&self, sender: &AsyncComponentSender<Self>, update: impl FnOnce(&mut Settings)) -> Option<Settings> {
+        let result = Settings::get().and_then(|mut settings| {
+            update(&mut settings);
+
+            Settings::update(&settings)?;
+
+            Ok(settings)
+        });
+
+        match result {
+            Ok(settings) => Some(settings),
+
+            Err(err) => {
+                tracing::error!("Failed to update launcher settings: {err}");
+
+                sender.input(GeneralAppMsg::Toast {
+                    title: tr("settings-update-failed"),
+                    description: Some(err.to_string())
+                });
+
+                None
+            }
+        }
+    }
+
+    /// Update launcher settings and apply new game languages
+    fn update_game_settings(&self, sender: AsyncComponentSender<Self>, update: impl FnOnce(&mut Settings)) {
+        if let Some(settings) = self.update_settings(&sender, update) {
+            // Wine can take a while to start
+            std::thread::spawn(move || {
+                if let Err(err) = crate::game_language::apply(&settings.game) {
+                    tracing::error!("Failed to apply game language: {err}");
+
+                    sender.input(GeneralAppMsg::Toast {
+                        title: tr("game-language-apply-failed"),
+                        description: Some(err.to_string())
+                    });
+                }
+            });
+        }
+    }
+}
+
+/// Get name of the updates check interval
+fn interval_name(minutes: u64) -> String {
+    match minutes {
+        0 => tr("update-check-disabled"),
+
+        minutes if minutes < 60 => tr_args("update-check-minutes", [
+            ("minutes", minutes.to_string().into())
+        ]),
+
+        minutes => tr_args("update-check-hours", [
+            ("hours", (minutes / 60).to_string().into())
+        ])
+    }
+}
@@ -10,14 +10,17 @@ use anime_launcher_sdk::config::ConfigExt;
 use anime_launcher_sdk::star_rail::config::Config;
 use anime_launcher_sdk::star_rail::config::schema::launcher::LauncherStyle;
 
+use unic_langid::LanguageIdentifier;
+
 use crate::i18n::tr;
 
+use super::ReadyFlag;
 use super::general::*;
 use super::enhancements::*;
 
-pub static mut PREFERENCES_WINDOW: Option<adw::PreferencesWindow> = None;
-
 pub struct PreferencesApp {
+    window: adw::PreferencesWindow,
+
     general: AsyncController<GeneralApp>,
     enhancements: AsyncController<EnhancementsApp>
 }
@@ -33,9 +36,14 @@ pub enum PreferencesAppMsg {
     SetMainPatch(Option<MainPatch>),
 
     SetLauncherStyle(LauncherStyle),
+    SetBigPicture(bool),
+    SetLanguage(LanguageIdentifier),
 
     UpdateLauncherState,
 
+    /// Reload config and launcher settings files the same way as after external edits
+    ReloadConfig,
+
     Toast {
         title: String,
         description: Option<String>
@@ -44,7 +52,7 @@ pub enum PreferencesAppMsg {
 
 #[relm4::component(async, pub)]
 impl SimpleAsyncComponent for PreferencesApp {
-    type Init = gtk::Window;
+    type Init = (gtk::Window, ReadyFlag);
     type Input = PreferencesAppMsg;
     type Output = crate::ui::main::AppMsg;
 
@@ -61,7 +69,7 @@ impl SimpleAsyncComponent for PreferencesApp {
             add = model.enhancements.widget(),
 
             connect_close_request[sender] => move |_| {
-                if let Err(err) = Config::flush() {
+                if let Err(err) = crate::config_backups::flush() {
                     sender.input(PreferencesAppMsg::Toast {
                         title: tr("config-update-error"),
                         description: Some(err.to_string())
@@ -74,19 +82,23 @@ impl SimpleAsyncComponent for PreferencesApp {
     }
 
     async fn init(
-        parent: Self::Init,
+        init: Self::Init,
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
         tracing::info!("Initializing preferences window");
 
+        let (parent, ready) = init;
+
         let model = Self {
+            window: root.clone(),
+
             general: GeneralApp::builder()
-                .launch(())
+                .launch((root.clone(), ready.clone()))
                 .forward(sender.input_sender(), std::convert::identity),
 
             enhancements: EnhancementsApp::builder()
-                .launch(())
+                .launch((root.clone(), ready))
                 .forward(sender.input_sender(), std::convert::identity)
         };
 
@@ -94,13 +106,7 @@ impl SimpleAsyncComponent for PreferencesApp {
 
         widgets.preferences_window.set_transient_for(Some(&parent));
 
-        unsafe {
-            PREFERENCES_WINDOW = Some(widgets.preferences_window.clone());
-        }
-
         #[allow(unused_must_use)] {
-            model.enhancements.sender().send(EnhancementsAppMsg::SetGamescopeParent(widgets.preferences_window.clone()));
-
             model.general.sender().send(GeneralAppMsg::UpdateDownloadedWine);
             model.general.sender().send(GeneralAppMsg::UpdateDownloadedDxvk);
         }
@@ -127,6 +133,16 @@ impl SimpleAsyncComponent for PreferencesApp {
                 sender.output(Self::Output::SetLauncherStyle(style));
             }
 
+            #[allow(unused_must_use)]
+            PreferencesAppMsg::SetBigPicture(enabled) => {
+                sender.output(Self::Output::SetBigPicture(enabled));
+            }
+
+            #[allow(unused_must_use)]
+            PreferencesAppMsg::SetLanguage(lang) => {
+                sender.output(Self::Output::SetLanguage(lang));
+            }
+
             #[allow(unused_must_use)]
             PreferencesAppMsg::UpdateLauncherState => {
                 sender.output(Self::Output::UpdateLauncherState {
@@ -136,7 +152,13 @@ impl SimpleAsyncComponent for PreferencesApp {
                 });
             }
 
-            PreferencesAppMsg::Toast { title, description } => unsafe {
+            #[allow(unused_must_use)]
+            PreferencesAppMsg::ReloadConfig => {
+                sender.output(Self::Output::ReloadConfig);
+                sender.output(Self::Output::ReloadSettings);
+            }
+
+            PreferencesAppMsg::Toast { title, description } => {
                 let toast = adw::Toast::new(&title);
 
                 toast.set_timeout(4);
@@ -144,7 +166,7 @@ impl SimpleAsyncComponent for PreferencesApp {
                 if let Some(description) = description {
                     toast.set_button_label(Some(&tr("details")));
 
-                    let dialog = adw::MessageDialog::new(PREFERENCES_WINDOW.as_ref(), Some(&title), Some(&description));
+                    let dialog = adw::MessageDialog::new(Some(&self.window), Some(&title), Some(&description));
 
                     dialog.add_response("close", &tr("close"));
                     dialog.add_response("save", &tr("save"));
@@ -162,7 +184,7 @@ impl SimpleAsyncComponent for PreferencesApp {
                     });
                 }
 
-                PREFERENCES_WINDOW.as_ref().unwrap_unchecked().add_toast(toast);
+                self.window.add_toast(toast);
             }
         }
     }
@@ -1,4 +1,26 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 pub mod main;
 pub mod general;
 pub mod enhancements;
 pub mod gamescope;
+
+/// Shared between preferences components to tell them that the launcher is loaded
+///
+/// Widgets handlers are called when their initial values are set,
+/// so they shouldn't update the config until it's ready
+#[derive(Debug, Clone, Default)]
+pub struct ReadyFlag(Arc<AtomicBool>);
+
+impl ReadyFlag {
+    #[inline]
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn set(&self, ready: bool) {
+        self.0.store(ready, Ordering::Relaxed);
+    }
+}
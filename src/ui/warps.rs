@@ -0,0 +1,518 @@
+use relm4::{
+    prelude::*,
+    actions::*
+};
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib::clone;
+use gtk::gio;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::Config;
+
+use crate::warps::history::{self, WarpsArchive};
+use crate::warps::stats::BannerStats;
+use crate::warps::export::{self, ExportFormat};
+
+use crate::i18n::*;
+
+relm4::new_action_group!(WarpsActionGroup, "warps");
+
+relm4::new_stateless_action!(ExportCsv, WarpsActionGroup, "export_csv");
+relm4::new_stateless_action!(ExportSrgf, WarpsActionGroup, "export_srgf");
+relm4::new_stateless_action!(ExportUigf, WarpsActionGroup, "export_uigf");
+relm4::new_stateless_action!(Import, WarpsActionGroup, "import");
+
+pub struct WarpsApp {
+    window: adw::Window,
+    toast_overlay: adw::ToastOverlay,
+
+    archive: Option<WarpsArchive>,
+    status: Option<String>,
+
+    page: adw::PreferencesPage,
+    groups: Vec<adw::PreferencesGroup>
+}
+
+#[derive(Debug, Clone)]
+pub enum WarpsAppMsg {
+    /// Read the recently updated archive
+    Load,
+
+    Show,
+
+    /// Fetch new warps using the URL from the game's web cache
+    Fetch,
+
+    /// Save current archive to the file chosen by user
+    Export(ExportFormat),
+
+    /// Merge history from the file chosen by user
+    Import,
+
+    SetStatus(Option<String>),
+    SetArchive(WarpsArchive),
+
+    Toast {
+        title: String,
+        description: Option<String>
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for WarpsApp {
+    type Init = ();
+    type Input = WarpsAppMsg;
+    type Output = ();
+
+    view! {
+        window = adw::Window {
+            set_title: Some(&tr("warps")),
+            set_default_size: (640, 640),
+
+            set_modal: true,
+            set_hide_on_close: true,
+
+            #[local_ref]
+            toast_overlay -> adw::ToastOverlay {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    adw::HeaderBar {
+                        add_css_class: "flat",
+
+                        #[wrap(Some)]
+                        set_title_widget = &adw::WindowTitle {
+                            set_title: &tr("warps"),
+
+                            #[watch]
+                            set_subtitle: &match &model.archive {
+                                Some(archive) => tr_args("warps-uid", [
+                                    ("uid", archive.uid.clone().into())
+                                ]),
+
+                                None => String::new()
+                            }
+                        },
+
+                        pack_start = &gtk::Button {
+                            set_icon_name: "view-refresh-symbolic",
+                            set_tooltip_text: Some(&tr("warps-fetch")),
+
+                            #[watch]
+                            set_sensitive: model.status.is_none(),
+
+                            connect_clicked => WarpsAppMsg::Fetch
+                        },
+
+                        pack_end = &gtk::MenuButton {
+                            set_icon_name: "document-save-symbolic",
+                            set_tooltip_text: Some(&tr("warps-export-import")),
+                            set_menu_model: Some(&menu),
+
+                            #[watch]
+                            set_sensitive: model.status.is_none()
+                        },
+
+                        pack_end = &gtk::Spinner {
+                            #[watch]
+                            set_spinning: model.status.is_some(),
+
+                            #[watch]
+                            set_tooltip_text: model.status.as_deref()
+                        }
+                    },
+
+                    gtk::Label {
+                        add_css_class: "dim-label",
+                        set_margin_top: 8,
+
+                        #[watch]
+                        set_visible: model.status.is_some(),
+
+                        #[watch]
+                        set_label: model.status.as_deref().unwrap_or_default()
+                    },
+
+                    adw::StatusPage {
+                        set_icon_name: Some("view-list-symbolic"),
+                        set_title: &tr("warps-empty"),
+                        set_description: Some(&tr("warps-empty-description")),
+                        set_vexpand: true,
+
+                        #[watch]
+                        set_visible: model.archive.is_none()
+                    },
+
+                    #[local_ref]
+                    page -> adw::PreferencesPage {
+                        set_vexpand: true,
+
+                        #[watch]
+                        set_visible: model.archive.is_some()
+                    }
+                }
+            }
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: &Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        tracing::info!("Initializing warps window");
+
+        let model = Self {
+            window: root.clone(),
+            toast_overlay: adw::ToastOverlay::new(),
+
+            archive: None,
+            status: None,
+
+            page: adw::PreferencesPage::new(),
+            groups: Vec::new()
+        };
+
+        let toast_overlay = &model.toast_overlay;
+        let page = &model.page;
+
+        let menu = gio::Menu::new();
+        let section = gio::Menu::new();
+
+        section.append(Some(&tr("warps-export-csv")), Some(&ExportCsv::action_name()));
+        section.append(Some(&tr("warps-export-srgf")), Some(&ExportSrgf::action_name()));
+        section.append(Some(&tr("warps-export-uigf")), Some(&ExportUigf::action_name()));
+
+        menu.append_section(None, &section);
+
+        let section = gio::Menu::new();
+
+        section.append(Some(&tr("warps-import")), Some(&Import::action_name()));
+
+        menu.append_section(None, &section);
+
+        let widgets = view_output!();
+
+        let mut group = RelmActionGroup::<WarpsActionGroup>::new();
+
+        group.add_action::<ExportCsv>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(WarpsAppMsg::Export(ExportFormat::Csv));
+        })));
+
+        group.add_action::<ExportSrgf>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(WarpsAppMsg::Export(ExportFormat::Srgf));
+        })));
+
+        group.add_action::<ExportUigf>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(WarpsAppMsg::Export(ExportFormat::Uigf));
+        })));
+
+        group.add_action::<Import>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(WarpsAppMsg::Import);
+        })));
+
+        widgets.window.insert_action_group("warps", Some(&group.into_action_group()));
+
+        sender.input(WarpsAppMsg::Load);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        tracing::debug!("Called warps window event: {:?}", msg);
+
+        match msg {
+            WarpsAppMsg::Load => match WarpsArchive::list() {
+                Ok(archives) => {
+                    if let Some(archive) = archives.into_iter().next() {
+                        self.set_archive(archive);
+                    }
+                }
+
+                Err(err) => {
+                    tracing::error!("Failed to load warps history: {err}");
+
+                    self.toast(tr("warps-load-failed"), Some(err.to_string()));
+                }
+            }
+
+            WarpsAppMsg::Show => {
+                self.window.present();
+            }
+
+            WarpsAppMsg::Fetch => {
+                self.status = Some(tr("warps-searching-url"));
+
+                std::thread::spawn(move || {
+                    let config = match Config::get() {
+                        Ok(config) => config,
+
+                        Err(err) => {
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("warps-fetch-failed"),
+                                description: Some(err.to_string())
+                            });
+
+                            sender.input(WarpsAppMsg::SetStatus(None));
+
+                            return;
+                        }
+                    };
+
+                    let url = match crate::warps::url::find(config.game.path.for_edition(config.launcher.edition), config.launcher.edition) {
+                        Ok(Some(url)) => url,
+
+                        Ok(None) => {
+                            tracing::error!("Couldn't find warps URL: no url found");
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("wish-url-search-failed"),
+                                description: None
+                            });
+
+                            sender.input(WarpsAppMsg::SetStatus(None));
+
+                            return;
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Couldn't find warps URL: {err}");
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("wish-url-search-failed"),
+                                description: Some(err.to_string())
+                            });
+
+                            sender.input(WarpsAppMsg::SetStatus(None));
+
+                            return;
+                        }
+                    };
+
+                    let result = history::update(&url.url, config.launcher.edition, |gacha_type, count| {
+                        sender.input(WarpsAppMsg::SetStatus(Some(tr_args("warps-fetching", [
+                            ("banner", tr(gacha_type.locale_key()).into()),
+                            ("count", count.to_string().into())
+                        ]))));
+                    });
+
+                    match result {
+                        Ok(Some((archive, added))) => {
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr_args("warps-fetched", [
+                                    ("count", added.to_string().into())
+                                ]),
+                                description: None
+                            });
+
+                            sender.input(WarpsAppMsg::SetArchive(archive));
+                        }
+
+                        Ok(None) => {
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("warps-empty"),
+                                description: None
+                            });
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to fetch warps history: {err}");
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("warps-fetch-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
+                    }
+
+                    sender.input(WarpsAppMsg::SetStatus(None));
+                });
+            }
+
+            WarpsAppMsg::Export(format) => {
+                let Some(archive) = self.archive.clone() else {
+                    self.toast(tr("warps-empty"), None);
+
+                    return;
+                };
+
+                std::thread::spawn(move || {
+                    let path = rfd::FileDialog::new()
+                        .set_file_name(&format.file_name(&archive.uid))
+                        .add_filter(&tr(format.locale_key()), &[format.extension()])
+                        .save_file();
+
+                    let Some(path) = path else {
+                        return;
+                    };
+
+                    match export::export_file(&archive, format, &path) {
+                        Ok(()) => {
+                            tracing::info!("Exported warps history to {:?}", path);
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr_args("warps-exported", [
+                                    ("path", path.to_string_lossy().to_string().into())
+                                ]),
+                                description: None
+                            });
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to export warps history: {err}");
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("warps-export-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
+                    }
+                });
+            }
+
+            WarpsAppMsg::Import => {
+                std::thread::spawn(move || {
+                    let path = rfd::FileDialog::new()
+                        .add_filter(&tr("warps-import-files"), &["json", "csv"])
+                        .pick_file();
+
+                    let Some(path) = path else {
+                        return;
+                    };
+
+                    match export::import_file(&path) {
+                        Ok(imported) => {
+                            let added = imported.iter()
+                                .map(|(_, added)| added)
+                                .sum::<usize>();
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr_args("warps-imported", [
+                                    ("count", added.to_string().into())
+                                ]),
+                                description: None
+                            });
+
+                            sender.input(WarpsAppMsg::Load);
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to import warps history: {err}");
+
+                            sender.input(WarpsAppMsg::Toast {
+                                title: tr("warps-import-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
+                    }
+                });
+            }
+
+            WarpsAppMsg::SetStatus(status) => {
+                self.status = status;
+            }
+
+            WarpsAppMsg::SetArchive(archive) => self.set_archive(archive),
+
+            WarpsAppMsg::Toast { title, description } => self.toast(title, description)
+        }
+    }
+}
+
+impl WarpsApp {
+    fn set_archive(&mut self, archive: WarpsArchive) {
+        for group in self.groups.drain(..) {
+            self.page.remove(&group);
+        }
+
+        for stats in BannerStats::all(&archive) {
+            let group = adw::PreferencesGroup::new();
+
+            group.set_title(&tr(stats.gacha_type.locale_key()));
+            group.set_description(Some(&tr_args("warps-total", [
+                ("count", stats.total.to_string().into())
+            ])));
+
+            let rows = [
+                (tr("warps-pity-5"), stats.pity_5.to_string()),
+                (tr("warps-pity-4"), stats.pity_4.to_string()),
+                (tr("warps-four-stars"), stats.four_stars.to_string()),
+                (tr("warps-average-5"), stats.average_5()
+                    .map(|average| format!("{average:.1}"))
+                    .unwrap_or_else(|| String::from("-")))
+            ];
+
+            for (title, value) in rows {
+                let row = adw::ActionRow::new();
+
+                row.set_title(&title);
+                row.add_suffix(&gtk::Label::new(Some(&value)));
+
+                group.add(&row);
+            }
+
+            if !stats.five_stars.is_empty() {
+                let five_stars = adw::ExpanderRow::new();
+
+                five_stars.set_title(&tr("warps-five-stars"));
+                five_stars.set_subtitle(&stats.five_stars.len().to_string());
+
+                for pull in &stats.five_stars {
+                    let row = adw::ActionRow::new();
+
+                    row.set_title(&pull.name);
+                    row.set_subtitle(&pull.time);
+
+                    row.add_suffix(&gtk::Label::new(Some(&tr_args("warps-pulls", [
+                        ("count", pull.pulls.to_string().into())
+                    ]))));
+
+                    five_stars.add_row(&row);
+                }
+
+                group.add(&five_stars);
+            }
+
+            self.page.add(&group);
+            self.groups.push(group);
+        }
+
+        self.archive = Some(archive);
+    }
+
+    fn toast<T: AsRef<str>>(&self, title: T, description: Option<T>) {
+        let toast = adw::Toast::new(title.as_ref());
+
+        toast.set_timeout(4);
+
+        if let Some(description) = description {
+            toast.set_button_label(Some(&tr("details")));
+
+            let dialog = adw::MessageDialog::new(
+                Some(&self.window),
+                Some(title.as_ref()),
+                Some(description.as_ref())
+            );
+
+            dialog.add_response("close", &tr("close"));
+            dialog.add_response("save", &tr("save"));
+
+            dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+            dialog.connect_response(Some("save"), |_, _| {
+                if let Err(err) = open::that(crate::DEBUG_FILE.as_os_str()) {
+                    tracing::error!("Failed to open debug file: {err}");
+                }
+            });
+
+            toast.connect_button_clicked(move |_| {
+                dialog.present();
+            });
+        }
+
+        self.toast_overlay.add_toast(toast);
+    }
+}
@@ -0,0 +1,351 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use gtk::{gio, glib};
+use gtk::prelude::*;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::Config;
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::anime_game_core::star_rail::prelude::*;
+
+use crate::settings::UpdatesSettings;
+
+/// Updates check intervals in minutes available in the settings
+pub const CHECK_INTERVALS: &[u64] = &[0, 30, 60, 180, 360, 720, 1440];
+
+/// How long the `check-updates` command waits for the network
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(120);
+
+lazy_static::lazy_static! {
+    /// Path to the lock file taken while the game update is installed.
+    /// Standard is `$HOME/.local/share/anime-game-launcher/.installing`
+    pub static ref INSTALL_LOCK_FILE: PathBuf = crate::LAUNCHER_FOLDER.join(".installing");
+}
+
+/// Lock which prevents the launcher and the `check-updates` command
+/// from installing the update at the same time. Released when dropped
+#[derive(Debug)]
+pub struct InstallLock;
+
+impl InstallLock {
+    /// Take the lock. Fails if the update is being installed by another process
+    pub fn acquire() -> anyhow::Result<Self> {
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(INSTALL_LOCK_FILE.as_path()) {
+            Ok(mut file) => {
+                write!(file, "{}", std::process::id())?;
+
+                Ok(Self)
+            }
+
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                let pid = std::fs::read_to_string(INSTALL_LOCK_FILE.as_path())?;
+
+                if let Ok(pid) = pid.trim().parse::<u32>() {
+                    if PathBuf::from(format!("/proc/{pid}")).exists() {
+                        anyhow::bail!("Update is being installed by another launcher process ({pid})");
+                    }
+                }
+
+                // Lock is left by the crashed process
+                tracing::warn!("Removing stale install lock of process {}", pid.trim());
+
+                std::fs::remove_file(INSTALL_LOCK_FILE.as_path())?;
+
+                Self::acquire()
+            }
+
+            Err(err) => Err(err.into())
+        }
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(INSTALL_LOCK_FILE.as_path()) {
+            tracing::error!("Failed to remove install lock: {err}");
+        }
+    }
+}
+
+/// Latest game and main patch versions
+#[derive(Debug)]
+pub struct UpdatesStatus {
+    pub diff: Option<VersionDiff>,
+    pub patch: Option<MainPatch>
+}
+
+/// Sync patch folder and get the game and main patch versions
+///
+/// Unlike the launcher start it uses the current config, so it works
+/// for the long running launcher and from the command line
+pub fn check() -> anyhow::Result<UpdatesStatus> {
+    let config = Config::get()?;
+
+    let patch = Patch::new(&config.patch.path, config.launcher.edition);
+
+    match patch.is_sync(&config.patch.servers) {
+        Ok(Some(_)) => (),
+
+        Ok(None) => {
+            for server in &config.patch.servers {
+                match patch.sync(server) {
+                    Ok(_) => break,
+                    Err(err) => tracing::error!("Failed to sync patch folder with remote: {server}: {err}")
+                }
+            }
+        }
+
+        Err(err) => tracing::error!("Failed to compare local patch folder with remote: {err}")
+    }
+
+    let patch = match patch.main_patch() {
+        Ok(patch) => Some(patch),
+
+        Err(err) => {
+            tracing::error!("Failed to fetch main patch info: {err}");
+
+            None
+        }
+    };
+
+    let game = Game::new(config.game.path.for_edition(config.launcher.edition), config.launcher.edition);
+
+    let diff = match game.try_get_diff() {
+        Ok(diff) => Some(diff),
+
+        Err(err) => {
+            tracing::error!("Failed to find game diff: {err}");
+
+            None
+        }
+    };
+
+    if diff.is_none() && patch.is_none() {
+        anyhow::bail!("Failed to get both game and main patch versions");
+    }
+
+    Ok(UpdatesStatus { diff, patch })
+}
+
+/// Check if current local time is within the predownloads time window
+pub fn is_predownload_time(settings: &UpdatesSettings) -> bool {
+    let (start, end) = (settings.predownload_from, settings.predownload_to);
+
+    // Same hours mean any time
+    if start == end {
+        return true;
+    }
+
+    let Ok(now) = gtk::glib::DateTime::now_local() else {
+        return true;
+    };
+
+    let hour = now.hour() as u8;
+
+    // Window can go through midnight, e.g. 22 - 6
+    if start < end {
+        (start..end).contains(&hour)
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+#[inline]
+fn temp_folder() -> PathBuf {
+    Config::get().ok()
+        .and_then(|config| config.launcher.temp)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Check if the predownload is available and not downloaded yet
+pub fn predownload_needed(diff: &VersionDiff) -> bool {
+    let VersionDiff::Predownload { .. } = diff else {
+        return false;
+    };
+
+    match diff.file_name() {
+        Some(file_name) => !temp_folder().join(file_name).exists(),
+        None => false
+    }
+}
+
+/// Download the update to the temp folder
+pub fn predownload(mut diff: VersionDiff, progress: impl Fn(u64, u64) + Clone + Send + 'static) -> anyhow::Result<()> {
+    diff.download_to(temp_folder(), progress)?;
+
+    Ok(())
+}
+
+/// Install the update to the game folder
+///
+/// Fails if the update is being installed by another launcher process
+pub fn install(mut diff: VersionDiff, updater: impl Fn(DiffUpdate) + Clone + Send + 'static) -> anyhow::Result<()> {
+    let _lock = InstallLock::acquire()?;
+
+    let config = Config::get()?;
+
+    if let Some(temp) = config.launcher.temp {
+        diff = diff.with_temp_folder(temp);
+    }
+
+    diff.install_to(config.game.path.for_edition(config.launcher.edition), updater)?;
+
+    Ok(())
+}
+
+/// Wait until the network is available
+///
+/// Returns `false` if it's not available after the timeout
+pub fn wait_network(timeout: Duration) -> bool {
+    let monitor = gio::NetworkMonitor::default();
+    let context = glib::MainContext::default();
+
+    let started = Instant::now();
+
+    while !monitor.is_network_available() {
+        if started.elapsed() >= timeout {
+            return false;
+        }
+
+        // Let the monitor receive network changes
+        while context.iteration(false) {}
+
+        std::thread::sleep(Duration::from_secs(1));
+    }
+
+    true
+}
+
+/// Options of the `check-updates` command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommandOptions {
+    /// Start predownload if it's available
+    pub predownload: bool,
+
+    /// Install the game update if it's available
+    pub install: bool,
+
+    /// Don't download anything on metered connections
+    pub skip_metered: bool,
+
+    /// Wait for the network before checking updates
+    pub wait_network: bool
+}
+
+impl CommandOptions {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+
+        for arg in args {
+            match arg.as_str() {
+                "--predownload"  => options.predownload = true,
+                "--install"      => options.install = true,
+                "--skip-metered" => options.skip_metered = true,
+                "--wait-network" => options.wait_network = true,
+
+                _ => anyhow::bail!("Unknown argument: {arg}")
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Print updates status and download them. Used by the `check-updates` command
+///
+/// Predownload is started if it's requested or enabled in the settings.
+/// Nothing is downloaded if the launcher is running to not to race with it
+pub fn run_command(options: CommandOptions) -> anyhow::Result<()> {
+    if options.wait_network && !wait_network(NETWORK_TIMEOUT) {
+        anyhow::bail!("Network is not available");
+    }
+
+    let status = check()?;
+
+    match &status.diff {
+        Some(VersionDiff::Latest { version, .. }) => println!("Game is up to date ({version})"),
+        Some(VersionDiff::Diff { current, latest, .. }) => println!("Game update available: {current} -> {latest}"),
+        Some(VersionDiff::Predownload { current, latest, .. }) => println!("Game predownload available: {current} -> {latest}"),
+        Some(VersionDiff::Outdated { current, latest, .. }) => println!("Game is too outdated to be updated: {current} -> {latest}"),
+        Some(VersionDiff::NotInstalled { latest, .. }) => println!("Game is not installed. Latest version is {latest}"),
+
+        None => println!("Failed to get game version")
+    }
+
+    match status.patch.as_ref().map(|patch| patch.status()) {
+        Some(PatchStatus::NotAvailable) => println!("Main patch is not available"),
+        Some(PatchStatus::Outdated { current, .. }) => println!("Main patch is outdated ({current})"),
+        Some(PatchStatus::Testing { version, .. }) => println!("Main patch {version} is in testing"),
+        Some(PatchStatus::Available { version, .. }) => println!("Main patch {version} is available"),
+
+        None => println!("Failed to get main patch status")
+    }
+
+    let Some(diff) = status.diff else {
+        return Ok(());
+    };
+
+    let settings = crate::settings::Settings::get()?.updates;
+
+    let predownload = predownload_needed(&diff) && (options.predownload || (settings.auto_predownload && is_predownload_time(&settings)));
+    let install_update = options.install && matches!(diff, VersionDiff::Diff { .. });
+
+    if !predownload && !install_update {
+        return Ok(());
+    }
+
+    if crate::single_instance::is_running().unwrap_or_default() {
+        println!("Launcher is running. Updates should be downloaded there");
+
+        return Ok(());
+    }
+
+    if options.skip_metered && gio::NetworkMonitor::default().is_network_metered() {
+        println!("Network connection is metered. Skipping downloading");
+
+        return Ok(());
+    }
+
+    if install_update {
+        println!("Installing the update");
+
+        install(diff, |state| {
+            match state {
+                DiffUpdate::InstallerUpdate(InstallerUpdate::DownloadingProgress(curr, total)) |
+                DiffUpdate::InstallerUpdate(InstallerUpdate::UnpackingProgress(curr, total)) |
+                DiffUpdate::ApplyingHdiffProgress(curr, total) |
+                DiffUpdate::RemovingOutdatedProgress(curr, total) => print_progress(curr, total),
+
+                DiffUpdate::InstallerUpdate(InstallerUpdate::DownloadingError(err)) => eprintln!("\nDownloading failed: {err}"),
+                DiffUpdate::InstallerUpdate(InstallerUpdate::UnpackingError(err)) => eprintln!("\nUnpacking failed: {err}"),
+
+                _ => ()
+            }
+        })?;
+
+        eprintln!();
+
+        println!("Update installed");
+
+        return Ok(());
+    }
+
+    println!("Predownloading the update");
+
+    predownload(diff, print_progress)?;
+
+    eprintln!();
+
+    println!("Predownload finished");
+
+    Ok(())
+}
+
+fn print_progress(curr: u64, total: u64) {
+    if total > 0 {
+        eprint!("\r{:.2}%", curr as f64 / total as f64 * 100.0);
+    }
+}
@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value as Json};
+
+use super::history::{WarpRecord, WarpsArchive, is_valid_uid};
+
+/// Name of the launcher written to the exported files
+const EXPORT_APP: &str = "the-honkers-railway-launcher";
+
+/// Columns of the CSV files. Same as the `WarpRecord` fields
+const CSV_COLUMNS: &[&str] = &[
+    "id", "uid", "gacha_id", "gacha_type", "item_id", "count",
+    "time", "name", "lang", "item_type", "rank_type"
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    Csv,
+
+    /// Star Rail Gacha Format v1.0
+    Srgf,
+
+    /// Uniformed Interchangeable GachaLog Format v4.0
+    Uigf
+}
+
+impl ExportFormat {
+    pub const LIST: &[Self] = &[
+        Self::Csv,
+        Self::Srgf,
+        Self::Uigf
+    ];
+
+    #[inline]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Srgf | Self::Uigf => "json"
+        }
+    }
+
+    /// Default name of the exported file
+    pub fn file_name(&self, uid: &str) -> String {
+        match self {
+            Self::Csv  => format!("warps_{uid}.csv"),
+            Self::Srgf => format!("warps_{uid}_srgf.json"),
+            Self::Uigf => format!("warps_{uid}_uigf.json")
+        }
+    }
+
+    /// Translation key of the format name
+    pub fn locale_key(&self) -> &'static str {
+        match self {
+            Self::Csv  => "warps-format--csv",
+            Self::Srgf => "warps-format--srgf",
+            Self::Uigf => "warps-format--uigf"
+        }
+    }
+}
+
+/// Serialize archive in the given format
+pub fn export(archive: &WarpsArchive, format: ExportFormat) -> anyhow::Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(&archive.records)),
+
+        ExportFormat::Srgf => Ok(serde_json::to_string_pretty(&json!({
+            "info": {
+                "srgf_version": "v1.0",
+                "uid": archive.uid,
+                "lang": archive_lang(archive),
+                "region_time_zone": time_zone(archive),
+                "export_timestamp": timestamp()?,
+                "export_app": EXPORT_APP,
+                "export_app_version": crate::APP_VERSION
+            },
+            "list": archive.records.iter().map(record_json).collect::<Vec<_>>()
+        }))?),
+
+        ExportFormat::Uigf => Ok(serde_json::to_string_pretty(&json!({
+            "info": {
+                "export_timestamp": timestamp()?,
+                "export_app": EXPORT_APP,
+                "export_app_version": crate::APP_VERSION,
+                "version": "v4.0"
+            },
+            "hkrpg": [{
+                "uid": archive.uid,
+                "timezone": time_zone(archive),
+                "lang": archive_lang(archive),
+                "list": archive.records.iter().map(record_json).collect::<Vec<_>>()
+            }]
+        }))?)
+    }
+}
+
+/// Write archive to the file in the given format
+pub fn export_file(archive: &WarpsArchive, format: ExportFormat, path: impl AsRef<Path>) -> anyhow::Result<()> {
+    std::fs::write(path, export(archive, format)?)?;
+
+    Ok(())
+}
+
+/// Parse exported history. Format is detected from the content
+///
+/// Returns archives with imported records. UIGF files can contain several accounts
+pub fn import(content: &str) -> anyhow::Result<Vec<WarpsArchive>> {
+    let content = content.trim_start_matches('\u{feff}');
+
+    match serde_json::from_str::<Json>(content) {
+        Ok(json) => import_json(&json),
+        Err(_) => import_csv(content)
+    }
+}
+
+/// Import history from the file and store it in the local archives
+///
+/// Returns uid and amount of new records of each imported account
+pub fn import_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<(String, usize)>> {
+    let mut imported = Vec::new();
+
+    let archives = import(&std::fs::read_to_string(path)?)?;
+
+    // Check all the accounts before storing any of them
+    if let Some(archive) = archives.iter().find(|archive| !is_valid_uid(&archive.uid)) {
+        anyhow::bail!("File contains wrong account uid: {:?}", archive.uid);
+    }
+
+    for archive in archives {
+        let mut stored = WarpsArchive::load(&archive.uid)?;
+
+        if stored.region_time_zone.is_none() {
+            stored.region_time_zone = archive.region_time_zone;
+        }
+
+        let added = stored.merge(archive.records);
+
+        stored.save()?;
+
+        tracing::info!("Imported {added} new warps of {}", stored.uid);
+
+        imported.push((stored.uid, added));
+    }
+
+    Ok(imported)
+}
+
+#[inline]
+fn timestamp() -> anyhow::Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+fn archive_lang(archive: &WarpsArchive) -> String {
+    archive.records.last()
+        .map(|record| record.lang.clone())
+        .unwrap_or_else(|| String::from("en-us"))
+}
+
+/// Get time zone of the archive records
+///
+/// Older archives don't store it so it's guessed from the server in uid
+fn time_zone(archive: &WarpsArchive) -> i64 {
+    archive.region_time_zone.unwrap_or_else(|| match archive.uid.chars().next() {
+        Some('6') => -5,
+        Some('7') => 1,
+        _ => 8
+    })
+}
+
+/// Record without account fields which are stored in the file info
+fn record_json(record: &WarpRecord) -> Json {
+    json!({
+        "gacha_id": record.gacha_id,
+        "gacha_type": record.gacha_type,
+        "item_id": record.item_id,
+        "count": record.count,
+        "time": record.time,
+        "name": record.name,
+        "item_type": record.item_type,
+        "rank_type": record.rank_type,
+        "id": record.id
+    })
+}
+
+fn to_csv(records: &[WarpRecord]) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+
+    csv.push('\n');
+
+    for record in records {
+        let fields = [
+            &record.id, &record.uid, &record.gacha_id, &record.gacha_type, &record.item_id, &record.count,
+            &record.time, &record.name, &record.lang, &record.item_type, &record.rank_type
+        ];
+
+        let line = fields.iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        csv.push_str(&line);
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split CSV content to rows of fields
+fn parse_csv(content: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            }
+
+            '"' if field.is_empty() => quoted = true,
+
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+
+            '\n' | '\r' if !quoted => {
+                if char == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+
+                row.push(std::mem::take(&mut field));
+
+                if row.iter().any(|field| !field.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+
+            _ => field.push(char)
+        }
+    }
+
+    if quoted {
+        anyhow::bail!("CSV file has unclosed quote");
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn import_csv(content: &str) -> anyhow::Result<Vec<WarpsArchive>> {
+    let mut rows = parse_csv(content)?.into_iter();
+
+    let Some(header) = rows.next() else {
+        anyhow::bail!("File is empty");
+    };
+
+    let columns = header.iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim().to_string(), i))
+        .collect::<HashMap<_, _>>();
+
+    if !["id", "uid", "gacha_type", "time"].iter().all(|column| columns.contains_key(*column)) {
+        anyhow::bail!("Unknown file format");
+    }
+
+    let mut records = Vec::new();
+
+    for row in rows {
+        let record = CSV_COLUMNS.iter()
+            .map(|column| {
+                let value = columns.get(*column)
+                    .and_then(|i| row.get(*i))
+                    .cloned()
+                    .unwrap_or_default();
+
+                (column.to_string(), Json::String(value))
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        records.push(parse_record(&Json::Object(record), None, None)?);
+    }
+
+    Ok(group_records(records, None))
+}
+
+fn import_json(json: &Json) -> anyhow::Result<Vec<WarpsArchive>> {
+    let info = &json["info"];
+
+    // UIGF v4.0
+    if let Some(accounts) = json["hkrpg"].as_array() {
+        let mut archives = Vec::new();
+
+        for account in accounts {
+            let uid = json_string(&account["uid"]);
+            let lang = json_string(&account["lang"]);
+
+            let records = parse_records(&account["list"], uid.as_deref(), lang.as_deref())?;
+
+            archives.extend(group_records(records, account["timezone"].as_i64()));
+        }
+
+        return Ok(archives);
+    }
+
+    // SRGF v1.0 and other files with records list
+    if json["list"].is_array() {
+        let uid = json_string(&info["uid"]);
+        let lang = json_string(&info["lang"]);
+
+        let records = parse_records(&json["list"], uid.as_deref(), lang.as_deref())?;
+
+        return Ok(group_records(records, info["region_time_zone"].as_i64()));
+    }
+
+    anyhow::bail!("Unknown file format")
+}
+
+fn parse_records(list: &Json, uid: Option<&str>, lang: Option<&str>) -> anyhow::Result<Vec<WarpRecord>> {
+    let Some(list) = list.as_array() else {
+        anyhow::bail!("Records list is missing");
+    };
+
+    list.iter()
+        .map(|record| parse_record(record, uid, lang))
+        .collect()
+}
+
+/// Read record fields. They can be stored either as strings or numbers
+fn parse_record(record: &Json, uid: Option<&str>, lang: Option<&str>) -> anyhow::Result<WarpRecord> {
+    let field = |name: &str| json_string(&record[name]).filter(|value| !value.is_empty());
+
+    let required = |name: &str| field(name)
+        .ok_or_else(|| anyhow::anyhow!("Record doesn't have {name} field"));
+
+    Ok(WarpRecord {
+        id: required("id")?,
+        uid: field("uid").or_else(|| uid.map(String::from)).ok_or_else(|| anyhow::anyhow!("Record doesn't have uid field"))?,
+        gacha_id: field("gacha_id").unwrap_or_default(),
+        gacha_type: required("gacha_type")?,
+        item_id: field("item_id").unwrap_or_default(),
+        count: field("count").unwrap_or_else(|| String::from("1")),
+        time: required("time")?,
+        name: field("name").unwrap_or_default(),
+        lang: field("lang").or_else(|| lang.map(String::from)).unwrap_or_default(),
+        item_type: field("item_type").unwrap_or_default(),
+        rank_type: field("rank_type").unwrap_or_else(|| String::from("3"))
+    })
+}
+
+fn json_string(value: &Json) -> Option<String> {
+    match value {
+        Json::String(value) => Some(value.clone()),
+        Json::Number(value) => Some(value.to_string()),
+
+        _ => None
+    }
+}
+
+/// Split records by accounts
+fn group_records(records: Vec<WarpRecord>, time_zone: Option<i64>) -> Vec<WarpsArchive> {
+    let mut accounts: Vec<(String, Vec<WarpRecord>)> = Vec::new();
+
+    for record in records {
+        match accounts.iter_mut().find(|(uid, _)| uid == &record.uid) {
+            Some((_, records)) => records.push(record),
+            None => accounts.push((record.uid.clone(), vec![record]))
+        }
+    }
+
+    accounts.into_iter()
+        .map(|(uid, records)| {
+            let mut archive = WarpsArchive::new(uid);
+
+            archive.region_time_zone = time_zone;
+            archive.merge(records);
+
+            archive
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, uid: &str, name: &str) -> WarpRecord {
+        WarpRecord {
+            id: id.to_string(),
+            uid: uid.to_string(),
+            gacha_id: String::from("2003"),
+            gacha_type: String::from("11"),
+            item_id: String::from("1102"),
+            count: String::from("1"),
+            time: String::from("2023-05-01 12:00:00"),
+            name: name.to_string(),
+            lang: String::from("en-us"),
+            item_type: String::from("Character"),
+            rank_type: String::from("5")
+        }
+    }
+
+    fn archive() -> WarpsArchive {
+        let mut archive = WarpsArchive::new("700000001");
+
+        archive.region_time_zone = Some(1);
+
+        archive.merge([
+            record("1683000000000000001", "700000001", "Seele"),
+            record("1683000000000000002", "700000001", "Name, with \"quotes\"\nand new line"),
+            record("1683000000000000003", "700000001", "Windows\r\nline")
+        ]);
+
+        archive
+    }
+
+    #[test]
+    fn round_trip() {
+        let archive = archive();
+
+        for format in ExportFormat::LIST.iter().copied() {
+            let imported = import(&export(&archive, format).unwrap()).unwrap();
+
+            assert_eq!(imported.len(), 1, "{format:?}");
+
+            assert_eq!(imported[0].uid, archive.uid, "{format:?}");
+            assert_eq!(imported[0].records, archive.records, "{format:?}");
+
+            // CSV doesn't store time zone
+            if format != ExportFormat::Csv {
+                assert_eq!(imported[0].region_time_zone, archive.region_time_zone, "{format:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_guessed_time_zone() {
+        let mut archive = archive();
+
+        archive.region_time_zone = None;
+
+        for format in [ExportFormat::Srgf, ExportFormat::Uigf] {
+            let imported = import(&export(&archive, format).unwrap()).unwrap();
+
+            assert_eq!(imported[0].region_time_zone, Some(1), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("Seele"), "Seele");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+
+        let row = ["plain", "a,b", "say \"hi\"", "two\nlines", "\"", ""];
+
+        let line = row.iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        assert_eq!(parse_csv(&line).unwrap(), [row.map(String::from)]);
+    }
+
+    #[test]
+    fn csv_parsing() {
+        assert_eq!(parse_csv("a,b\r\n1,2\r\n\r\n3,\"4\"").unwrap(), [
+            vec!["a", "b"],
+            vec!["1", "2"],
+            vec!["3", "4"]
+        ]);
+
+        assert!(parse_csv("a,\"b\n1,2").is_err());
+    }
+
+    #[test]
+    fn csv_columns_order() {
+        let csv = "\u{feff}time,id,gacha_type,uid\n2023-05-01 12:00:00,1683000000000000001,11,700000001\n";
+
+        let imported = import(csv).unwrap();
+
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].uid, "700000001");
+        assert_eq!(imported[0].records[0].id, "1683000000000000001");
+        assert_eq!(imported[0].records[0].rank_type, "3");
+    }
+
+    #[test]
+    fn uigf_several_accounts() {
+        let json = r#"{
+            "info": { "version": "v4.0" },
+            "hkrpg": [
+                { "uid": 700000001, "timezone": 1, "lang": "en-us", "list": [{ "id": "1", "gacha_type": "1", "time": "2023-05-01 12:00:00" }] },
+                { "uid": "100000001", "timezone": 8, "lang": "zh-cn", "list": [{ "id": 2, "gacha_type": 2, "time": "2023-05-01 12:00:00" }] }
+            ]
+        }"#;
+
+        let imported = import(json).unwrap();
+
+        assert_eq!(imported.iter().map(|archive| archive.uid.as_str()).collect::<Vec<_>>(), ["700000001", "100000001"]);
+        assert_eq!(imported.iter().map(|archive| archive.region_time_zone).collect::<Vec<_>>(), [Some(1), Some(8)]);
+        assert_eq!(imported[1].records[0].lang, "zh-cn");
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(import("{\"foo\": []}").is_err());
+        assert!(import("foo,bar\n1,2").is_err());
+        assert!(import("").is_err());
+    }
+}
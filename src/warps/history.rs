@@ -0,0 +1,542 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::anime_game_core::minreq;
+use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+use super::WARPS_FOLDER;
+
+/// Version of the archive format. Increase it on incompatible changes
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Records amount per API request. Bigger values are not allowed by the API
+const PAGE_SIZE: usize = 20;
+
+/// Delay between API requests so the API doesn't reject them
+const REQUESTS_DELAY: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GachaType {
+    /// Stellar warp
+    Regular,
+
+    /// Departure warp
+    Departure,
+
+    /// Character event warp
+    CharacterEvent,
+
+    /// Light cone event warp
+    LightConeEvent
+}
+
+impl GachaType {
+    pub const LIST: &[Self] = &[
+        Self::CharacterEvent,
+        Self::LightConeEvent,
+        Self::Regular,
+        Self::Departure
+    ];
+
+    /// Banner type used by the API
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Regular        => "1",
+            Self::Departure      => "2",
+            Self::CharacterEvent => "11",
+            Self::LightConeEvent => "12"
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::LIST.iter().find(|gacha| gacha.id() == id).copied()
+    }
+
+    /// Translation key of the banner name
+    pub fn locale_key(&self) -> &'static str {
+        match self {
+            Self::Regular        => "warp-banner--regular",
+            Self::Departure      => "warp-banner--departure",
+            Self::CharacterEvent => "warp-banner--character",
+            Self::LightConeEvent => "warp-banner--light-cone"
+        }
+    }
+}
+
+/// Warp record as returned by the API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarpRecord {
+    pub id: String,
+    pub uid: String,
+    pub gacha_id: String,
+    pub gacha_type: String,
+    pub item_id: String,
+    pub count: String,
+    pub time: String,
+    pub name: String,
+    pub lang: String,
+    pub item_type: String,
+    pub rank_type: String
+}
+
+impl WarpRecord {
+    #[inline]
+    pub fn gacha_type(&self) -> Option<GachaType> {
+        GachaType::from_id(&self.gacha_type)
+    }
+
+    /// Item rarity (3, 4 or 5 stars)
+    #[inline]
+    pub fn rank(&self) -> u8 {
+        self.rank_type.parse().unwrap_or(3)
+    }
+
+    /// Records ids are numbers increasing with time
+    #[inline]
+    fn order_key(&self) -> (usize, &str) {
+        (self.id.len(), self.id.as_str())
+    }
+}
+
+/// Locally stored warps history of one account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarpsArchive {
+    pub version: u32,
+    pub uid: String,
+
+    /// Timestamp of the last update
+    pub updated: u64,
+
+    /// Time zone of the records time. Returned by the API
+    #[serde(default)]
+    pub region_time_zone: Option<i64>,
+
+    /// Records from the oldest to the newest one
+    pub records: Vec<WarpRecord>
+}
+
+impl WarpsArchive {
+    pub fn new(uid: impl ToString) -> Self {
+        Self {
+            version: ARCHIVE_VERSION,
+            uid: uid.to_string(),
+            updated: 0,
+            region_time_zone: None,
+            records: Vec::new()
+        }
+    }
+
+    /// Get path to the archive of the account
+    ///
+    /// Fails if uid is not a number so it can't point outside the `WARPS_FOLDER`
+    pub fn path(uid: &str) -> anyhow::Result<PathBuf> {
+        if !is_valid_uid(uid) {
+            anyhow::bail!("Wrong account uid: {uid:?}");
+        }
+
+        Ok(WARPS_FOLDER.join(format!("{uid}.json")))
+    }
+
+    /// Load archive of the account or create an empty one
+    pub fn load(uid: &str) -> anyhow::Result<Self> {
+        let path = Self::path(uid)?;
+
+        if !path.exists() {
+            return Ok(Self::new(uid));
+        }
+
+        let archive = serde_json::from_slice::<Self>(&std::fs::read(path)?)?;
+
+        if archive.version > ARCHIVE_VERSION {
+            anyhow::bail!("Warps archive version {} is not supported. Please update the launcher", archive.version);
+        }
+
+        Ok(archive)
+    }
+
+    /// List stored archives from the recently updated one
+    pub fn list() -> anyhow::Result<Vec<Self>> {
+        let mut archives = Vec::new();
+
+        if WARPS_FOLDER.exists() {
+            for entry in WARPS_FOLDER.read_dir()?.flatten() {
+                let path = entry.path();
+
+                if path.extension().map(|ext| ext == "json").unwrap_or_default() {
+                    if let Some(uid) = path.file_stem() {
+                        let uid = uid.to_string_lossy();
+
+                        if is_valid_uid(&uid) {
+                            archives.push(Self::load(&uid)?);
+                        }
+                    }
+                }
+            }
+        }
+
+        archives.sort_by(|a, b| b.updated.cmp(&a.updated));
+
+        Ok(archives)
+    }
+
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        if !WARPS_FOLDER.exists() {
+            std::fs::create_dir_all(WARPS_FOLDER.as_path())?;
+        }
+
+        self.updated = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        std::fs::write(Self::path(&self.uid)?, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    /// Add new records to the archive
+    ///
+    /// Returns amount of added records
+    pub fn merge(&mut self, records: impl IntoIterator<Item = WarpRecord>) -> usize {
+        let mut ids = self.records.iter()
+            .map(|record| record.id.clone())
+            .collect::<HashSet<_>>();
+
+        let old_len = self.records.len();
+
+        for record in records {
+            if ids.insert(record.id.clone()) {
+                self.records.push(record);
+            }
+        }
+
+        self.records.sort_by(|a, b| a.order_key().cmp(&b.order_key()));
+
+        self.records.len() - old_len
+    }
+
+    /// Get records of the banner from the oldest to the newest one
+    pub fn banner(&self, gacha_type: GachaType) -> Vec<&WarpRecord> {
+        self.records.iter()
+            .filter(|record| record.gacha_type() == Some(gacha_type))
+            .collect()
+    }
+}
+
+/// Check that account uid contains only digits
+pub fn is_valid_uid(uid: &str) -> bool {
+    !uid.is_empty() && uid.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn api_uri(edition: GameEdition) -> &'static str {
+    match edition {
+        GameEdition::Global => concat!("https://api-os-takumi.mi", "ho", "yo.com/common/gacha_record/api/getGachaLog"),
+        GameEdition::China  => concat!("https://api-takumi.mi", "ho", "yo.com/common/gacha_record/api/getGachaLog")
+    }
+}
+
+/// Get authorization params from the warps URL
+fn auth_query(url: &str) -> anyhow::Result<String> {
+    let Some((_, query)) = url.split_once('?') else {
+        anyhow::bail!("Warps URL doesn't contain query params");
+    };
+
+    let query = query.split('#').next().unwrap_or_default();
+
+    let query = query.split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+
+            !["gacha_type", "page", "size", "end_id"].contains(&name)
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    if !query.contains("authkey=") {
+        anyhow::bail!("Warps URL doesn't contain authkey");
+    }
+
+    Ok(query)
+}
+
+/// Request one page of the banner history
+/// 
+/// Returns records and their time zone
+fn fetch_page(edition: GameEdition, query: &str, gacha_type: GachaType, end_id: &str) -> anyhow::Result<(Vec<WarpRecord>, Option<i64>)> {
+    let uri = format!("{}?{query}&gacha_type={}&page=1&size={PAGE_SIZE}&end_id={end_id}", api_uri(edition), gacha_type.id());
+
+    let response = serde_json::from_slice::<serde_json::Value>(minreq::get(uri).send()?.as_bytes())?;
+
+    if response["retcode"].as_i64() != Some(0) {
+        anyhow::bail!("API error: {}", response["message"].as_str().unwrap_or("unknown error"));
+    }
+
+    let records = serde_json::from_value(response["data"]["list"].clone())?;
+
+    Ok((records, response["data"]["region_time_zone"].as_i64()))
+}
+
+/// Fetch records of one banner which are newer than the stored ones
+///
+/// `fetch_page` is called with id of the last fetched record (`0` for the first page).
+/// `newest_stored` is called once with the first page and returns id of the newest stored record.
+/// `updater` is called with amount of fetched records after each page
+///
+/// Records are returned from the newest to the oldest one
+fn fetch_new_records(
+    mut fetch_page: impl FnMut(&str) -> anyhow::Result<Vec<WarpRecord>>,
+    newest_stored: impl FnOnce(&[WarpRecord]) -> anyhow::Result<Option<String>>,
+    updater: impl Fn(usize)
+) -> anyhow::Result<Vec<WarpRecord>> {
+    let mut end_id = String::from("0");
+    let mut records = Vec::new();
+
+    let mut newest_stored = Some(newest_stored);
+    let mut stored_id = None;
+
+    loop {
+        let page = fetch_page(&end_id)?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+
+        // API returned the same page again
+        if last.id == end_id {
+            break;
+        }
+
+        end_id = last.id.clone();
+
+        if let Some(newest_stored) = newest_stored.take() {
+            stored_id = newest_stored(&page)?;
+        }
+
+        let full_page = page.len() == PAGE_SIZE;
+
+        for record in page {
+            if Some(&record.id) == stored_id.as_ref() {
+                updater(records.len());
+
+                return Ok(records);
+            }
+
+            records.push(record);
+        }
+
+        updater(records.len());
+
+        if !full_page {
+            break;
+        }
+    }
+
+    Ok(records)
+}
+
+/// Fetch new records of all the banners and store them in the archive
+///
+/// Fetching of each banner stops on the record which is already stored.
+/// `updater` is called with the current banner and amount of fetched records
+///
+/// Returns updated archive and amount of new records,
+/// or `None` if the account has no warps
+pub fn update(url: &str, edition: GameEdition, updater: impl Fn(GachaType, usize)) -> anyhow::Result<Option<(WarpsArchive, usize)>> {
+    let query = auth_query(url)?;
+
+    let mut archive: Option<WarpsArchive> = None;
+    let mut added = 0;
+
+    for gacha_type in GachaType::LIST.iter().copied() {
+        let mut time_zone = None;
+
+        updater(gacha_type, 0);
+
+        let records = fetch_new_records(
+            |end_id| {
+                if end_id != "0" {
+                    std::thread::sleep(REQUESTS_DELAY);
+                }
+
+                let (page, page_time_zone) = fetch_page(edition, &query, gacha_type, end_id)?;
+
+                time_zone = time_zone.or(page_time_zone);
+
+                Ok(page)
+            },
+
+            |page| {
+                if archive.is_none() {
+                    archive = Some(WarpsArchive::load(&page[0].uid)?);
+                }
+
+                Ok(archive.as_ref()
+                    .and_then(|archive| archive.banner(gacha_type).last().map(|record| record.id.clone())))
+            },
+
+            |fetched| updater(gacha_type, fetched)
+        )?;
+
+        if let Some(archive) = &mut archive {
+            if time_zone.is_some() {
+                archive.region_time_zone = time_zone;
+            }
+
+            added += archive.merge(records);
+        }
+    }
+
+    match archive {
+        Some(mut archive) => {
+            archive.save()?;
+
+            tracing::info!("Fetched {added} new warps of {}", archive.uid);
+
+            Ok(Some((archive, added)))
+        }
+
+        None => Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: u64, gacha_type: GachaType, rank: u8) -> WarpRecord {
+        WarpRecord {
+            id: id.to_string(),
+            uid: String::from("100000001"),
+            gacha_id: String::from("1001"),
+            gacha_type: gacha_type.id().to_string(),
+            item_id: String::from("20000"),
+            count: String::from("1"),
+            time: String::from("2023-05-01 12:00:00"),
+            name: format!("Item {id}"),
+            lang: String::from("en-us"),
+            item_type: String::from("Light Cone"),
+            rank_type: rank.to_string()
+        }
+    }
+
+    /// Records from the newest to the oldest one, like the API returns them
+    fn records(ids: impl DoubleEndedIterator<Item = u64>) -> Vec<WarpRecord> {
+        ids.rev().map(|id| record(id, GachaType::Regular, 3)).collect()
+    }
+
+    /// Serve pages by `end_id` and remember requested ones
+    fn fetch_from<'a>(pages: &'a [Vec<WarpRecord>], requested: &'a mut Vec<String>) -> impl FnMut(&str) -> anyhow::Result<Vec<WarpRecord>> + 'a {
+        move |end_id| {
+            requested.push(end_id.to_string());
+
+            let page = match end_id {
+                "0" => pages.first(),
+
+                end_id => pages.iter()
+                    .position(|page| page.last().map(|record| record.id.as_str()) == Some(end_id))
+                    .and_then(|i| pages.get(i + 1))
+            };
+
+            Ok(page.cloned().unwrap_or_default())
+        }
+    }
+
+    fn ids(records: &[WarpRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.id.as_str()).collect()
+    }
+
+    #[test]
+    fn merge_deduplicates_by_id() {
+        let mut archive = WarpsArchive::new("100000001");
+
+        assert_eq!(archive.merge(records(1..=3)), 3);
+        assert_eq!(archive.merge(records(2..=4)), 1);
+        assert_eq!(archive.merge([record(4, GachaType::Regular, 3), record(4, GachaType::Regular, 3)]), 0);
+
+        assert_eq!(ids(&archive.records), ["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn merge_sorts_numeric_ids() {
+        let mut archive = WarpsArchive::new("100000001");
+
+        archive.merge([record(10, GachaType::Regular, 3), record(9, GachaType::Regular, 3), record(100, GachaType::Regular, 3)]);
+
+        assert_eq!(ids(&archive.records), ["9", "10", "100"]);
+    }
+
+    #[test]
+    fn overlapping_pages() {
+        // Second page starts with the last record of the first one
+        let pages = [records(81..=100), records(70..=81)];
+        let mut requested = Vec::new();
+
+        let fetched = fetch_new_records(fetch_from(&pages, &mut requested), |_| Ok(None), |_| ()).unwrap();
+
+        assert_eq!(fetched.len(), 32);
+        assert_eq!(requested, ["0", "81"]);
+
+        let mut archive = WarpsArchive::new("100000001");
+
+        assert_eq!(archive.merge(fetched), 31);
+        assert_eq!(ids(&archive.records), (70..=100).map(|id| id.to_string()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stop_on_newest_stored() {
+        let pages = [records(81..=100), records(61..=80), records(41..=60)];
+
+        // Stored record is on the first page
+        let mut requested = Vec::new();
+
+        let fetched = fetch_new_records(fetch_from(&pages, &mut requested), |_| Ok(Some(String::from("95"))), |_| ()).unwrap();
+
+        assert_eq!(ids(&fetched), ["100", "99", "98", "97", "96"]);
+        assert_eq!(requested, ["0"]);
+
+        // Stored record is on the second page
+        let mut requested = Vec::new();
+
+        let fetched = fetch_new_records(fetch_from(&pages, &mut requested), |_| Ok(Some(String::from("70"))), |_| ()).unwrap();
+
+        assert_eq!(fetched.len(), 30);
+        assert_eq!(fetched.last().map(|record| record.id.as_str()), Some("71"));
+        assert_eq!(requested, ["0", "81"]);
+    }
+
+    #[test]
+    fn stop_on_last_page() {
+        let pages = [records(81..=100), records(71..=80)];
+        let mut requested = Vec::new();
+
+        let fetched = fetch_new_records(fetch_from(&pages, &mut requested), |_| Ok(None), |_| ()).unwrap();
+
+        // Second page is not full so the third one is not requested
+        assert_eq!(fetched.len(), 30);
+        assert_eq!(requested, ["0", "81"]);
+
+        // Empty history
+        let mut requested = Vec::new();
+
+        let fetched = fetch_new_records(fetch_from(&[], &mut requested), |_| Ok(None), |_| ()).unwrap();
+
+        assert!(fetched.is_empty());
+        assert_eq!(requested, ["0"]);
+    }
+
+    #[test]
+    fn stop_on_repeated_page() {
+        let page = records(81..=100);
+        let mut requests = 0;
+
+        let fetched = fetch_new_records(|_| {
+            requests += 1;
+
+            Ok(page.clone())
+        }, |_| Ok(None), |_| ()).unwrap();
+
+        assert_eq!(fetched.len(), 20);
+        assert_eq!(requests, 2);
+    }
+}
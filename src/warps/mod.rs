@@ -0,0 +1,10 @@
+pub mod web_cache;
+pub mod url;
+pub mod history;
+pub mod stats;
+pub mod export;
+
+lazy_static::lazy_static! {
+    /// Path to warps history archives folder. Standard is `$HOME/.local/share/anime-game-launcher/warps`
+    pub static ref WARPS_FOLDER: std::path::PathBuf = crate::LAUNCHER_FOLDER.join("warps");
+}
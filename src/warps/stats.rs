@@ -0,0 +1,159 @@
+use super::history::{GachaType, WarpsArchive};
+
+/// 5 star item with amount of warps spent on it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiveStarPull {
+    pub name: String,
+    pub time: String,
+    pub pulls: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannerStats {
+    pub gacha_type: GachaType,
+
+    /// Total amount of warps
+    pub total: usize,
+
+    /// Warps made since the last 5 star item
+    pub pity_5: usize,
+
+    /// Warps made since the last 4 star item
+    pub pity_4: usize,
+
+    pub four_stars: usize,
+
+    /// 5 star items from the newest one
+    pub five_stars: Vec<FiveStarPull>
+}
+
+impl BannerStats {
+    pub fn new(archive: &WarpsArchive, gacha_type: GachaType) -> Self {
+        let mut stats = Self {
+            gacha_type,
+            total: 0,
+            pity_5: 0,
+            pity_4: 0,
+            four_stars: 0,
+            five_stars: Vec::new()
+        };
+
+        for record in archive.banner(gacha_type) {
+            stats.total += 1;
+            stats.pity_5 += 1;
+            stats.pity_4 += 1;
+
+            match record.rank() {
+                5 => {
+                    stats.five_stars.push(FiveStarPull {
+                        name: record.name.clone(),
+                        time: record.time.clone(),
+                        pulls: stats.pity_5
+                    });
+
+                    stats.pity_5 = 0;
+                    stats.pity_4 = 0;
+                }
+
+                4 => {
+                    stats.four_stars += 1;
+                    stats.pity_4 = 0;
+                }
+
+                _ => ()
+            }
+        }
+
+        stats.five_stars.reverse();
+
+        stats
+    }
+
+    /// Get stats of all the banners
+    pub fn all(archive: &WarpsArchive) -> Vec<Self> {
+        GachaType::LIST.iter()
+            .map(|gacha_type| Self::new(archive, *gacha_type))
+            .collect()
+    }
+
+    /// Average amount of warps per 5 star item
+    pub fn average_5(&self) -> Option<f64> {
+        if self.five_stars.is_empty() {
+            return None;
+        }
+
+        let pulls = self.five_stars.iter()
+            .map(|pull| pull.pulls)
+            .sum::<usize>();
+
+        Some(pulls as f64 / self.five_stars.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::history::WarpRecord;
+
+    fn archive(gacha_type: GachaType, ranks: &[u8]) -> WarpsArchive {
+        let mut archive = WarpsArchive::new("100000001");
+
+        archive.merge(ranks.iter().enumerate().map(|(i, rank)| WarpRecord {
+            id: (1000 + i).to_string(),
+            uid: String::from("100000001"),
+            gacha_id: String::from("1001"),
+            gacha_type: gacha_type.id().to_string(),
+            item_id: String::from("20000"),
+            count: String::from("1"),
+            time: format!("2023-05-01 12:00:{i:02}"),
+            name: format!("Item {i}"),
+            lang: String::from("en-us"),
+            item_type: String::from("Character"),
+            rank_type: rank.to_string()
+        }));
+
+        archive
+    }
+
+    #[test]
+    fn pity_resets_on_five_star() {
+        let stats = BannerStats::new(&archive(GachaType::CharacterEvent, &[3, 4, 3, 5, 3, 3, 4, 3, 3]), GachaType::CharacterEvent);
+
+        assert_eq!(stats.total, 9);
+        assert_eq!(stats.pity_5, 5);
+        assert_eq!(stats.pity_4, 2);
+        assert_eq!(stats.four_stars, 2);
+
+        assert_eq!(stats.five_stars, [FiveStarPull {
+            name: String::from("Item 3"),
+            time: String::from("2023-05-01 12:00:03"),
+            pulls: 4
+        }]);
+
+        // 4 star pity is reset by 5 star item as well
+        let stats = BannerStats::new(&archive(GachaType::CharacterEvent, &[3, 3, 5, 3]), GachaType::CharacterEvent);
+
+        assert_eq!(stats.pity_5, 1);
+        assert_eq!(stats.pity_4, 1);
+    }
+
+    #[test]
+    fn five_stars_from_the_newest() {
+        let stats = BannerStats::new(&archive(GachaType::Regular, &[5, 3, 3, 5, 3]), GachaType::Regular);
+
+        assert_eq!(stats.five_stars.iter().map(|pull| pull.pulls).collect::<Vec<_>>(), [3, 1]);
+        assert_eq!(stats.average_5(), Some(2.0));
+        assert_eq!(stats.pity_5, 1);
+    }
+
+    #[test]
+    fn banners_are_counted_separately() {
+        let archive = archive(GachaType::Regular, &[3, 5, 3]);
+
+        let stats = BannerStats::new(&archive, GachaType::Departure);
+
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.pity_5, 0);
+        assert_eq!(stats.average_5(), None);
+    }
+}
@@ -0,0 +1,276 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anime_launcher_sdk::anime_game_core::star_rail::consts::GameEdition;
+
+use anime_launcher_sdk::config::ConfigExt;
+use anime_launcher_sdk::star_rail::config::Config;
+
+use super::web_cache::{self, CacheEntry};
+
+/// Authorization key of the warps URL is valid for 24 hours
+pub const URL_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarpUrl {
+    pub url: String,
+
+    /// Time when the URL was cached by the game, in microseconds since Unix epoch
+    pub time: u64
+}
+
+impl WarpUrl {
+    /// Time passed since the URL was cached
+    pub fn age(&self) -> Duration {
+        let cached = UNIX_EPOCH + Duration::from_micros(self.time);
+
+        SystemTime::now().duration_since(cached).unwrap_or_default()
+    }
+
+    /// Time left before the URL's authorization key will expire.
+    /// `None` if it's expired already
+    #[inline]
+    pub fn expires_in(&self) -> Option<Duration> {
+        URL_LIFETIME.checked_sub(self.age()).filter(|left| !left.is_zero())
+    }
+
+    /// URL to open in the browser
+    pub fn page_url(&self) -> String {
+        if self.url.contains('#') {
+            self.url.clone()
+        } else {
+            format!("{}#/log", self.url)
+        }
+    }
+}
+
+/// Get game's web cache folders
+///
+/// Older game versions store cache in `webCaches/Cache`,
+/// newer ones in `webCaches/<game version>/Cache`
+fn cache_folders(web_caches: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![web_caches.join("Cache/Cache_Data")];
+
+    if let Ok(entries) = web_caches.read_dir() {
+        for entry in entries.flatten() {
+            folders.push(entry.path().join("Cache/Cache_Data"));
+        }
+    }
+
+    folders.into_iter()
+        .filter(|folder| folder.is_dir())
+        .collect()
+}
+
+/// Check that the URL is the warps history page or API URL
+/// and contains authorization params
+pub fn is_valid(url: &str) -> bool {
+    let Some((path, query)) = url.split_once('?') else {
+        return false;
+    };
+
+    if !path.starts_with("https://") || !path.to_ascii_lowercase().contains("gacha") {
+        return false;
+    }
+
+    let mut authkey = false;
+    let mut authkey_ver = false;
+
+    for param in query.split('#').next().unwrap_or_default().split('&') {
+        let (name, value) = param.split_once('=').unwrap_or((param, ""));
+
+        match name {
+            "authkey" => authkey = !value.is_empty(),
+            "authkey_ver" => authkey_ver = !value.is_empty(),
+
+            // Don't pick URLs of other games sharing the cache
+            "game_biz" if !value.starts_with("hkrpg") => return false,
+
+            _ => ()
+        }
+    }
+
+    authkey && authkey_ver
+}
+
+/// Get warps URL from the cache entry key
+///
+/// Newer Chromium versions prefix keys with network isolation info
+/// separated by spaces, so the URL is the last part of the key
+pub fn from_cache_key(key: &str) -> Option<String> {
+    let key = key.split_whitespace().last()?;
+    let url = &key[key.find("https://")?..];
+
+    let url = url.trim_end_matches('\0');
+
+    is_valid(url).then(|| url.to_string())
+}
+
+/// Find the newest valid warps URL in the cache entries
+pub fn find_in_entries(mut entries: Vec<CacheEntry>) -> Option<WarpUrl> {
+    entries.sort_by(|a, b| b.time.cmp(&a.time));
+
+    entries.iter().find_map(|entry| {
+        Some(WarpUrl {
+            url: from_cache_key(&entry.key)?,
+            time: entry.time
+        })
+    })
+}
+
+/// Find warps history URL in the game's web cache
+pub fn find(game_path: &Path, edition: GameEdition) -> anyhow::Result<Option<WarpUrl>> {
+    let web_caches = game_path
+        .join(edition.data_folder())
+        .join("webCaches");
+
+    let folders = cache_folders(&web_caches);
+
+    if folders.is_empty() {
+        anyhow::bail!("Cache folder doesn't exist");
+    }
+
+    let mut entries = Vec::new();
+
+    for folder in folders {
+        match web_cache::read_entries(&folder) {
+            Ok(folder_entries) => entries.extend(folder_entries),
+            Err(err) => tracing::warn!("Failed to read web cache {:?}: {err}", folder)
+        }
+    }
+
+    tracing::debug!("Found {} web cache entries", entries.len());
+
+    Ok(find_in_entries(entries))
+}
+
+/// Find warps history URL of the game from the config
+pub fn get() -> anyhow::Result<Option<WarpUrl>> {
+    let config = Config::get()?;
+
+    find(config.game.path.for_edition(config.launcher.edition), config.launcher.edition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUERY: &str = "authkey_ver=1&sign_type=2&authkey=abc%2Bdef&game_biz=hkrpg_global&lang=en";
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros() as u64
+    }
+
+    fn entry(key: impl ToString, time: u64) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            time
+        }
+    }
+
+    #[test]
+    fn validate_urls() {
+        let cases = [
+            (format!("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?{QUERY}"), true),
+            (format!("https://webstatic-sea.hoyoverse.com/hkrpg/event/e20211215gacha-v2/index.html?{QUERY}#/log"), true),
+            (String::from("https://webstatic-sea.hoyoverse.com/hkrpg/event/e20211215gacha-v2/index.html?authkey_ver=1&game_biz=hkrpg_global"), false),
+            (String::from("https://webstatic-sea.hoyoverse.com/hkrpg/event/e20211215gacha-v2/index.html?authkey=&authkey_ver=1"), false),
+            (String::from("https://webstatic-sea.hoyoverse.com/hkrpg/event/e20211215gacha-v2/index.html?authkey=abc"), false),
+            (String::from("https://hk4e-api-os.hoyoverse.com/event/gacha_info/api/getGachaLog?authkey_ver=1&authkey=abc&game_biz=hk4e_global"), false),
+            (format!("https://sg-public-api.hoyoverse.com/event/announcement/index.html?{QUERY}"), false),
+            (format!("http://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?{QUERY}"), false),
+            (String::from("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog"), false)
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(is_valid(&url), expected, "url: {url}");
+        }
+    }
+
+    #[test]
+    fn url_from_cache_key() {
+        let url = format!("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?{QUERY}");
+
+        assert_eq!(from_cache_key(&url), Some(url.clone()));
+        assert_eq!(from_cache_key(&format!("1/0/_dk_https://mihoyo.com https://mihoyo.com {url}")), Some(url.clone()));
+        assert_eq!(from_cache_key(&format!("{url}\0\0")), Some(url.clone()));
+        assert_eq!(from_cache_key(&format!("1/0/{url}")), Some(url.clone()));
+        assert_eq!(from_cache_key(&format!("{url} https://mihoyo.com")), None);
+        assert_eq!(from_cache_key(""), None);
+    }
+
+    #[test]
+    fn find_newest_url_in_cache() {
+        let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/web_cache/valid");
+
+        let url = find_in_entries(web_cache::read_entries(&folder).unwrap()).unwrap();
+
+        // Newer entries without authkey, of other game and deleted one are skipped
+        assert_eq!(url.url, format!("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?{QUERY}&gacha_type=11"));
+        assert_eq!(url.time, 1_680_000_060_000_000);
+
+        // Cache entries are too old
+        assert_eq!(url.expires_in(), None);
+    }
+
+    #[test]
+    fn find_in_broken_cache() {
+        let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/web_cache");
+
+        assert_eq!(find_in_entries(web_cache::read_entries(&folder.join("corrupt")).unwrap()), None);
+
+        let url = find_in_entries(web_cache::read_entries(&folder.join("truncated")).unwrap()).unwrap();
+
+        assert!(url.url.ends_with("&gacha_type=1"));
+    }
+
+    #[test]
+    fn skip_expired_and_invalid_urls() {
+        let now = now();
+        let hour = 60 * 60 * 1_000_000;
+
+        let url = format!("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?{QUERY}");
+
+        let entries = vec![
+            entry(format!("{url}&expired"), now - 30 * hour),
+            entry(format!("{url}&fresh"), now - hour),
+            entry("https://api-os-takumi.mihoyo.com/common/gacha_record/api/getGachaLog?authkey_ver=1&game_biz=hkrpg_global", now - hour / 2),
+            entry("https://mihoyo.com/favicon.ico", now)
+        ];
+
+        let found = find_in_entries(entries).unwrap();
+
+        assert_eq!(found.url, format!("{url}&fresh"));
+
+        let expires_in = found.expires_in().unwrap();
+
+        assert!(expires_in <= URL_LIFETIME - Duration::from_secs(60 * 60));
+        assert!(expires_in > URL_LIFETIME - Duration::from_secs(2 * 60 * 60));
+
+        // Only expired URL is left
+        let found = find_in_entries(vec![
+            entry(format!("{url}&expired"), now - 30 * hour),
+            entry("https://mihoyo.com/favicon.ico", now)
+        ]).unwrap();
+
+        assert_eq!(found.url, format!("{url}&expired"));
+        assert_eq!(found.expires_in(), None);
+    }
+
+    #[test]
+    fn page_url() {
+        let url = WarpUrl {
+            url: format!("https://webstatic-sea.hoyoverse.com/hkrpg/event/e20211215gacha-v2/index.html?{QUERY}"),
+            time: now()
+        };
+
+        assert!(url.page_url().ends_with("#/log"));
+
+        let url = WarpUrl {
+            url: format!("{}#/log", url.url),
+            time: url.time
+        };
+
+        assert_eq!(url.page_url(), url.url);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Magic number of the block files
+const BLOCK_MAGIC: u32 = 0xC104CAC3;
+
+/// Size of the block files header
+const BLOCK_HEADER_SIZE: usize = 8192;
+
+/// Size of `EntryStore` struct
+const ENTRY_SIZE: usize = 256;
+
+/// Offset of the inline key in `EntryStore` struct
+const ENTRY_KEY_OFFSET: usize = 96;
+
+/// Entries are stored in up to 4 blocks
+const ENTRY_MAX_BLOCKS: usize = 4;
+
+/// Keys longer than this are surely broken
+const MAX_KEY_LENGTH: usize = 64 * 1024;
+
+/// Magic number of the simple cache entry files
+const SIMPLE_MAGIC: u64 = 0xfcfb6d1ba7725c30;
+
+/// Size of `SimpleFileHeader` struct
+const SIMPLE_HEADER_SIZE: usize = 24;
+
+/// Microseconds between Windows (1601) and Unix (1970) epochs
+const WINDOWS_EPOCH_OFFSET: u64 = 11_644_473_600_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// Entry key. Contains requested URL
+    pub key: String,
+
+    /// Creation time in microseconds since Unix epoch
+    pub time: u64
+}
+
+/// Read entries of the Chromium disk cache folder used by the game's web browser
+///
+/// Both backends are supported: block files (`data_N`, `f_XXXXXX`)
+/// and simple cache (`<hash>_0` files). Malformed entries are skipped
+pub fn read_entries(folder: &Path) -> anyhow::Result<Vec<CacheEntry>> {
+    let mut entries = Vec::new();
+    let mut block_files = HashMap::new();
+
+    for entry in folder.read_dir()?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if let Some(number) = name.strip_prefix("data_") {
+            let Ok(number) = number.parse::<u32>() else {
+                continue;
+            };
+
+            match std::fs::read(entry.path()) {
+                Ok(data) => {
+                    block_files.insert(number, data);
+                }
+
+                Err(err) => tracing::warn!("Failed to read cache file {name}: {err}")
+            }
+        }
+
+        else if is_simple_entry_name(&name) {
+            match read_simple_entry(&entry.path()) {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) => (),
+
+                Err(err) => tracing::warn!("Failed to read cache file {name}: {err}")
+            }
+        }
+    }
+
+    entries.extend(parse_block_files(&block_files, |number| {
+        std::fs::read(folder.join(format!("f_{number:06x}"))).ok()
+    }));
+
+    Ok(entries)
+}
+
+/// Parse entries of the block files
+///
+/// `block_files` contains content of `data_N` files by their number.
+/// `external` reads content of the `f_XXXXXX` file by its number
+pub fn parse_block_files(block_files: &HashMap<u32, Vec<u8>>, external: impl Fn(u32) -> Option<Vec<u8>>) -> Vec<CacheEntry> {
+    let mut entries = Vec::new();
+
+    for data in block_files.values() {
+        if read_u32(data, 0) != Some(BLOCK_MAGIC) || read_u32(data, 12) != Some(ENTRY_SIZE as u32) {
+            continue;
+        }
+
+        let mut offset = BLOCK_HEADER_SIZE;
+
+        while offset + ENTRY_SIZE <= data.len() {
+            if let Some(entry) = parse_entry(data, offset, block_files, &external) {
+                entries.push(entry);
+            }
+
+            offset += ENTRY_SIZE;
+        }
+    }
+
+    entries
+}
+
+/// Parse `EntryStore` struct
+fn parse_entry(data: &[u8], offset: usize, block_files: &HashMap<u32, Vec<u8>>, external: &impl Fn(u32) -> Option<Vec<u8>>) -> Option<CacheEntry> {
+    // Only ENTRY_NORMAL entries are valid
+    if read_u32(data, offset + 20)? != 0 {
+        return None;
+    }
+
+    let time = read_u64(data, offset + 24)?;
+    let key_len = read_u32(data, offset + 32)? as usize;
+    let long_key = read_u32(data, offset + 36)?;
+
+    if key_len == 0 || key_len > MAX_KEY_LENGTH {
+        return None;
+    }
+
+    let key = if long_key != 0 {
+        read_address(long_key, block_files, external)?
+            .get(..key_len)?
+            .to_vec()
+    }
+
+    else {
+        if key_len > ENTRY_SIZE * ENTRY_MAX_BLOCKS - ENTRY_KEY_OFFSET {
+            return None;
+        }
+
+        data.get(offset + ENTRY_KEY_OFFSET..offset + ENTRY_KEY_OFFSET + key_len)?.to_vec()
+    };
+
+    Some(CacheEntry {
+        key: String::from_utf8(key).ok()?,
+        time: time.checked_sub(WINDOWS_EPOCH_OFFSET)?
+    })
+}
+
+/// Read data stored by the cache address
+fn read_address(address: u32, block_files: &HashMap<u32, Vec<u8>>, external: &impl Fn(u32) -> Option<Vec<u8>>) -> Option<Vec<u8>> {
+    // Address is not initialized
+    if address & 0x8000_0000 == 0 {
+        return None;
+    }
+
+    let block_size = match (address >> 28) & 0x7 {
+        0 => return external(address & 0x0FFF_FFFF),
+
+        1 => 36,
+        2 => 256,
+        3 => 1024,
+        4 => 4096,
+
+        _ => return None
+    };
+
+    let blocks = ((address >> 24) & 0x3) as usize + 1;
+    let file = (address >> 16) & 0xFF;
+    let start = (address & 0xFFFF) as usize;
+
+    let offset = BLOCK_HEADER_SIZE + start * block_size;
+
+    block_files.get(&file)?
+        .get(offset..offset + blocks * block_size)
+        .map(|data| data.to_vec())
+}
+
+/// Simple cache entries are stored in `<16 hex digits>_0` files
+fn is_simple_entry_name(name: &str) -> bool {
+    match name.split_once('_') {
+        Some((hash, "0")) => hash.len() == 16 && hash.chars().all(|char| char.is_ascii_hexdigit()),
+        _ => false
+    }
+}
+
+/// Read key of the simple cache entry. Its modification time is used as creation time
+fn read_simple_entry(path: &Path) -> anyhow::Result<Option<CacheEntry>> {
+    let data = std::fs::read(path)?;
+
+    let Some(key) = parse_simple_entry(&data) else {
+        return Ok(None);
+    };
+
+    let time = std::fs::metadata(path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)?
+        .as_micros() as u64;
+
+    Ok(Some(CacheEntry { key, time }))
+}
+
+/// Parse key of the simple cache entry file
+pub fn parse_simple_entry(data: &[u8]) -> Option<String> {
+    if read_u64(data, 0)? != SIMPLE_MAGIC {
+        return None;
+    }
+
+    let key_len = read_u32(data, 12)? as usize;
+
+    if key_len == 0 || key_len > MAX_KEY_LENGTH {
+        return None;
+    }
+
+    let key = data.get(SIMPLE_HEADER_SIZE..SIMPLE_HEADER_SIZE + key_len)?;
+
+    String::from_utf8(key.to_vec()).ok()
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+#[inline]
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Creation time of the fixture entries
+    const TIME: u64 = 1_680_000_000_000_000;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/web_cache")
+            .join(name)
+    }
+
+    #[test]
+    fn read_valid_cache() {
+        let entries = read_entries(&fixture("valid")).unwrap();
+
+        // Deleted entry is skipped
+        assert_eq!(entries.len(), 6);
+        assert!(!entries.iter().any(|entry| entry.key.contains("&deleted")));
+
+        // Inline key
+        let entry = entries.iter().find(|entry| entry.key.ends_with("&gacha_type=11")).unwrap();
+
+        assert!(entry.key.starts_with("https://"));
+        assert_eq!(entry.time, TIME + 60_000_000);
+
+        // Long key stored in other block file
+        let entry = entries.iter().find(|entry| entry.key.starts_with("1/0/_dk_")).unwrap();
+
+        assert!(entry.key.ends_with("#/log"));
+        assert_eq!(entry.time, TIME - 60_000_000);
+
+        // Long key stored in external file
+        assert!(entries.iter().any(|entry| entry.key.ends_with(&"y".repeat(200))));
+
+        // Simple cache entry
+        assert!(entries.iter().any(|entry| entry.key.ends_with("game_biz=hkrpg_global")));
+    }
+
+    #[test]
+    fn read_truncated_cache() {
+        let entries = read_entries(&fixture("truncated")).unwrap();
+
+        // Only entries which were written completely are read
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry.key.ends_with("&gacha_type=1") && entry.time == TIME));
+    }
+
+    #[test]
+    fn read_corrupt_cache() {
+        assert_eq!(read_entries(&fixture("corrupt")).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn parse_truncated_files() {
+        let folder = fixture("valid");
+
+        let block_file = std::fs::read(folder.join("data_1")).unwrap();
+        let long_keys = std::fs::read(folder.join("data_2")).unwrap();
+        let simple_entry = std::fs::read(folder.join("0123456789abcdef_0")).unwrap();
+
+        for len in 0..=block_file.len() {
+            let block_files = HashMap::from([
+                (1, block_file[..len].to_vec()),
+                (2, long_keys[..len.min(long_keys.len())].to_vec())
+            ]);
+
+            let entries = parse_block_files(&block_files, |_| None);
+
+            assert!(entries.len() <= (len.saturating_sub(BLOCK_HEADER_SIZE)) / ENTRY_SIZE);
+        }
+
+        for len in 0..simple_entry.len() {
+            let key = parse_simple_entry(&simple_entry[..len]);
+
+            assert_eq!(key.is_some(), len >= SIMPLE_HEADER_SIZE + 104, "length: {len}");
+        }
+    }
+}